@@ -1,3 +1,5 @@
+use crate::commands::error::AppError;
+use crate::db::schema::Engine;
 use crate::models::*;
 use crate::services::{EngineService, download::nwjs};
 use std::sync::Arc;
@@ -7,11 +9,12 @@ use tokio::sync::Mutex;
 /// 引擎状态
 pub struct EngineState {
     pub engine_service: Arc<Mutex<EngineService>>,
+    pub db: Arc<Mutex<toasty::Db>>,
 }
 
 /// 获取所有引擎
 #[tauri::command]
-pub async fn get_engines(state: State<'_, EngineState>) -> Result<Vec<EngineDto>, String> {
+pub async fn get_engines(state: State<'_, EngineState>) -> Result<Vec<EngineDto>, AppError> {
     let service = state.engine_service.lock().await;
     let engines = service.get_all_engines().await?;
     let dtos = engines.into_iter().map(|e| service.to_dto(e)).collect();
@@ -24,7 +27,7 @@ pub async fn find_engine(
     engine_type: String,
     version: Option<String>,
     state: State<'_, EngineState>,
-) -> Result<Option<EngineDto>, String> {
+) -> Result<Option<EngineDto>, AppError> {
     let service = state.engine_service.lock().await;
     let engine = service
         .find_engine(&engine_type, version.as_deref())
@@ -40,7 +43,7 @@ pub async fn add_engine(
     engine_type: String,
     path: String,
     state: State<'_, EngineState>,
-) -> Result<EngineDto, String> {
+) -> Result<EngineDto, AppError> {
     let service = state.engine_service.lock().await;
     let engine = service.add_engine(name, version, engine_type, path).await?;
     Ok(service.to_dto(engine))
@@ -52,7 +55,7 @@ pub async fn delete_engine(
     id: String,
     state: State<'_, EngineState>,
     app: AppHandle,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let service = state.engine_service.lock().await;
     let engine = service.get_engine_by_id(&id).await?;
 
@@ -65,7 +68,12 @@ pub async fn delete_engine(
                     let _ = std::fs::remove_dir_all(&engine_path);
                     // 尝试清理空父目录
                     if let Some(parent) = engine_path.parent() {
-                        if parent.is_dir() && parent.read_dir().map(|mut i| i.next().is_none()).unwrap_or(false) {
+                        if parent.is_dir()
+                            && parent
+                                .read_dir()
+                                .map(|mut i| i.next().is_none())
+                                .unwrap_or(false)
+                        {
                             let _ = std::fs::remove_dir(parent);
                         }
                     }
@@ -76,7 +84,7 @@ pub async fn delete_engine(
         }
     }
 
-    service.delete_engine(&id).await
+    Ok(service.delete_engine(&id).await?)
 }
 
 /// 获取引擎更新信息
@@ -84,31 +92,58 @@ pub async fn delete_engine(
 pub async fn get_engine_update_info(
     id: String,
     state: State<'_, EngineState>,
-) -> Result<EngineUpdateInfo, String> {
+) -> Result<EngineUpdateInfo, AppError> {
     let service = state.engine_service.lock().await;
     let engine = service
         .get_engine_by_id(&id)
         .await?
-        .ok_or_else(|| format!("运行器不存在: {}", id))?;
+        .ok_or_else(|| AppError::NotFound(format!("运行器不存在: {}", id)))?;
 
     if engine.engine_type != "nwjs" {
-        return Ok(EngineUpdateInfo {
-            engine_id: engine.id,
-            current_version: engine.version.clone(),
-            latest_version: engine.version,
-            update_available: false,
-        });
+        return Ok(engine_update_info(engine, None));
     }
 
-    let info = nwjs::get_stable_info().await?;
-    let update_available = is_newer_version(&engine.version, &info.version);
+    let mirror = nwjs::resolve_mirror(&mut *state.db.lock().await).await?;
+    let info = nwjs::get_stable_info(&mirror).await?;
+    Ok(engine_update_info(engine, Some(&info.version)))
+}
 
-    Ok(EngineUpdateInfo {
+/// 检查全部引擎的更新；NW.js 最新稳定版只获取一次
+#[tauri::command]
+pub async fn check_all_engine_updates(
+    state: State<'_, EngineState>,
+) -> Result<Vec<EngineUpdateInfo>, AppError> {
+    let service = state.engine_service.lock().await;
+    let engines = service.get_all_engines().await?;
+
+    let latest_nwjs = if engines.iter().any(|e| e.engine_type == "nwjs") {
+        let mirror = nwjs::resolve_mirror(&mut *state.db.lock().await).await?;
+        Some(nwjs::get_stable_info(&mirror).await?.version)
+    } else {
+        None
+    };
+
+    Ok(engines
+        .into_iter()
+        .map(|engine| {
+            let latest = match engine.engine_type.as_str() {
+                "nwjs" => latest_nwjs.as_deref(),
+                _ => None,
+            };
+            engine_update_info(engine, latest)
+        })
+        .collect())
+}
+
+/// `latest` 为 `None` 表示该引擎不支持在线更新
+fn engine_update_info(engine: Engine, latest: Option<&str>) -> EngineUpdateInfo {
+    let latest_version = latest.unwrap_or(&engine.version).to_string();
+    EngineUpdateInfo {
+        update_available: is_newer_version(&engine.version, &latest_version),
         engine_id: engine.id,
         current_version: engine.version,
-        latest_version: info.version,
-        update_available,
-    })
+        latest_version,
+    }
 }
 
 /// 更新引擎
@@ -117,12 +152,12 @@ pub async fn update_engine(
     id: String,
     app: AppHandle,
     state: State<'_, EngineState>,
-) -> Result<EngineUpdateResult, String> {
+) -> Result<EngineUpdateResult, AppError> {
     let service = state.engine_service.lock().await;
     let engine = service
         .get_engine_by_id(&id)
         .await?
-        .ok_or_else(|| format!("运行器不存在: {}", id))?;
+        .ok_or_else(|| AppError::NotFound(format!("运行器不存在: {}", id)))?;
 
     if engine.engine_type != "nwjs" {
         return Ok(EngineUpdateResult {
@@ -134,7 +169,8 @@ pub async fn update_engine(
         });
     }
 
-    let info = nwjs::get_stable_info().await?;
+    let mirror = nwjs::resolve_mirror(&mut *state.db.lock().await).await?;
+    let info = nwjs::get_stable_info(&mirror).await?;
     if !is_newer_version(&engine.version, &info.version) {
         return Ok(EngineUpdateResult {
             engine_id: engine.id,
@@ -147,17 +183,23 @@ pub async fn update_engine(
 
     let result = nwjs::download_and_install(
         &app,
+        &mirror,
         info.version.clone(),
         nwjs::NwjsFlavor::Normal,
         info.target,
+        true,
     )
-    .await?;
+    .await
+    .map_err(AppError::Download)?;
 
     remove_old_engine_path(&app, &engine.engine_path);
 
     service
         .update_engine_install(&engine.id, info.version.clone(), result.install_dir.clone())
         .await?;
+    service
+        .update_engine_build(&engine.id, &result.target, &result.install_dir)
+        .await?;
 
     // 默认清理旧版 NW.js
     prune_old_nwjs_engines(&service, &app, &engine.id, &engine.name).await?;
@@ -232,7 +274,7 @@ async fn prune_old_nwjs_engines(
 #[tauri::command]
 pub async fn get_engine_registry(
     state: State<'_, crate::commands::state::AppState>,
-) -> Result<Vec<crate::engines::EngineMetaDto>, String> {
+) -> Result<Vec<crate::engines::EngineMetaDto>, AppError> {
     let registry = state.engine_registry.lock().await;
     Ok(registry.list_for_frontend())
 }
@@ -241,7 +283,7 @@ pub async fn get_engine_registry(
 #[tauri::command]
 pub async fn get_engine_registry_detail(
     state: State<'_, crate::commands::state::AppState>,
-) -> Result<Vec<crate::engines::EngineDetailDto>, String> {
+) -> Result<Vec<crate::engines::EngineDetailDto>, AppError> {
     let registry = state.engine_registry.lock().await;
     Ok(registry.list_detail_for_frontend())
 }
@@ -252,7 +294,7 @@ pub async fn set_engine_enabled(
     id: String,
     enabled: bool,
     state: State<'_, crate::commands::state::AppState>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     {
         let mut registry = state.engine_registry.lock().await;
         registry.set_enabled(&id, enabled)?;
@@ -260,7 +302,120 @@ pub async fn set_engine_enabled(
     let setting_key = format!("engine.{}.enabled", id);
     let value = if enabled { "1" } else { "0" };
     let mut db = state.db.lock().await;
-    crate::db::set_setting(&mut db, &setting_key, value).await
+    Ok(crate::db::set_setting(&mut db, &setting_key, value).await?)
+}
+
+/// 检测目录（或可执行文件所在目录）的引擎类型，返回各候选引擎的置信度与得分
+#[tauri::command]
+pub async fn detect_engine(
+    path: String,
+    state: State<'_, crate::commands::state::AppState>,
+) -> Result<crate::engines::EngineDetectionDto, AppError> {
+    let path = std::path::Path::new(&path);
+    let dir = if path.is_file() {
+        path.parent()
+            .ok_or_else(|| "无法解析游戏目录".to_string())?
+            .to_path_buf()
+    } else if path.is_dir() {
+        path.to_path_buf()
+    } else {
+        return Err(AppError::PathInvalid(format!(
+            "路径不存在: {}",
+            path.display()
+        )));
+    };
+
+    let ctx = crate::engines::context::FsDetectionContext::new(dir);
+    let registry = state.engine_registry.lock().await;
+    let candidates: Vec<crate::engines::EngineCandidateDto> = registry
+        .candidates(&ctx)
+        .into_iter()
+        .map(|c| crate::engines::EngineCandidateDto {
+            engine_type: c.id.to_string(),
+            name: c.name.to_string(),
+            confidence: c.confidence,
+            score: c.raw_score,
+        })
+        .collect();
+
+    Ok(crate::engines::EngineDetectionDto {
+        engine_type: candidates.first().map(|c| c.engine_type.clone()),
+        confidence: candidates.first().map(|c| c.confidence).unwrap_or(0),
+        candidates,
+    })
+}
+
+/// 重新检测时采用新结果的最低置信度
+const REDETECT_MIN_CONFIDENCE: i32 = 60;
+
+/// 对游戏库中所有游戏重新检测引擎类型
+///
+/// 仅在新结果置信度足够且与当前不同时更新；路径不存在或引擎类型由用户手动指定的游戏跳过，
+/// 不会把已识别的引擎降级为 `other`。
+#[tauri::command]
+pub async fn redetect_engines(
+    state: State<'_, crate::commands::state::AppState>,
+) -> Result<RedetectEnginesResult, AppError> {
+    let service = state.game_service.lock().await.clone();
+    let games = service.get_all_games().await?;
+    let mut result = RedetectEnginesResult {
+        checked: 0,
+        skipped_missing: 0,
+        skipped_user_set: 0,
+        changed: Vec::new(),
+    };
+
+    let mut updates = Vec::new();
+    {
+        let registry = state.engine_registry.lock().await;
+        for game in games {
+            if game.engine_user_set {
+                result.skipped_user_set += 1;
+                continue;
+            }
+            let dir = std::path::Path::new(&game.game_path);
+            if !dir.is_dir() {
+                result.skipped_missing += 1;
+                continue;
+            }
+            result.checked += 1;
+            let ctx = crate::engines::context::FsDetectionContext::new(dir.to_path_buf());
+            let Some((engine_type, confidence)) = registry.detect(&ctx) else {
+                continue;
+            };
+            // 与导入时一致，按 EngineType 归一化后保存
+            let engine_type = EngineType::from_str(engine_type);
+            if engine_type == EngineType::Other
+                || engine_type.as_str() == game.engine_type
+                || confidence < REDETECT_MIN_CONFIDENCE
+            {
+                continue;
+            }
+            updates.push(EngineChange {
+                game_id: game.id,
+                title: game.title,
+                old_engine_type: game.engine_type,
+                new_engine_type: engine_type.as_str().to_string(),
+                confidence,
+            });
+        }
+    }
+
+    for change in updates {
+        service
+            .set_detected_engine(&change.game_id, &change.new_engine_type, change.confidence)
+            .await?;
+        tracing::info!(
+            game_id = %change.game_id,
+            from = %change.old_engine_type,
+            to = %change.new_engine_type,
+            confidence = change.confidence,
+            "已更新游戏引擎类型"
+        );
+        result.changed.push(change);
+    }
+
+    Ok(result)
 }
 
 /// 获取引擎完整配置详情
@@ -268,11 +423,11 @@ pub async fn set_engine_enabled(
 pub async fn get_engine_profile_detail(
     id: String,
     state: State<'_, crate::commands::state::AppState>,
-) -> Result<crate::engines::EngineProfileDetailDto, String> {
+) -> Result<crate::engines::EngineProfileDetailDto, AppError> {
     let registry = state.engine_registry.lock().await;
-    registry
+    Ok(registry
         .get_profile_detail(&id)
-        .ok_or_else(|| format!("引擎 '{}' 不存在", id))
+        .ok_or_else(|| format!("引擎 '{}' 不存在", id))?)
 }
 
 fn is_newer_version(current: &str, latest: &str) -> bool {
@@ -0,0 +1,149 @@
+//! 命令层错误：序列化为 `{ code, message }`，前端按 `code` 区分错误类型，`message` 用于展示。
+
+use crate::engines::EngineNearMissDto;
+use crate::models::SettingsFieldError;
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+/// Tauri 命令返回的错误
+#[derive(Debug, Clone)]
+pub enum AppError {
+    /// 游戏、引擎、任务等记录不存在
+    NotFound(String),
+    /// 需要的运行时（NW.js、mkxp-z 等）未安装
+    RuntimeMissing(String),
+    /// 路径不存在或不符合要求
+    PathInvalid(String),
+    /// 数据库读写失败
+    Db(String),
+    /// 文件读写失败
+    Io(String),
+    /// 下载失败或被取消
+    Download(String),
+    /// 输入校验失败，`fields` 列出失败的字段
+    Validation {
+        message: String,
+        fields: Vec<SettingsFieldError>,
+    },
+    /// 目录未识别为任何已启用引擎的游戏，`near_misses` 为各引擎的规则命中情况（最接近的在前），
+    /// 前端据此说明原因并让用户手动选择引擎；`path` 为未识别的目录
+    EngineNotSupported {
+        message: String,
+        path: String,
+        near_misses: Vec<EngineNearMissDto>,
+    },
+    /// 其它错误（服务层返回的字符串错误）
+    Other(String),
+}
+
+impl AppError {
+    /// 游戏记录不存在
+    pub fn game_not_found(id: &str) -> Self {
+        Self::NotFound(format!("游戏不存在: {}", id))
+    }
+
+    /// 目录未识别出引擎；诊断最多保留前几个最接近的引擎
+    pub fn engine_not_supported(
+        dir: &std::path::Path,
+        mut near_misses: Vec<EngineNearMissDto>,
+    ) -> Self {
+        near_misses.truncate(5);
+        Self::EngineNotSupported {
+            message: format!("未能识别游戏引擎: {}", dir.display()),
+            path: dir.to_string_lossy().to_string(),
+            near_misses,
+        }
+    }
+
+    /// 前端据此区分错误类型
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::NotFound(_) => "not_found",
+            Self::RuntimeMissing(_) => "runtime_missing",
+            Self::PathInvalid(_) => "path_invalid",
+            Self::Db(_) => "db",
+            Self::Io(_) => "io",
+            Self::Download(_) => "download",
+            Self::Validation { .. } => "validation",
+            Self::EngineNotSupported { .. } => "engine_not_supported",
+            Self::Other(_) => "other",
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            Self::NotFound(message)
+            | Self::RuntimeMissing(message)
+            | Self::PathInvalid(message)
+            | Self::Db(message)
+            | Self::Io(message)
+            | Self::Download(message)
+            | Self::Validation { message, .. }
+            | Self::EngineNotSupported { message, .. }
+            | Self::Other(message) => message,
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        Self::Other(message)
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        Self::Other(message.to_string())
+    }
+}
+
+/// 命令之间互相调用、或在返回 `String` 错误的辅助函数中使用时退化为消息
+impl From<AppError> for String {
+    fn from(error: AppError) -> Self {
+        match error {
+            AppError::NotFound(message)
+            | AppError::RuntimeMissing(message)
+            | AppError::PathInvalid(message)
+            | AppError::Db(message)
+            | AppError::Io(message)
+            | AppError::Download(message)
+            | AppError::Validation { message, .. }
+            | AppError::EngineNotSupported { message, .. }
+            | AppError::Other(message) => message,
+        }
+    }
+}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let len = match self {
+            Self::Validation { .. } => 3,
+            Self::EngineNotSupported { .. } => 5,
+            _ => 2,
+        };
+        let mut state = serializer.serialize_struct("AppError", len)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", self.message())?;
+        match self {
+            Self::Validation { fields, .. } => state.serialize_field("fields", fields)?,
+            Self::EngineNotSupported {
+                path, near_misses, ..
+            } => {
+                state.serialize_field("path", path)?;
+                state.serialize_field("bestScore", &near_misses.first().map(|n| n.score))?;
+                state.serialize_field("nearMisses", near_misses)?;
+            }
+            _ => {}
+        }
+        state.end()
+    }
+}
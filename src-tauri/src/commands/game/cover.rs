@@ -2,12 +2,15 @@ use super::cover_resolver::{
     resolve_cover_for_game, resolve_entry_path_for_cover, resolve_existing_cover,
 };
 use super::game::{default_game_config, normalize_engine_type};
-use crate::commands::state::AppState;
+use crate::commands::error::AppError;
+use crate::commands::state::{AppState, cached_read_config, cached_write_config};
 use crate::db::schema::Game;
 use crate::models::GameDto;
+use crate::services::game::{screenshot, session};
 use crate::services::{FileService, GameService};
 use std::path::Path;
 use tauri::State;
+use uuid::Uuid;
 
 /// 按优先级更新封面图标
 pub(crate) async fn update_game_cover(
@@ -23,6 +26,12 @@ pub(crate) async fn update_game_cover(
 
     // 快速同步检查：已有封面是否依然有效（仅做路径存在性检查）
     if !force_extract && let Some(existing) = resolve_existing_cover(&file_service, root, game) {
+        // 早期保存的封面没有缩略图，这里补生成
+        let thumb_source = existing.clone();
+        let _ = tokio::task::spawn_blocking(move || {
+            FileService::new().ensure_cover_thumbnail(&thumb_source)
+        })
+        .await;
         let _ = service
             .update_cover_path(&game.id, Some(existing.to_string_lossy().to_string()))
             .await;
@@ -76,12 +85,15 @@ pub(crate) async fn update_game_cover(
 
 /// 重新提取图标/封面
 #[tauri::command]
-pub async fn refresh_game_cover(id: String, state: State<'_, AppState>) -> Result<GameDto, String> {
+pub async fn refresh_game_cover(
+    id: String,
+    state: State<'_, AppState>,
+) -> Result<GameDto, AppError> {
     let service = state.game_service.lock().await;
     let game = service
         .get_game_by_id(&id)
         .await?
-        .ok_or_else(|| format!("游戏不存在: {}", id))?;
+        .ok_or_else(|| AppError::game_not_found(&id))?;
 
     let root = state.container_root_path().await;
 
@@ -111,13 +123,100 @@ pub async fn refresh_game_cover(id: String, state: State<'_, AppState>) -> Resul
     .await;
 
     if !refreshed {
-        return Err("未找到可提取的图标，请确认入口或同目录 .exe 是否存在".to_string());
+        return Err(AppError::NotFound(
+            "未找到可提取的图标，请确认入口或同目录 .exe 是否存在".to_string(),
+        ));
     }
 
     let updated = service
         .get_game_by_id(&id)
         .await?
-        .ok_or_else(|| format!("游戏不存在: {}", id))?;
+        .ok_or_else(|| AppError::game_not_found(&id))?;
 
-    Ok(service.to_dto(updated))
+    Ok(service.to_dto(updated).await?)
+}
+
+/// 从远程 URL 下载图片并设为封面
+#[tauri::command]
+pub async fn set_cover_from_url(
+    id: String,
+    url: String,
+    state: State<'_, AppState>,
+) -> Result<GameDto, AppError> {
+    let game = find_game(&state, &id).await?;
+    let (data, ext) = crate::services::download::cover::fetch_cover_image(&url).await?;
+
+    // 临时文件带上实际扩展名，save_cover_to_profile 据此命名 cover.<ext> 并生成缩略图
+    let tmp = std::env::temp_dir().join(format!("{}.{}", Uuid::new_v4(), ext));
+    std::fs::write(&tmp, &data).map_err(|e| format!("保存封面失败: {}", e))?;
+    let result = save_cover_file(&state, &game, &tmp).await;
+    let _ = std::fs::remove_file(&tmp);
+    Ok(result?)
+}
+
+/// 截取正在运行的游戏窗口作为封面（目前仅支持 Linux）
+#[tauri::command]
+pub async fn capture_window_cover(
+    id: String,
+    pid: u32,
+    state: State<'_, AppState>,
+) -> Result<GameDto, AppError> {
+    let game = find_game(&state, &id).await?;
+    if !session::is_tracked_pid(&state.running_games, &game.id, pid) {
+        return Err(AppError::Other("该进程不是此游戏的运行实例".to_string()));
+    }
+
+    let tmp = std::env::temp_dir().join(format!("{}.png", Uuid::new_v4()));
+    let capture_path = tmp.clone();
+    tokio::task::spawn_blocking(move || screenshot::capture_window(pid, &capture_path))
+        .await
+        .map_err(|e| format!("截取游戏窗口失败: {}", e))??;
+    let result = save_cover_file(&state, &game, &tmp).await;
+    let _ = std::fs::remove_file(&tmp);
+    Ok(result?)
+}
+
+async fn find_game(state: &AppState, id: &str) -> Result<Game, AppError> {
+    let service = state.game_service.lock().await;
+    service
+        .get_game_by_id(id)
+        .await?
+        .ok_or_else(|| AppError::game_not_found(id))
+}
+
+/// 把图片保存为 profile 封面，同步配置中的 cover_file 与数据库封面路径
+async fn save_cover_file(state: &AppState, game: &Game, image: &Path) -> Result<GameDto, String> {
+    let root = state.container_root_path().await;
+    let file_service = FileService::new();
+    let saved = file_service.save_cover_to_profile(&root, &game.profile_key, image)?;
+
+    let config_path = file_service.game_config_path(&root, &game.profile_key);
+    let mut config = cached_read_config(
+        &state.config_cache,
+        &file_service,
+        &config_path,
+        &game.profile_key,
+    )
+    .unwrap_or_else(|| default_game_config(game));
+    config.cover_file = saved
+        .file_name()
+        .and_then(|n| n.to_str())
+        .map(str::to_string);
+    cached_write_config(
+        &state.config_cache,
+        &file_service,
+        &config_path,
+        &game.profile_key,
+        &config,
+    )?;
+
+    let service = state.game_service.lock().await;
+    service
+        .update_cover_path(&game.id, Some(saved.to_string_lossy().to_string()))
+        .await?;
+    let updated = service
+        .get_game_by_id(&game.id)
+        .await?
+        .ok_or_else(|| AppError::game_not_found(&game.id))?;
+    service.to_dto(updated).await
 }
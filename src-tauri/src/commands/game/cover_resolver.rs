@@ -5,6 +5,8 @@ use crate::commands::state::{ConfigCache, cached_read_config};
 use crate::db::schema::Game;
 use crate::models::{EngineType, GameDto};
 use crate::services::FileService;
+use crate::services::cover_protocol::cover_url;
+use crate::services::fs::COVER_THUMB_FILE;
 use std::path::{Path, PathBuf};
 
 /// 按引擎类型从游戏目录提取封面：优先查找图标目录图片，其次提取 exe 图标，最后回退到封面图片。
@@ -28,10 +30,13 @@ pub(crate) fn resolve_cover_for_game(
         |path: &Path| file_service.save_exe_icon_to_profile(root, profile_key, path);
 
     match engine {
-        EngineType::RpgMakerVX
+        EngineType::RpgMaker2000
+        | EngineType::RpgMakerXP
+        | EngineType::RpgMakerVX
         | EngineType::RpgMakerVXAce
         | EngineType::RpgMakerMV
-        | EngineType::RpgMakerMZ => {
+        | EngineType::RpgMakerMZ
+        | EngineType::WolfRpg => {
             if let Some(icon) = file_service.find_icon_dir_image(game_dir) {
                 if let Some(saved) = save_image(&icon) {
                     return Some(saved);
@@ -47,6 +52,8 @@ pub(crate) fn resolve_cover_for_game(
             }
         }
         EngineType::RenPy
+        | EngineType::Tyrano
+        | EngineType::KiriKiri
         | EngineType::Unity
         | EngineType::Godot
         | EngineType::Html
@@ -62,6 +69,12 @@ pub(crate) fn resolve_cover_for_game(
         }
     }
 
+    // macOS 原生游戏（.app）没有 PE 图标，最后回退到包内的 .icns
+    #[cfg(target_os = "macos")]
+    if let Some(saved) = file_service.save_app_bundle_icon_to_profile(root, profile_key, game_dir) {
+        return Some(saved);
+    }
+
     None
 }
 
@@ -110,10 +123,13 @@ pub(crate) fn resolve_existing_cover(
                 .and_then(|e| e.to_str())
                 .unwrap_or("")
                 .to_lowercase();
-            if matches!(
-                ext.as_str(),
-                "png" | "jpg" | "jpeg" | "webp" | "bmp" | "ico"
-            ) {
+            let is_thumb = path.file_name().is_some_and(|n| n == COVER_THUMB_FILE);
+            if !is_thumb
+                && matches!(
+                    ext.as_str(),
+                    "png" | "jpg" | "jpeg" | "webp" | "bmp" | "ico"
+                )
+            {
                 return Some(path);
             }
         }
@@ -143,7 +159,7 @@ pub(crate) fn resolve_entry_path_for_cover(game_path: &Path, entry_path: &str) -
     }
 }
 
-/// 从游戏配置缓存中补充封面路径到 GameDto，若当前封面失效则尝试读取配置中的 cover_file。
+/// 从游戏配置缓存中补充封面 URL 到 GameDto：数据库记录的封面失效时尝试读取配置中的 cover_file。
 pub(crate) fn fill_cover_from_config(
     cache: &ConfigCache,
     file_service: &FileService,
@@ -151,11 +167,9 @@ pub(crate) fn fill_cover_from_config(
     game: &Game,
     mut dto: GameDto,
 ) -> GameDto {
-    if let Some(path) = dto.cover_path.as_deref() {
-        if Path::new(path).exists() {
-            return dto;
-        }
-        dto.cover_path = None;
+    // build_dto 仅在数据库记录的封面文件存在时才输出 URL
+    if dto.cover_path.is_some() {
+        return dto;
     }
 
     let config_path = file_service.game_config_path(root, &game.profile_key);
@@ -173,8 +187,11 @@ pub(crate) fn fill_cover_from_config(
         profile_dir.join(&cover_file)
     };
 
-    if cover_path.exists() {
-        dto.cover_path = Some(cover_path.to_string_lossy().to_string());
+    if cover_path.is_file() {
+        dto.cover_thumb_path = file_service
+            .cover_thumbnail(&cover_path)
+            .map(|_| cover_url(&game.profile_key, true, game.updated_at));
+        dto.cover_path = Some(cover_url(&game.profile_key, false, game.updated_at));
     }
 
     dto
@@ -1,18 +1,154 @@
-use super::cover_resolver::fill_cover_from_config;
-use crate::commands::state::{AppState, cache_remove};
+use super::cover::update_game_cover;
+use super::cover_resolver::{fill_cover_from_config, resolve_entry_path_for_cover};
+use crate::commands::error::AppError;
+use crate::commands::state::{AppState, cache_remove, cached_write_config};
 use crate::db::schema::Game;
-use crate::models::{AddGameInput, GameConfig, GameDto, UpdateGameInput};
-use crate::services::FileService;
+use crate::engines::EngineRegistry;
+use crate::engines::context::FsDetectionContext;
+use crate::models::{
+    AddGameInput, GameConfig, GameDiskUsage, GameDto, GameSortBy, GameStatus, SandboxHome,
+    SortDirection, UpdateGameInput,
+};
+use crate::services::{FileService, GameService};
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
-use tauri::State;
+use tauri::{AppHandle, Manager, State};
 
 /// 获取所有游戏
 #[tauri::command]
-pub async fn get_games(state: State<'_, AppState>) -> Result<Vec<GameDto>, String> {
+pub async fn get_games(state: State<'_, AppState>) -> Result<Vec<GameDto>, AppError> {
     let games = {
         let service = state.game_service.lock().await;
         service.get_all_games().await?
     };
+    Ok(games_to_dtos(&state, games).await?)
+}
+
+/// 按评分、标题、最后游玩或添加时间排序获取游戏，可选按完成状态筛选
+#[tauri::command]
+pub async fn list_games_sorted(
+    sort_by: GameSortBy,
+    direction: SortDirection,
+    status: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<GameDto>, AppError> {
+    let status = status.as_deref().map(GameStatus::parse).transpose()?;
+    let games = {
+        let service = state.game_service.lock().await;
+        service.get_games_sorted(sort_by, direction, status).await?
+    };
+    Ok(games_to_dtos(&state, games).await?)
+}
+
+/// 按标题或路径搜索游戏
+#[tauri::command]
+pub async fn search_games(
+    query: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<GameDto>, AppError> {
+    let games = {
+        let service = state.game_service.lock().await;
+        service.search_games(&query).await?
+    };
+    Ok(games_to_dtos(&state, games).await?)
+}
+
+/// 按标签筛选游戏
+#[tauri::command]
+pub async fn list_games_by_tag(
+    tag: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<GameDto>, AppError> {
+    let games = {
+        let service = state.game_service.lock().await;
+        service.get_games_by_tag(&tag).await?
+    };
+    Ok(games_to_dtos(&state, games).await?)
+}
+
+/// 设置或取消收藏游戏
+#[tauri::command]
+pub async fn set_game_favorite(
+    id: String,
+    favorite: bool,
+    state: State<'_, AppState>,
+) -> Result<(), AppError> {
+    let service = state.game_service.lock().await;
+    Ok(service.set_favorite(&id, favorite).await?)
+}
+
+/// 设置游戏评分（0-5），传空清除评分
+#[tauri::command]
+pub async fn set_game_rating(
+    id: String,
+    rating: Option<i32>,
+    state: State<'_, AppState>,
+) -> Result<(), AppError> {
+    let service = state.game_service.lock().await;
+    Ok(service.set_rating(&id, rating).await?)
+}
+
+/// 固定游戏使用的 NW.js 版本，传空取消固定（启动时使用最新已安装版本）
+///
+/// 固定前会检查该版本已安装在本地运行时目录中。
+#[tauri::command]
+pub async fn set_game_runtime(
+    id: String,
+    version: Option<String>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), AppError> {
+    let version = version
+        .map(|v| v.trim().trim_start_matches('v').to_string())
+        .filter(|v| !v.is_empty());
+
+    if let Some(version) = &version {
+        let installed = tokio::task::spawn_blocking(move || {
+            crate::services::download::nwjs::list_installed(&app)
+        })
+        .await
+        .map_err(|e| format!("扫描运行时失败: {}", e))??;
+        if !installed.iter().any(|r| r.version == *version) {
+            return Err(AppError::RuntimeMissing(format!(
+                "NW.js {} 未安装，请先在设置中下载该版本",
+                version
+            )));
+        }
+    }
+
+    let service = state.game_service.lock().await;
+    Ok(service.set_runtime_version(&id, version).await?)
+}
+
+/// 设置游戏完成状态（未开始/进行中/已通关/弃坑）
+#[tauri::command]
+pub async fn set_game_status(
+    id: String,
+    status: String,
+    state: State<'_, AppState>,
+) -> Result<(), AppError> {
+    let status = GameStatus::parse(&status)?;
+    let service = state.game_service.lock().await;
+    Ok(service.set_status(&id, status).await?)
+}
+
+/// 设置游戏标签，返回保存后的标签
+#[tauri::command]
+pub async fn set_game_tags(
+    id: String,
+    tags: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, AppError> {
+    let service = state.game_service.lock().await;
+    Ok(service.set_tags(&id, tags).await?)
+}
+
+/// 批量构建游戏 DTO（含标签与配置中的封面）
+async fn games_to_dtos(state: &AppState, games: Vec<Game>) -> Result<Vec<GameDto>, String> {
+    let mut tags = {
+        let service = state.game_service.lock().await;
+        service.get_all_tags().await?
+    };
     let root = state.container_root_path().await;
 
     let cache = state.config_cache.clone();
@@ -21,22 +157,8 @@ pub async fn get_games(state: State<'_, AppState>) -> Result<Vec<GameDto>, Strin
         games
             .into_iter()
             .map(|g| {
-                let path_valid = std::path::Path::new(&g.game_path).exists();
-                let dto = GameDto {
-                    id: g.id.clone(),
-                    title: g.title.clone(),
-                    engine_type: g.engine_type.clone(),
-                    path: g.game_path.clone(),
-                    game_type: g.game_type.clone(),
-                    detection_confidence: g.detection_confidence,
-                    path_valid,
-                    runtime_version: g.runtime_version.clone(),
-                    cover_path: g.cover_path.clone(),
-                    play_count: g.play_count,
-                    created_at: g.created_at,
-                    last_played_at: g.last_played_at,
-                    updated_at: g.updated_at,
-                };
+                let tags = tags.remove(&g.id).unwrap_or_default();
+                let dto = GameService::build_dto(&g, tags);
                 fill_cover_from_config(&cache, &file_service, &root, &g, dto)
             })
             .collect()
@@ -49,7 +171,7 @@ pub async fn get_games(state: State<'_, AppState>) -> Result<Vec<GameDto>, Strin
 
 /// 获取单个游戏
 #[tauri::command]
-pub async fn get_game(id: String, state: State<'_, AppState>) -> Result<Option<GameDto>, String> {
+pub async fn get_game(id: String, state: State<'_, AppState>) -> Result<Option<GameDto>, AppError> {
     let game = {
         let service = state.game_service.lock().await;
         service.get_game_by_id(&id).await?
@@ -57,28 +179,49 @@ pub async fn get_game(id: String, state: State<'_, AppState>) -> Result<Option<G
     let Some(game) = game else {
         return Ok(None);
     };
+    let tags = {
+        let service = state.game_service.lock().await;
+        service.get_tags(&game.id).await?
+    };
 
     let root = state.container_root_path().await;
 
     let cache = state.config_cache.clone();
     let dto = tokio::task::spawn_blocking(move || {
         let file_service = FileService::new();
-        let path_valid = std::path::Path::new(&game.game_path).exists();
-        let dto = GameDto {
-            id: game.id.clone(),
-            title: game.title.clone(),
-            engine_type: game.engine_type.clone(),
-            path: game.game_path.clone(),
-            game_type: game.game_type.clone(),
-            detection_confidence: game.detection_confidence,
-            path_valid,
-            runtime_version: game.runtime_version.clone(),
-            cover_path: game.cover_path.clone(),
-            play_count: game.play_count,
-            created_at: game.created_at,
-            last_played_at: game.last_played_at,
-            updated_at: game.updated_at,
-        };
+        let dto = GameService::build_dto(&game, tags);
+        fill_cover_from_config(&cache, &file_service, &root, &game, dto)
+    })
+    .await
+    .map_err(|e| format!("封面解析失败: {}", e))?;
+
+    Ok(Some(dto))
+}
+
+/// 按路径查找已在库中的游戏（拖放导入前检查用）；路径为文件时按其所在目录查找
+#[tauri::command]
+pub async fn get_game_by_path(
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<Option<GameDto>, AppError> {
+    let path = Path::new(&path);
+    let dir = if path.is_file() {
+        path.parent().unwrap_or(path)
+    } else {
+        path
+    };
+    let service = state.game_service.lock().await;
+    let Some(game) = service.get_game_by_path(&normalize_path(dir)).await? else {
+        return Ok(None);
+    };
+    let tags = service.get_tags(&game.id).await?;
+    drop(service);
+
+    let root = state.container_root_path().await;
+    let cache = state.config_cache.clone();
+    let dto = tokio::task::spawn_blocking(move || {
+        let file_service = FileService::new();
+        let dto = GameService::build_dto(&game, tags);
         fill_cover_from_config(&cache, &file_service, &root, &game, dto)
     })
     .await
@@ -89,10 +232,13 @@ pub async fn get_game(id: String, state: State<'_, AppState>) -> Result<Option<G
 
 /// 添加游戏
 #[tauri::command]
-pub async fn add_game(input: AddGameInput, state: State<'_, AppState>) -> Result<GameDto, String> {
+pub async fn add_game(
+    input: AddGameInput,
+    state: State<'_, AppState>,
+) -> Result<GameDto, AppError> {
     let service = state.game_service.lock().await;
     let game = service.add_game(input).await?;
-    Ok(service.to_dto(game))
+    Ok(service.to_dto(game).await?)
 }
 
 /// 更新游戏
@@ -101,20 +247,187 @@ pub async fn update_game(
     id: String,
     input: UpdateGameInput,
     state: State<'_, AppState>,
-) -> Result<GameDto, String> {
+) -> Result<GameDto, AppError> {
+    let service = state.game_service.lock().await;
+    let game = service.update_game(&id, input).await?;
+    Ok(service.to_dto(game).await?)
+}
+
+/// 在其它位置查找已移动游戏时的最大搜索深度
+const RELOCATE_SEARCH_DEPTH: u32 = 4;
+
+/// 将游戏指向新的目录（游戏被移动到其它位置时使用），保留 profile、存档与统计
+///
+/// 新目录必须能被识别为与原记录相同的引擎；封面失效时会重新提取。
+#[tauri::command]
+pub async fn relocate_game(
+    id: String,
+    new_path: String,
+    state: State<'_, AppState>,
+) -> Result<GameDto, AppError> {
+    let new_dir = PathBuf::from(new_path.trim());
+    if !new_dir.is_dir() {
+        return Err(AppError::PathInvalid(format!(
+            "目录不存在: {}",
+            new_dir.display()
+        )));
+    }
+
     let service = state.game_service.lock().await;
+    let game = service
+        .get_game_by_id(&id)
+        .await?
+        .ok_or_else(|| AppError::game_not_found(&id))?;
+
+    let detected = {
+        let registry = state.engine_registry.lock().await;
+        detect_engine_type(&registry, &new_dir)
+    };
+    match detected {
+        Some(engine_type) if engine_type == game.engine_type => {}
+        Some(engine_type) => {
+            return Err(AppError::PathInvalid(format!(
+                "新目录识别为 {}，与原游戏引擎 {} 不一致",
+                engine_type, game.engine_type
+            )));
+        }
+        None => return Err(AppError::PathInvalid("新目录未识别为游戏目录".to_string())),
+    }
+
+    let old_dir = PathBuf::from(&game.game_path);
+    let input = UpdateGameInput {
+        path: Some(normalize_path(&new_dir)),
+        ..Default::default()
+    };
     let game = service.update_game(&id, input).await?;
-    Ok(service.to_dto(game))
+
+    let root = state.container_root_path().await;
+    let file_service = FileService::new();
+    let config_path = file_service.game_config_path(&root, &game.profile_key);
+    let mut config = config_path
+        .is_file()
+        .then(|| file_service.read_game_config(&config_path).ok())
+        .flatten();
+    // 位于旧目录内的绝对入口路径改为指向新目录
+    if let Some(cfg) = config.as_mut()
+        && let Ok(relative) = Path::new(cfg.entry_path.trim()).strip_prefix(&old_dir)
+    {
+        cfg.entry_path = normalize_path(&Path::new(&game.game_path).join(relative));
+        cached_write_config(
+            &state.config_cache,
+            &file_service,
+            &config_path,
+            &game.profile_key,
+            cfg,
+        )?;
+    }
+    let entry_exe = config
+        .as_ref()
+        .and_then(|cfg| resolve_entry_path_for_cover(Path::new(&game.game_path), &cfg.entry_path));
+    update_game_cover(
+        &service,
+        &root,
+        &game,
+        &normalize_engine_type(&game),
+        Path::new(&game.game_path),
+        entry_exe.as_deref(),
+        false,
+    )
+    .await;
+
+    tracing::info!(game_id = %game.id, path = %game.game_path, "已更新游戏目录");
+    let game = service
+        .get_game_by_id(&id)
+        .await?
+        .ok_or_else(|| AppError::game_not_found(&id))?;
+    Ok(service.to_dto(game).await?)
+}
+
+/// 在 `search_root` 下查找已移动游戏的候选目录
+///
+/// 目录名与原目录名或游戏标题相同（不区分大小写）且识别为相同引擎的目录视为候选，
+/// 结果可直接传给 `relocate_game`。
+#[tauri::command]
+pub async fn find_relocated(
+    id: String,
+    search_root: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, AppError> {
+    let root = PathBuf::from(search_root.trim());
+    if !root.is_dir() {
+        return Err(AppError::PathInvalid(format!(
+            "目录不存在: {}",
+            root.display()
+        )));
+    }
+    let game = {
+        let service = state.game_service.lock().await;
+        service
+            .get_game_by_id(&id)
+            .await?
+            .ok_or_else(|| AppError::game_not_found(&id))?
+    };
+
+    let mut names = vec![game.title.to_lowercase()];
+    if let Some(name) = Path::new(&game.game_path).file_name() {
+        names.push(name.to_string_lossy().to_lowercase());
+    }
+    let current = normalize_path(Path::new(&game.game_path));
+    let registry = state.engine_registry.clone().lock_owned().await;
+
+    Ok(tokio::task::spawn_blocking(move || {
+        let mut found = Vec::new();
+        let mut level = vec![root];
+        for _ in 0..=RELOCATE_SEARCH_DEPTH {
+            let mut next = Vec::new();
+            for dir in level {
+                let Ok(entries) = std::fs::read_dir(&dir) else {
+                    continue;
+                };
+                for path in entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()) {
+                    let name = path
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_lowercase())
+                        .unwrap_or_default();
+                    if name.starts_with('.') {
+                        continue;
+                    }
+                    if names.contains(&name)
+                        && detect_engine_type(&registry, &path).as_deref()
+                            == Some(game.engine_type.as_str())
+                    {
+                        let normalized = normalize_path(&path);
+                        if normalized != current {
+                            found.push(normalized);
+                        }
+                        continue;
+                    }
+                    next.push(path);
+                }
+            }
+            next.sort();
+            level = next;
+        }
+        found
+    })
+    .await
+    .map_err(|e| format!("查找游戏目录失败: {}", e))?)
+}
+
+/// 识别目录的引擎类型
+fn detect_engine_type(registry: &EngineRegistry, dir: &Path) -> Option<String> {
+    let ctx = FsDetectionContext::new(dir.to_path_buf());
+    registry.detect(&ctx).map(|(id, _)| id.to_string())
 }
 
 /// 删除游戏
 #[tauri::command]
-pub async fn delete_game(id: String, state: State<'_, AppState>) -> Result<(), String> {
+pub async fn delete_game(id: String, state: State<'_, AppState>) -> Result<(), AppError> {
     let service = state.game_service.lock().await;
     let game = service
         .get_game_by_id(&id)
         .await?
-        .ok_or_else(|| format!("游戏不存在: {}", id))?;
+        .ok_or_else(|| AppError::game_not_found(&id))?;
     let profile_key = game.profile_key.clone();
     service.delete_game(&id).await?;
     drop(service);
@@ -122,9 +435,70 @@ pub async fn delete_game(id: String, state: State<'_, AppState>) -> Result<(), S
     Ok(())
 }
 
+/// 批量删除游戏，返回实际删除的数量
+///
+/// `remove_profiles` 为 true 时同时删除各游戏在容器中的 profile 目录（存档、配置），
+/// 不删除游戏本体文件。
+#[tauri::command]
+pub async fn delete_games(
+    ids: Vec<String>,
+    remove_profiles: Option<bool>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<u32, AppError> {
+    if ids.is_empty() {
+        return Ok(0);
+    }
+
+    let wanted: HashSet<&str> = ids.iter().map(String::as_str).collect();
+    let games: Vec<Game> = {
+        let service = state.game_service.lock().await;
+        service.get_all_games().await?
+    }
+    .into_iter()
+    .filter(|g| wanted.contains(g.id.as_str()))
+    .collect();
+
+    let db_path = crate::db::database_path(
+        &app.path()
+            .app_data_dir()
+            .map_err(|e| format!("获取应用数据目录失败: {}", e))?,
+    );
+    let removed = {
+        // 持有 toasty 连接锁，事务期间不会有其它写入
+        let _db_lock = state.db.lock().await;
+        tokio::task::spawn_blocking(move || crate::db::bulk::delete_games(&db_path, &ids))
+            .await
+            .map_err(|e| format!("批量删除游戏失败: {}", e))??
+    };
+
+    let root = state.container_root_path().await;
+    let file_service = FileService::new();
+    for game in &games {
+        cache_remove(&state.config_cache, &game.profile_key);
+        if !remove_profiles.unwrap_or(false) || game.profile_key.is_empty() {
+            continue;
+        }
+        let profile_dir = file_service.game_profile_dir(&root, &game.profile_key);
+        if !profile_dir.exists() {
+            continue;
+        }
+        let profile_dir = crate::utils::path::canonicalize(&profile_dir);
+        if !crate::utils::path::is_profile_dir(&root, &profile_dir) {
+            tracing::warn!(path = %profile_dir.display(), "profile 目录不在容器内，跳过删除");
+            continue;
+        }
+        if let Err(e) = std::fs::remove_dir_all(&profile_dir) {
+            tracing::warn!(path = %profile_dir.display(), error = %e, "删除 profile 目录失败");
+        }
+    }
+
+    Ok(removed as u32)
+}
+
 /// 移除游戏库中的全部条目，不删除实际游戏文件。
 #[tauri::command]
-pub async fn remove_all_games(state: State<'_, AppState>) -> Result<u32, String> {
+pub async fn remove_all_games(state: State<'_, AppState>) -> Result<u32, AppError> {
     let service = state.game_service.lock().await;
     let removed = service.delete_all_games().await?;
     state.config_cache.lock().unwrap().clear();
@@ -136,12 +510,12 @@ pub async fn remove_all_games(state: State<'_, AppState>) -> Result<u32, String>
 pub async fn get_game_profile_dir(
     id: String,
     state: State<'_, AppState>,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     let service = state.game_service.lock().await;
     let game = service
         .get_game_by_id(&id)
         .await?
-        .ok_or_else(|| format!("游戏不存在: {}", id))?;
+        .ok_or_else(|| AppError::game_not_found(&id))?;
 
     let container_root = state.container_root.lock().await;
     let root = PathBuf::from(container_root.as_str());
@@ -152,12 +526,84 @@ pub async fn get_game_profile_dir(
     Ok(dir.to_string_lossy().to_string())
 }
 
+/// 在文件管理器中打开游戏目录
+#[tauri::command]
+pub async fn open_game_folder(id: String, state: State<'_, AppState>) -> Result<(), AppError> {
+    let game = {
+        let service = state.game_service.lock().await;
+        service
+            .get_game_by_id(&id)
+            .await?
+            .ok_or_else(|| AppError::game_not_found(&id))?
+    };
+    open_in_file_manager(Path::new(&game.game_path))
+}
+
+/// 在文件管理器中打开游戏存档目录
+///
+/// NW.js 游戏打开 profile 中的 `User Data`，其它游戏打开 profile 目录（沙盒 HOME）；
+/// 目录不存在时先创建，从未启动过的游戏也能打开。
+#[tauri::command]
+pub async fn open_game_save_folder(id: String, state: State<'_, AppState>) -> Result<(), AppError> {
+    let game = {
+        let service = state.game_service.lock().await;
+        service
+            .get_game_by_id(&id)
+            .await?
+            .ok_or_else(|| AppError::game_not_found(&id))?
+    };
+    let is_nwjs = {
+        let registry = state.engine_registry.lock().await;
+        registry
+            .get_entry(&game.engine_type)
+            .is_some_and(|e| e.profile.launch.strategy == "nwjs")
+    };
+
+    let root = state.container_root_path().await;
+    let file_service = FileService::new();
+    file_service.ensure_game_dirs(&root, &game.profile_key)?;
+    let dir = if is_nwjs {
+        file_service.game_user_data_dir(&root, &game.profile_key)
+    } else {
+        file_service.game_profile_dir(&root, &game.profile_key)
+    };
+    open_in_file_manager(&dir)
+}
+
+/// 统计游戏目录与 profile 目录的磁盘占用
+#[tauri::command]
+pub async fn get_game_disk_usage(
+    id: String,
+    state: State<'_, AppState>,
+) -> Result<GameDiskUsage, AppError> {
+    let game = {
+        let service = state.game_service.lock().await;
+        service
+            .get_game_by_id(&id)
+            .await?
+            .ok_or_else(|| AppError::game_not_found(&id))?
+    };
+    let root = state.container_root_path().await;
+    let profile_dir = FileService::new().game_profile_dir(&root, &game.profile_key);
+
+    Ok(tokio::task::spawn_blocking(move || GameDiskUsage {
+        game_bytes: crate::utils::path::dir_size(Path::new(&game.game_path)),
+        profile_bytes: crate::utils::path::dir_size(&profile_dir),
+    })
+    .await
+    .map_err(|e| format!("统计磁盘占用失败: {}", e))?)
+}
+
 /// 打开本地路径（文件或目录）
 #[tauri::command]
-pub async fn open_path(path: String) -> Result<(), String> {
-    let target = PathBuf::from(path);
+pub async fn open_path(path: String) -> Result<(), AppError> {
+    open_in_file_manager(Path::new(&path))
+}
+
+/// 用系统文件管理器或默认程序打开路径
+fn open_in_file_manager(target: &Path) -> Result<(), AppError> {
     if !target.exists() {
-        return Err("路径不存在".to_string());
+        return Err(AppError::PathInvalid("路径不存在".to_string()));
     }
 
     #[cfg(target_os = "windows")]
@@ -167,9 +613,9 @@ pub async fn open_path(path: String) -> Result<(), String> {
     #[cfg(target_os = "linux")]
     let mut cmd = std::process::Command::new("xdg-open");
 
-    cmd.arg(&target)
+    cmd.arg(target)
         .spawn()
-        .map_err(|e| format!("打开路径失败: {}", e))?;
+        .map_err(|e| AppError::Io(format!("打开路径失败: {}", e)))?;
 
     Ok(())
 }
@@ -184,10 +630,24 @@ pub(crate) fn default_game_config(game: &Game) -> GameConfig {
         runtime_version: game.runtime_version.clone(),
         runner: "auto".to_string(),
         args: Vec::new(),
-        sandbox_home: true,
+        env: Default::default(),
+        sandbox_home: SandboxHome::Full,
         use_bottles: false,
         bottle_name: None,
+        proton_version: None,
         cover_file: None,
+        wine_prefix: None,
+        allow_multiple: false,
+        locale: None,
+        use_gamemode: false,
+        use_mangohud: false,
+        pre_launch: None,
+        post_exit: None,
+        flavor: None,
+        devtools: false,
+        self_contained: false,
+        minimize_on_launch: None,
+        launch_in_terminal: None,
     }
 }
 
@@ -211,6 +671,18 @@ pub(crate) fn is_nwjs_runtime_dir(path: &Path) -> bool {
     has_exe && has_pak && has_icudtl && has_locales
 }
 
+/// 查找游戏目录下一级子目录中自带的 NW.js 运行时（如 `nwjs/`、`nw/`）。
+pub(crate) fn find_bundled_nwjs(game_dir: &Path) -> Option<PathBuf> {
+    let mut dirs: Vec<PathBuf> = std::fs::read_dir(game_dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir() && is_nwjs_runtime_dir(path))
+        .collect();
+    dirs.sort();
+    dirs.into_iter().next()
+}
+
 /// 将路径规范化为绝对路径（解析符号链接）并以字符串形式返回。
 pub(crate) fn normalize_path(path: &Path) -> String {
     crate::utils::path::canonicalize(path)
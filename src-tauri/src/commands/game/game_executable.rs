@@ -51,7 +51,9 @@ fn resolve_renpy_icon_exe(entry_exe: &Path, game_dir: &Path) -> Option<PathBuf>
 
 fn find_executable_for_icon(engine: EngineType, game_dir: &Path) -> Option<PathBuf> {
     match engine {
-        EngineType::RpgMakerVX
+        EngineType::RpgMaker2000
+        | EngineType::RpgMakerXP
+        | EngineType::RpgMakerVX
         | EngineType::RpgMakerVXAce
         | EngineType::RpgMakerMV
         | EngineType::RpgMakerMZ => find_executable_by_candidates(
@@ -66,10 +68,15 @@ fn find_executable_for_icon(engine: EngineType, game_dir: &Path) -> Option<PathB
             ],
         )
         .or_else(|| find_root_windows_exe(game_dir, &[])),
+        EngineType::WolfRpg => find_executable_by_candidates(game_dir, &["Game.exe"])
+            .or_else(|| find_root_windows_exe(game_dir, &["config"])),
         EngineType::RenPy => find_root_windows_exe(game_dir, &["renpy", "python"]),
+        EngineType::KiriKiri => find_root_windows_exe(game_dir, &["krkrconf", "config", "setup"]),
         EngineType::Unity => find_unity_executable(game_dir),
         EngineType::Godot => find_godot_executable(game_dir),
-        EngineType::Html | EngineType::Other => find_root_windows_exe(game_dir, &[]),
+        EngineType::Tyrano | EngineType::Html | EngineType::Other => {
+            find_root_windows_exe(game_dir, &[])
+        }
     }
 }
 
@@ -218,3 +225,68 @@ fn find_root_windows_exe(game_dir: &Path, excluded: &[&str]) -> Option<PathBuf>
 
     fallback
 }
+
+/// 列出候选可执行文件时的最大子目录深度
+const EXECUTABLE_SCAN_DEPTH: usize = 2;
+
+/// 列出游戏目录中可作为启动入口的文件（供用户手动选择），返回相对游戏目录的路径。
+///
+/// 包括 Windows `.exe`、`.sh` 启动脚本、`.x86_64`/`.x86` 以及 Linux 原生可执行文件；
+/// 跳过隐藏目录，只向下查找有限层数。
+pub(crate) fn list_executable_candidates(game_dir: &Path) -> Vec<String> {
+    let mut found = Vec::new();
+    let mut pending = vec![(game_dir.to_path_buf(), 0)];
+    while let Some((dir, depth)) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                let hidden = entry.file_name().to_string_lossy().starts_with('.');
+                if !hidden && depth < EXECUTABLE_SCAN_DEPTH {
+                    pending.push((path, depth + 1));
+                }
+                continue;
+            }
+            if is_executable_candidate(&path) {
+                if let Ok(relative) = path.strip_prefix(game_dir) {
+                    found.push(relative.to_string_lossy().replace('\\', "/"));
+                }
+            }
+        }
+    }
+    // 根目录的文件在前，同层按名称排序
+    found.sort_by(|a, b| {
+        a.matches('/')
+            .count()
+            .cmp(&b.matches('/').count())
+            .then_with(|| a.to_lowercase().cmp(&b.to_lowercase()))
+    });
+    found
+}
+
+fn is_executable_candidate(path: &Path) -> bool {
+    if !path.is_file() {
+        return false;
+    }
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    if matches!(ext.as_str(), "exe" | "sh" | "x86_64" | "x86") {
+        return true;
+    }
+    // 共享库通常也带可执行位，不作为入口
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    !(name.ends_with(".so") || name.contains(".so."))
+        && crate::utils::path::is_linux_native_executable(path)
+}
@@ -1,22 +1,27 @@
-use super::game::{default_game_config, normalize_engine_type};
+use super::game::{default_game_config, normalize_engine_type, normalize_path};
+use super::game_executable::list_executable_candidates;
+use crate::commands::error::AppError;
 use crate::commands::state::{AppState, cached_read_config, cached_write_config};
-use crate::models::{EngineType, GameConfig};
+use crate::models::{EngineType, GameConfig, SaveGameSettingsResult, SettingsFieldError};
 use crate::services::FileService;
-use std::path::{Path, PathBuf};
-use tauri::State;
+use crate::services::download::nwjs;
+use crate::services::extension::{BottlesService, ProtonService};
+use crate::services::game::arg_template;
+use std::path::{Component, Path, PathBuf};
+use tauri::{AppHandle, State};
 
 /// 获取游戏设置（settings.toml）
 #[tauri::command]
 pub async fn get_game_settings(
     id: String,
     state: State<'_, AppState>,
-) -> Result<GameConfig, String> {
+) -> Result<GameConfig, AppError> {
     let game = {
         let service = state.game_service.lock().await;
         service
             .get_game_by_id(&id)
             .await?
-            .ok_or_else(|| format!("游戏不存在: {}", id))?
+            .ok_or_else(|| AppError::game_not_found(&id))?
     };
 
     let root = state.container_root_path().await;
@@ -39,18 +44,21 @@ pub async fn get_game_settings(
 }
 
 /// 保存游戏设置（settings.toml）
+///
+/// 保存前校验启动参数、入口路径、封面与运行时版本，返回全部校验失败的字段。
 #[tauri::command]
 pub async fn save_game_settings(
     id: String,
     input: GameConfig,
+    app: AppHandle,
     state: State<'_, AppState>,
-) -> Result<(), String> {
+) -> Result<SaveGameSettingsResult, AppError> {
     let game = {
         let service = state.game_service.lock().await;
         service
             .get_game_by_id(&id)
             .await?
-            .ok_or_else(|| format!("游戏不存在: {}", id))?
+            .ok_or_else(|| AppError::game_not_found(&id))?
     };
 
     let root = state.container_root_path().await;
@@ -61,39 +69,162 @@ pub async fn save_game_settings(
 
     let mut config = input;
     config.engine_type = normalize_engine_type(&game);
+    config.env = config
+        .env
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let key = key.trim();
+            (!key.is_empty()).then(|| (key.to_string(), value))
+        })
+        .collect();
+    config.locale = config
+        .locale
+        .map(|locale| locale.trim().to_string())
+        .filter(|locale| !locale.is_empty());
+    config.args.retain(|arg| !arg.trim().is_empty());
+    config.runtime_version = config
+        .runtime_version
+        .map(|v| v.trim().trim_start_matches('v').to_string())
+        .filter(|v| !v.is_empty());
+    config.cover_file = config
+        .cover_file
+        .map(|f| f.trim().to_string())
+        .filter(|f| !f.is_empty());
+    config.bottle_name = config
+        .bottle_name
+        .map(|b| b.trim().to_string())
+        .filter(|b| !b.is_empty());
+    config.proton_version = config
+        .proton_version
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty());
+    for hook in [&mut config.pre_launch, &mut config.post_exit] {
+        *hook = hook.take().filter(|command| !command.trim().is_empty());
+    }
+
+    let mut errors = Vec::new();
+    let mut fail = |field: String, message: String| {
+        errors.push(SettingsFieldError { field, message });
+    };
+
+    config.flavor = match config.flavor.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(flavor) => match nwjs::NwjsFlavor::parse(flavor) {
+            Some(flavor) => Some(flavor.as_str().to_string()),
+            None => {
+                fail(
+                    "flavor".into(),
+                    format!("未知的 NW.js 版本类型: {}", flavor),
+                );
+                None
+            }
+        },
+    };
+
+    if config.runner == "proton" {
+        if !cfg!(target_os = "linux") {
+            fail("runner".into(), "Proton 仅支持在 Linux 上运行".to_string());
+        } else if let Some(version) = config.proton_version.as_deref()
+            && ProtonService::find_version(Some(version)).is_none()
+        {
+            fail(
+                "protonVersion".into(),
+                format!("未找到 Proton 版本: {}", version),
+            );
+        }
+    }
+
+    // 为游戏单独指定的 bottle 优先于全局默认，保存前确认它仍存在；检测不到 Bottles CLI 时不校验
+    if let Some(bottle) = config.bottle_name.as_deref()
+        && let Some(cli) = BottlesService::detect_cli().await
+        && let Ok(bottles) = BottlesService::list_bottles(&cli).await
+        && !bottles.iter().any(|b| b == bottle)
+    {
+        fail("bottleName".into(), format!("未找到 bottle: {}", bottle));
+    }
 
+    for (index, arg) in config.args.iter().enumerate() {
+        if arg.contains(['\0', '\n', '\r']) {
+            fail(
+                format!("args[{}]", index),
+                "启动参数不能包含换行或空字符".to_string(),
+            );
+        } else if let Some(token) = arg_template::unknown_tokens(arg).first() {
+            fail(
+                format!("args[{}]", index),
+                format!(
+                    "未知的占位符 ${{{}}}，可用: {}",
+                    token,
+                    arg_template::token_list()
+                ),
+            );
+        }
+    }
+
+    let game_dir = PathBuf::from(&game.game_path);
+    let entry = config.entry_path.trim();
     let engine = EngineType::from_str(&config.engine_type);
-    let requires_entry = matches!(engine, EngineType::Other);
-    if requires_entry && config.entry_path.trim().is_empty() {
-        return Err("入口文件不能为空".to_string());
+    if entry.is_empty() {
+        if matches!(engine, EngineType::Other) {
+            fail("entryPath".into(), "入口文件不能为空".to_string());
+        }
+    } else if Path::new(entry).is_relative() && !is_under_dir(&game_dir, Path::new(entry)) {
+        fail("entryPath".into(), "入口路径必须位于游戏目录内".to_string());
     }
 
-    if let Some(cover_file) = config.cover_file.clone() {
-        let profile_dir = file_service.game_profile_dir(&root, &game.profile_key);
-        let cover_path = if Path::new(&cover_file).is_absolute() {
-            PathBuf::from(&cover_file)
+    let cover_path = config.cover_file.as_deref().map(|cover_file| {
+        if Path::new(cover_file).is_absolute() {
+            PathBuf::from(cover_file)
         } else {
-            let in_profile = profile_dir.join(&cover_file);
+            let in_profile = file_service
+                .game_profile_dir(&root, &game.profile_key)
+                .join(cover_file);
             if in_profile.exists() {
                 in_profile
             } else {
-                PathBuf::from(&game.game_path).join(&cover_file)
-            }
-        };
-        if cover_path.exists() {
-            if let Ok(saved) =
-                file_service.save_cover_to_profile(&root, &game.profile_key, &cover_path)
-            {
-                let svc = state.game_service.lock().await;
-                let _ = svc
-                    .update_cover_path(&game.id, Some(saved.to_string_lossy().to_string()))
-                    .await;
-                // 同步 cover_file 为实际保存的文件名
-                if let Some(name) = saved.file_name().and_then(|n| n.to_str()) {
-                    config.cover_file = Some(name.to_string());
-                }
+                game_dir.join(cover_file)
             }
         }
+    });
+    if let Some(cover_path) = &cover_path {
+        if !cover_path.is_file() {
+            fail("coverFile".into(), "封面文件不存在".to_string());
+        } else if image::ImageFormat::from_path(cover_path).is_err() {
+            fail("coverFile".into(), "封面文件不是支持的图片格式".to_string());
+        }
+    }
+
+    if let Some(version) = config.runtime_version.clone() {
+        let installed = tokio::task::spawn_blocking(move || nwjs::list_installed(&app))
+            .await
+            .map_err(|e| format!("扫描运行时失败: {}", e))??;
+        if !installed.iter().any(|r| r.version == version) {
+            fail(
+                "runtimeVersion".into(),
+                format!("NW.js {} 未安装，请先在设置中下载该版本", version),
+            );
+        }
+    }
+
+    if !errors.is_empty() {
+        let summary: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+        return Err(AppError::Validation {
+            message: format!("设置校验失败: {}", summary.join("；")),
+            fields: errors,
+        });
+    }
+
+    if let Some(cover_path) = cover_path
+        && let Ok(saved) = file_service.save_cover_to_profile(&root, &game.profile_key, &cover_path)
+    {
+        let svc = state.game_service.lock().await;
+        let _ = svc
+            .update_cover_path(&game.id, Some(saved.to_string_lossy().to_string()))
+            .await;
+        // 同步 cover_file 为实际保存的文件名
+        if let Some(name) = saved.file_name().and_then(|n| n.to_str()) {
+            config.cover_file = Some(name.to_string());
+        }
     }
 
     cached_write_config(
@@ -102,5 +233,122 @@ pub async fn save_game_settings(
         &config_path,
         &game.profile_key,
         &config,
+    )?;
+
+    let mut warnings = Vec::new();
+    let unredirected = config.sandbox_home.unredirected_vars();
+    if !unredirected.is_empty() {
+        warnings.push(SettingsFieldError {
+            field: "sandboxHome".into(),
+            message: format!(
+                "未隔离 {}，游戏将直接读写你的用户目录（如 ~/.config）",
+                unredirected.join("、")
+            ),
+        });
+    }
+    Ok(SaveGameSettingsResult { warnings })
+}
+
+/// 列出游戏目录中可作为启动入口的文件（相对游戏目录），供用户在自动识别错误时手动选择
+#[tauri::command]
+pub async fn list_executables(
+    id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, AppError> {
+    let game = {
+        let service = state.game_service.lock().await;
+        service
+            .get_game_by_id(&id)
+            .await?
+            .ok_or_else(|| AppError::game_not_found(&id))?
+    };
+    let game_dir = PathBuf::from(&game.game_path);
+    if !game_dir.is_dir() {
+        return Err(AppError::PathInvalid(format!(
+            "游戏路径不存在: {}",
+            game.game_path
+        )));
+    }
+    tokio::task::spawn_blocking(move || list_executable_candidates(&game_dir))
+        .await
+        .map_err(|e| AppError::Other(format!("扫描可执行文件失败: {}", e)))
+}
+
+/// 设置游戏的启动入口（相对游戏目录或绝对路径），保存到 settings.toml；传空字符串恢复自动识别
+#[tauri::command]
+pub async fn set_game_entry(
+    id: String,
+    entry_path: String,
+    state: State<'_, AppState>,
+) -> Result<(), AppError> {
+    let game = {
+        let service = state.game_service.lock().await;
+        service
+            .get_game_by_id(&id)
+            .await?
+            .ok_or_else(|| AppError::game_not_found(&id))?
+    };
+
+    let game_dir = PathBuf::from(&game.game_path);
+    let entry = entry_path.trim();
+    let entry_path = if entry.is_empty() {
+        String::new()
+    } else {
+        let path = Path::new(entry);
+        let resolved = if path.is_absolute() {
+            path.to_path_buf()
+        } else if is_under_dir(&game_dir, path) {
+            game_dir.join(path)
+        } else {
+            return Err(entry_error("入口路径必须位于游戏目录内"));
+        };
+        if !resolved.is_file() {
+            return Err(entry_error("入口文件不存在"));
+        }
+        normalize_path(&resolved)
+    };
+
+    let root = state.container_root_path().await;
+    let file_service = FileService::new();
+    let config_path = file_service.game_config_path(&root, &game.profile_key);
+    file_service.ensure_game_dirs(&root, &game.profile_key)?;
+    let mut config = cached_read_config(
+        &state.config_cache,
+        &file_service,
+        &config_path,
+        &game.profile_key,
     )
+    .unwrap_or_else(|| default_game_config(&game));
+    config.entry_path = entry_path;
+    cached_write_config(
+        &state.config_cache,
+        &file_service,
+        &config_path,
+        &game.profile_key,
+        &config,
+    )?;
+    Ok(())
+}
+
+fn entry_error(message: &str) -> AppError {
+    AppError::Validation {
+        message: message.to_string(),
+        fields: vec![SettingsFieldError {
+            field: "entryPath".into(),
+            message: message.to_string(),
+        }],
+    }
+}
+
+/// 相对路径 `relative` 拼接到 `dir` 后是否仍在 `dir` 内
+///
+/// 路径存在时按规范化后的结果判断（可识别符号链接），否则不允许出现 `..`。
+fn is_under_dir(dir: &Path, relative: &Path) -> bool {
+    let joined = dir.join(relative);
+    if joined.exists() {
+        return crate::utils::path::is_within(&joined, dir);
+    }
+    !relative
+        .components()
+        .any(|c| matches!(c, Component::ParentDir))
 }
@@ -1,27 +1,39 @@
 use super::cover::update_game_cover;
 use super::game::{
-    default_game_config, is_linux_native_entry, is_nwjs_runtime_dir, normalize_path,
+    default_game_config, find_bundled_nwjs, is_linux_native_entry, is_nwjs_runtime_dir,
+    normalize_path,
 };
+use super::game_executable::find_renpy_launch_script;
+use crate::commands::error::AppError;
 use crate::commands::state::AppState;
-use crate::models::{AddGameInput, ImportGameInput, SETTING_BOTTLES_ENABLED};
-use crate::services::FileService;
-use std::path::Path;
-use tauri::State;
+use crate::engines::context::FsDetectionContext;
+use crate::models::{
+    AddGameInput, EngineType, ImportGameDirsResult, ImportGameInput, SETTING_BOTTLES_ENABLED,
+    SandboxHome,
+};
+use crate::services::game::nwjs_entry::find_nwjs_entry;
+use crate::services::{ArchiveService, FileService};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, State};
+use uuid::Uuid;
+
+/// 支持直接导入的压缩包扩展名
+const GAME_ARCHIVE_SUFFIXES: &[&str] = &[".zip", ".7z", ".tar.gz"];
 
 /// 导入游戏目录
 #[tauri::command]
 pub async fn import_game_dir(
     input: ImportGameInput,
     state: State<'_, AppState>,
-) -> Result<crate::models::GameDto, String> {
+) -> Result<crate::models::GameDto, AppError> {
     let service = state.game_service.lock().await;
 
     let executable_path = normalize_path(Path::new(&input.executable_path));
-    let engine_type = input.engine_type;
+    let mut engine_type = input.engine_type;
 
     let exe_path = Path::new(&executable_path);
     if !exe_path.exists() || !exe_path.is_file() {
-        return Err("可执行文件不存在".to_string());
+        return Err(AppError::PathInvalid("可执行文件不存在".to_string()));
     }
 
     let game_dir = exe_path
@@ -29,7 +41,24 @@ pub async fn import_game_dir(
         .ok_or_else(|| "无法解析游戏目录".to_string())?;
 
     if is_nwjs_runtime_dir(game_dir) {
-        return Err("检测到 NW.js 运行器目录，无法作为游戏导入".to_string());
+        return Err(AppError::PathInvalid(
+            "检测到 NW.js 运行器目录，无法作为游戏导入".to_string(),
+        ));
+    }
+
+    // 未指定引擎时自动识别，识别失败返回诊断信息，由用户手动选择引擎
+    if engine_type.trim().is_empty() {
+        let registry = state.engine_registry.lock().await;
+        let ctx = FsDetectionContext::new(game_dir.to_path_buf());
+        engine_type = match registry.detect(&ctx) {
+            Some((id, _)) => id.to_string(),
+            None => {
+                return Err(AppError::engine_not_supported(
+                    game_dir,
+                    registry.explain(&ctx),
+                ));
+            }
+        };
     }
 
     let title = derive_game_title(exe_path, game_dir);
@@ -52,10 +81,10 @@ pub async fn import_game_dir(
     let file_service = FileService::new();
     let config_path = file_service.game_config_path(&root, &game.profile_key);
     if let Err(e) = file_service.ensure_game_dirs(&root, &game.profile_key) {
-        return Err(e);
+        return Err(AppError::Io(e));
     }
     let mut config = default_game_config(&game);
-    let (entry_patterns, sandbox_home) = {
+    let (entry_patterns, sandbox_home, uses_nwjs) = {
         let registry = state.engine_registry.lock().await;
         registry
             .get_entry(&engine_type)
@@ -63,24 +92,23 @@ pub async fn import_game_dir(
                 (
                     e.profile.launch.entry_patterns.clone(),
                     e.profile.launch.sandbox_home,
+                    e.profile.launch.strategy == "nwjs",
                 )
             })
-            .unwrap_or_else(|| (Vec::new(), true))
+            .unwrap_or_else(|| (Vec::new(), true, false))
     };
     // 自动模式会在启动时依据插件、入口类型和已安装运行时解析；这样在安装
     // mkxp-z 后，既有 VX/VX Ace 游戏也能自动切换，不需要重新导入。
     config.runner = "auto".to_string();
-    config.sandbox_home = sandbox_home;
+    config.sandbox_home = sandbox_home.into();
     if is_linux_native_entry(exe_path) {
         config.runner = "native".to_string();
-        config.sandbox_home = true;
+        config.sandbox_home = SandboxHome::Full;
     }
+    // 目录中自带 NW.js 时标记为自包含，启动时无需另行下载运行时
+    config.self_contained = uses_nwjs && find_bundled_nwjs(game_dir).is_some();
     if entry_patterns.is_empty() {
-        if game_dir.join("www").join("package.json").exists() {
-            config.entry_path = "www".to_string();
-        } else {
-            config.entry_path = "".to_string();
-        }
+        config.entry_path = find_nwjs_entry(game_dir).unwrap_or_default();
     } else {
         config.entry_path = executable_path.clone();
     }
@@ -107,10 +135,248 @@ pub async fn import_game_dir(
     )
     .await;
 
-    Ok(service.to_dto(game))
+    Ok(service.to_dto(game).await?)
+}
+
+/// 从压缩包导入游戏：解压到 `dest_dir` 下与压缩包同名的目录，识别引擎后按目录导入
+///
+/// 解压进度通过 `archive_import_progress` 事件发送；失败时删除已解压的文件，
+/// 但未识别出引擎时保留解压目录（路径随错误返回），供用户手动选择引擎。
+#[tauri::command]
+pub async fn import_game_archive(
+    archive_path: String,
+    dest_dir: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<crate::models::GameDto, AppError> {
+    let archive = PathBuf::from(&archive_path);
+    if !archive.is_file() {
+        return Err(AppError::PathInvalid(format!(
+            "压缩包不存在: {}",
+            archive_path
+        )));
+    }
+    let file_name = archive
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("")
+        .to_string();
+    let stem = GAME_ARCHIVE_SUFFIXES
+        .iter()
+        .find_map(|suffix| strip_suffix_ignore_case(&file_name, suffix))
+        .map(str::trim)
+        .filter(|stem| !stem.is_empty())
+        .ok_or_else(|| AppError::PathInvalid(format!("不支持的压缩格式: {}", file_name)))?
+        .to_string();
+    let dest = PathBuf::from(&dest_dir);
+    if !dest.is_dir() {
+        return Err(AppError::PathInvalid(format!(
+            "目标目录不存在: {}",
+            dest_dir
+        )));
+    }
+    let target = dest.join(&stem);
+    if target.exists() {
+        return Err(AppError::PathInvalid(format!(
+            "目标目录已存在: {}",
+            target.display()
+        )));
+    }
+
+    // 先解压到同一目录下的临时目录，保证移动到目标位置时只需重命名
+    let task_id = Uuid::new_v4().to_string();
+    let staging = dest.join(format!(".{}.extracting-{}", stem, task_id));
+    let extracted = {
+        let archive = archive.clone();
+        let staging = staging.clone();
+        let target = target.clone();
+        let app = app.clone();
+        let task_id = task_id.clone();
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let archive_service = ArchiveService::new();
+            crate::utils::path::ensure_dir(&staging)?;
+            let last_percent = std::cell::Cell::new(None::<u8>);
+            let on_progress = |done: u64, total: u64| {
+                if total == 0 {
+                    return;
+                }
+                let percent = ((done as f64 / total as f64) * 100.0).floor().min(100.0) as u8;
+                if last_percent.replace(Some(percent)) == Some(percent) {
+                    return;
+                }
+                let _ = app.emit(
+                    "archive_import_progress",
+                    serde_json::json!({
+                        "taskId": task_id,
+                        "label": format!("正在解压… {percent}%"),
+                        "progress": percent,
+                    }),
+                );
+            };
+            archive_service.extract_auto_with_progress(&archive, &staging, Some(&on_progress))?;
+            // 压缩包顶层只有一个目录时直接使用该目录，避免多一层嵌套
+            let root = archive_service
+                .find_single_root_dir(&staging)
+                .unwrap_or_else(|| staging.clone());
+            archive_service.move_dir(&root, &target)?;
+            archive_service.remove_dir_if_exists(&staging)
+        })
+        .await
+        .unwrap_or_else(|e| Err(format!("解压失败: {}", e)))
+    };
+    if let Err(e) = extracted {
+        let _ = std::fs::remove_dir_all(&staging);
+        let _ = std::fs::remove_dir_all(&target);
+        return Err(AppError::Io(e));
+    }
+
+    let result = import_detected_dir(&target, state).await;
+    // 未识别出引擎时保留解压结果，用户可在错误返回的目录上手动选择引擎导入
+    if let Err(e) = &result
+        && !matches!(e, AppError::EngineNotSupported { .. })
+    {
+        let _ = std::fs::remove_dir_all(&target);
+    }
+    let _ = app.emit(
+        "archive_import_progress",
+        serde_json::json!({
+            "taskId": task_id,
+            "label": if result.is_ok() { "导入完成" } else { "导入失败" },
+            "progress": 100,
+        }),
+    );
+    result
+}
+
+/// 批量导入游戏目录（如一次拖入多个文件夹），逐个识别引擎后导入
+///
+/// 已在库中的目录计入 `skipped`，单个目录失败不影响其它目录；
+/// 每处理完一个目录发送一次 `import_dirs_progress` 事件。
+#[tauri::command]
+pub async fn import_game_dirs(
+    paths: Vec<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ImportGameDirsResult, AppError> {
+    let task_id = Uuid::new_v4().to_string();
+    let total = paths.len();
+    let mut result = ImportGameDirsResult {
+        imported: Vec::new(),
+        skipped: Vec::new(),
+        failed: Vec::new(),
+    };
+
+    for (index, path) in paths.into_iter().enumerate() {
+        let input = Path::new(&path);
+        let dir = if input.is_file() {
+            input.parent().unwrap_or(input)
+        } else {
+            input
+        };
+
+        if !dir.is_dir() {
+            result
+                .failed
+                .push((path.clone(), format!("目录不存在: {}", path)));
+        } else {
+            let existing = state
+                .game_service
+                .lock()
+                .await
+                .get_game_by_path(&normalize_path(dir))
+                .await?;
+            if existing.is_some() {
+                result.skipped.push(path.clone());
+            } else {
+                match import_detected_dir(dir, state.clone()).await {
+                    Ok(game) => result.imported.push(game),
+                    Err(e) => {
+                        tracing::warn!(path = %path, error = %e, "导入游戏目录失败");
+                        result.failed.push((path.clone(), e.to_string()));
+                    }
+                }
+            }
+        }
+
+        let _ = app.emit(
+            "import_dirs_progress",
+            serde_json::json!({
+                "taskId": task_id,
+                "path": path,
+                "label": format!("已处理 {}/{}", index + 1, total),
+                "progress": ((index + 1) * 100 / total.max(1)) as u8,
+            }),
+        );
+    }
+
+    Ok(result)
+}
+
+/// 识别目录的引擎与入口文件，再按目录导入
+async fn import_detected_dir(
+    game_dir: &Path,
+    state: State<'_, AppState>,
+) -> Result<crate::models::GameDto, AppError> {
+    let (engine_type, entry_exe) = {
+        let registry = state.engine_registry.lock().await;
+        let ctx = FsDetectionContext::new(game_dir.to_path_buf());
+        let Some((engine_type, _)) = registry.detect(&ctx) else {
+            return Err(AppError::engine_not_supported(
+                game_dir,
+                registry.explain(&ctx),
+            ));
+        };
+        let mut entry_exe = None;
+        if EngineType::from_str(engine_type) == EngineType::RenPy {
+            entry_exe = find_renpy_launch_script(game_dir);
+        }
+        if entry_exe.is_none()
+            && let Some(entry) = registry.get_entry(engine_type)
+        {
+            entry_exe = crate::engines::find_executable(
+                game_dir,
+                &entry.profile.launch.entry_patterns,
+                &entry.profile.launch.exclude_patterns,
+            );
+        }
+        (engine_type.to_string(), entry_exe)
+    };
+    // MV/MZ 没有固定的可执行文件，以游戏目录中的 package.json 或 index.html 作为导入依据
+    let entry_exe = entry_exe
+        .or_else(|| {
+            ["package.json", "index.html"]
+                .iter()
+                .map(|name| game_dir.join(name))
+                .find(|path| path.is_file())
+        })
+        .ok_or_else(|| {
+            AppError::Other(format!("未找到游戏的可执行文件: {}", game_dir.display()))
+        })?;
+
+    import_game_dir(
+        ImportGameInput {
+            executable_path: entry_exe.to_string_lossy().to_string(),
+            engine_type,
+        },
+        state,
+    )
+    .await
+}
+
+fn strip_suffix_ignore_case<'a>(name: &'a str, suffix: &str) -> Option<&'a str> {
+    let split = name.len().checked_sub(suffix.len())?;
+    if name.is_char_boundary(split) && name[split..].eq_ignore_ascii_case(suffix) {
+        Some(&name[..split])
+    } else {
+        None
+    }
 }
 
 fn derive_game_title(exe_path: &Path, game_dir: &Path) -> String {
+    if let Some(title) = crate::services::game::title::read_game_title(game_dir) {
+        return title;
+    }
+
     let stem = exe_path
         .file_stem()
         .and_then(|s| s.to_str())
@@ -1,24 +1,79 @@
-use super::game::{default_game_config, is_linux_native_entry, normalize_path};
+use super::game::{default_game_config, find_bundled_nwjs, is_linux_native_entry, normalize_path};
+use crate::commands::error::AppError;
+use crate::commands::settings::settings::{load_engine_defaults, load_launch_limit};
 use crate::commands::state::AppState;
-use crate::models::{EngineType, LaunchResult, SETTING_BOTTLES_DEFAULT, SETTING_BOTTLES_ENABLED};
+use crate::db::schema::Engine;
+use crate::models::{
+    EngineType, ExternalToolPaths, LaunchResult, SETTING_ALLOW_HOOKS, SETTING_BOTTLES_DEFAULT,
+    SETTING_BOTTLES_ENABLED, SETTING_EASYRPG_BINARY, SETTING_LAUNCH_IN_TERMINAL,
+    SETTING_MINIMIZE_ON_LAUNCH, SETTING_WINE_BINARY, SandboxHome,
+};
 use crate::services::FileService;
+use crate::services::download::nwjs::{self, NwjsFlavor};
+use crate::services::game::hooks::{self, HookContext};
+use crate::services::game::launcher::{LaunchSettings, NwjsRuntime};
+use crate::services::game::nwjs_entry::find_nwjs_entry;
+use crate::services::game::session::{self, RunningGameInfo};
+use serde::Serialize;
 use std::path::{Path, PathBuf};
-use tauri::State;
+use std::sync::Mutex as StdMutex;
+use tauri::{AppHandle, Emitter, Manager, State};
 
-/// 启动游戏
+/// 本次运行中已提示过缺失的启动包装工具，每个工具只提示一次
+static WARNED_LAUNCH_TOOLS: StdMutex<Vec<&'static str>> = StdMutex::new(Vec::new());
+
+/// `launch_tool_missing` 事件负载
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LaunchToolMissingEvent {
+    game_id: String,
+    /// 缺失的程序名（gamemoderun / mangohud），开启 devtools 但未安装 SDK 版时为 nwjs-sdk
+    tool: &'static str,
+}
+
+/// `runtime_target_mismatch` 事件负载
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RuntimeTargetMismatchEvent {
+    game_id: String,
+    version: String,
+    /// 运行时构建的目标平台
+    runtime_target: String,
+    /// 当前平台
+    current_target: String,
+}
+
+/// `launch_queued` 事件负载：已达同时运行上限，启动请求进入排队
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LaunchQueuedEvent {
+    game_id: String,
+    max_concurrent: u32,
+}
+
+/// `queued_launch_started` 事件负载：排队的启动请求已实际启动
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct QueuedLaunchStartedEvent {
+    game_id: String,
+    pid: u32,
+}
+
+/// 启动游戏，并在后台等待进程退出后发送 `game_exited` 事件
+///
+/// 设置了同时运行上限且名额已满时，按设置直接报错或排队等待，排队时命令在实际启动后才返回。
 #[tauri::command]
-pub async fn launch_game(id: String, state: State<'_, AppState>) -> Result<LaunchResult, String> {
+pub async fn launch_game(
+    id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<LaunchResult, AppError> {
     let game_service = state.game_service.lock().await;
     let game = game_service
         .get_game_by_id(&id)
         .await?
-        .ok_or_else(|| format!("游戏不存在: {}", id))?;
-
-    // 记录启动日志
-    crate::services::logger::log_game_launch(&id, &game.title, &game.engine_type);
+        .ok_or_else(|| AppError::game_not_found(&id))?;
 
-    // 更新最后游玩时间
-    game_service.update_last_played(&id).await?;
     drop(game_service);
 
     let container_path = state.container_root_path().await;
@@ -30,6 +85,58 @@ pub async fn launch_game(id: String, state: State<'_, AppState>) -> Result<Launc
         Some(default_game_config(&game))
     };
 
+    // 同一游戏默认只允许一个实例，避免多个进程写同一个 --user-data-dir
+    let allow_multiple = config.as_ref().is_some_and(|c| c.allow_multiple);
+    // 检查与占位是原子的，占位保留到进程登记到运行表，双击产生的两次调用只有一次能启动
+    let reservation = if allow_multiple {
+        None
+    } else {
+        Some(session::reserve_launch(
+            &state.running_games,
+            &state.launching_games,
+            &game.id,
+        )?)
+    };
+
+    // 每个运行中的游戏占用一个名额，退出后释放
+    let limit = load_launch_limit(&mut *state.db.lock().await).await?;
+    state.launch_slots.set_limit(limit.max_concurrent);
+    let mut queued = false;
+    let launch_slot = match state.launch_slots.try_acquire() {
+        Some(slot) => slot,
+        None if limit.queue => {
+            let _ = app.emit(
+                "launch_queued",
+                LaunchQueuedEvent {
+                    game_id: game.id.clone(),
+                    max_concurrent: limit.max_concurrent,
+                },
+            );
+            let slot = state.launch_slots.acquire().await?;
+            queued = true;
+            slot
+        }
+        None => {
+            return Err(AppError::Other(format!(
+                "同时运行的游戏已达上限（{}），请先关闭其它游戏",
+                limit.max_concurrent
+            )));
+        }
+    };
+
+    // 记录启动日志
+    crate::services::logger::log_game_launch(&id, &game.title, &game.engine_type);
+
+    // 更新最后游玩时间
+    state
+        .game_service
+        .lock()
+        .await
+        .update_last_played(&id)
+        .await?;
+    #[cfg(feature = "tray")]
+    crate::tray::refresh_menu(&app).await;
+
     // 获取 NW.js 运行时（MV/MZ 及所有 nwjs 策略的引擎，如 HTML）
     let engine_type = EngineType::from_str(&game.engine_type);
     let needs_nwjs = {
@@ -37,22 +144,121 @@ pub async fn launch_game(id: String, state: State<'_, AppState>) -> Result<Launc
         let runner = config.as_ref().map(|c| c.runner.as_str());
         if runner == Some("nwjs") {
             true
-        } else if runner == Some("native") || runner == Some("bottles") || runner == Some("mkxpz") {
+        } else if matches!(runner, Some("native" | "bottles" | "mkxpz" | "proton")) {
             false
         } else if let Some(entry) = registry.get_entry(&game.engine_type) {
             entry.profile.launch.strategy == "nwjs"
         } else {
-            matches!(engine_type, EngineType::RpgMakerMV | EngineType::RpgMakerMZ)
+            matches!(
+                engine_type,
+                EngineType::RpgMakerMV | EngineType::RpgMakerMZ | EngineType::Tyrano
+            )
         }
     };
-    let nwjs_runtime_dir = if needs_nwjs {
-        let engine_service = state.engine_service.lock().await;
-        let engine = if let Some(version) = game.runtime_version.as_deref() {
-            engine_service.find_engine("nwjs", Some(version)).await?
+    // 自包含游戏使用目录中自带的 NW.js；固定了运行时版本时优先使用已下载的版本
+    let bundled_nwjs = config
+        .as_ref()
+        .filter(|c| needs_nwjs && c.self_contained)
+        .and_then(|_| find_bundled_nwjs(Path::new(&game.game_path)));
+    let nwjs_runtime = if let Some(dir) = bundled_nwjs
+        .clone()
+        .filter(|_| game.runtime_version.is_none())
+    {
+        Some(NwjsRuntime {
+            dir,
+            devtools_port: None,
+        })
+    } else if needs_nwjs {
+        let flavor = match config
+            .as_ref()
+            .and_then(|c| c.flavor.as_deref())
+            .and_then(NwjsFlavor::parse)
+        {
+            Some(flavor) => flavor,
+            None => nwjs::resolve_default_flavor(&mut *state.db.lock().await).await?,
+        };
+        let engines = state
+            .engine_service
+            .lock()
+            .await
+            .list_engines_by_type("nwjs")
+            .await?;
+        let version = game.runtime_version.as_deref();
+        let version_matches = |e: &&Engine| version.is_none_or(|v| e.version == v);
+        // 所选 flavor 未安装时依次回退到普通版、任意已安装版本
+        let engine = engines
+            .iter()
+            .filter(version_matches)
+            .find(|e| flavor.matches_engine_name(&e.name))
+            .or_else(|| {
+                engines
+                    .iter()
+                    .filter(version_matches)
+                    .find(|e| NwjsFlavor::Normal.matches_engine_name(&e.name))
+            })
+            .or_else(|| engines.iter().find(version_matches));
+        if let Some(engine) = engine
+            && !flavor.matches_engine_name(&engine.name)
+        {
+            tracing::info!(
+                game_id = %game.id,
+                flavor = flavor.as_str(),
+                fallback = %engine.name,
+                "所选 NW.js 版本未安装，已回退"
+            );
+        }
+        // devtools 仅 SDK 版可用，普通版忽略该选项并提示
+        let wants_devtools = config.as_ref().is_some_and(|c| c.devtools);
+        let is_sdk = engine.is_some_and(|e| NwjsFlavor::Sdk.matches_engine_name(&e.name));
+        if wants_devtools && engine.is_some() && !is_sdk {
+            tracing::warn!(game_id = %game.id, "已开启 devtools，但未安装 SDK 版 NW.js");
+            let _ = app.emit(
+                "launch_tool_missing",
+                LaunchToolMissingEvent {
+                    game_id: game.id.clone(),
+                    tool: "nwjs-sdk",
+                },
+            );
+        }
+        // 运行时与当前平台架构不一致时（如 arm64 上只有 x64 版）仍尝试启动，但提示用户
+        if let Some(engine) = engine
+            && let Some(runtime_target) = engine.target.as_deref()
+            && let Ok(current_target) = nwjs::current_target()
+            && runtime_target != current_target
+        {
+            tracing::warn!(
+                game_id = %game.id,
+                version = %engine.version,
+                runtime_target,
+                current_target = %current_target,
+                "NW.js 运行时与当前平台不一致"
+            );
+            let _ = app.emit(
+                "runtime_target_mismatch",
+                RuntimeTargetMismatchEvent {
+                    game_id: game.id.clone(),
+                    version: engine.version.clone(),
+                    runtime_target: runtime_target.to_string(),
+                    current_target,
+                },
+            );
+        }
+        let devtools_port = if wants_devtools && is_sdk {
+            free_local_port()
         } else {
-            engine_service.find_latest_engine_by_type("nwjs").await?
+            None
         };
-        engine.map(|e| PathBuf::from(e.engine_path))
+        engine
+            .map(|e| NwjsRuntime {
+                dir: PathBuf::from(&e.engine_path),
+                devtools_port,
+            })
+            .or_else(|| {
+                bundled_nwjs.map(|dir| NwjsRuntime {
+                    dir,
+                    devtools_port: None,
+                })
+            })
     } else {
         None
     };
@@ -65,7 +271,7 @@ pub async fn launch_game(id: String, state: State<'_, AppState>) -> Result<Launc
             || (runner == Some("auto")
                 && matches!(
                     engine_type,
-                    EngineType::RpgMakerVX | EngineType::RpgMakerVXAce
+                    EngineType::RpgMakerXP | EngineType::RpgMakerVX | EngineType::RpgMakerVXAce
                 ))
     };
     let mkxpz_runtime_dir = if needs_mkxpz {
@@ -76,10 +282,24 @@ pub async fn launch_game(id: String, state: State<'_, AppState>) -> Result<Launc
         None
     };
 
-    if needs_nwjs && nwjs_runtime_dir.is_none() {
-        return Err("未安装 NW.js 运行时，请先下载并安装".to_string());
+    if needs_nwjs && nwjs_runtime.is_none() {
+        return Err(AppError::RuntimeMissing(
+            "未安装 NW.js 运行时，请先下载并安装".to_string(),
+        ));
     }
 
+    let tools = {
+        let mut db_lock = state.db.lock().await;
+        ExternalToolPaths {
+            wine_binary: crate::db::get_setting(&mut *db_lock, SETTING_WINE_BINARY).await?,
+            easyrpg_binary: crate::db::get_setting(&mut *db_lock, SETTING_EASYRPG_BINARY).await?,
+        }
+    };
+    let has_easyrpg = tools
+        .easyrpg_binary
+        .as_deref()
+        .is_some_and(|s| !s.trim().is_empty());
+
     if let Some(cfg) = config.as_mut() {
         if cfg.entry_path.trim().is_empty() {
             let entry_patterns = {
@@ -90,15 +310,7 @@ pub async fn launch_game(id: String, state: State<'_, AppState>) -> Result<Launc
                     .unwrap_or_default()
             };
             if entry_patterns.is_empty() {
-                if Path::new(&game.game_path)
-                    .join("www")
-                    .join("package.json")
-                    .exists()
-                {
-                    cfg.entry_path = "www".to_string();
-                } else {
-                    cfg.entry_path = "".to_string();
-                }
+                cfg.entry_path = find_nwjs_entry(Path::new(&game.game_path)).unwrap_or_default();
             } else {
                 let registry = state.engine_registry.lock().await;
                 if let Some(entry) = registry.get_entry(&game.engine_type) {
@@ -115,7 +327,8 @@ pub async fn launch_game(id: String, state: State<'_, AppState>) -> Result<Launc
             }
         }
 
-        if cfg.runner == "auto" {
+        let auto_runner = cfg.runner == "auto";
+        if auto_runner {
             let configured_entry = PathBuf::from(&cfg.entry_path);
             let entry = if configured_entry.is_absolute() {
                 configured_entry
@@ -124,7 +337,7 @@ pub async fn launch_game(id: String, state: State<'_, AppState>) -> Result<Launc
             };
             if is_linux_native_entry(&entry) {
                 cfg.runner = "native".to_string();
-                cfg.sandbox_home = true;
+                cfg.sandbox_home = SandboxHome::Full;
             } else {
                 let registry = state.engine_registry.lock().await;
                 if let Some(engine) = registry.get_entry(&game.engine_type) {
@@ -135,17 +348,27 @@ pub async fn launch_game(id: String, state: State<'_, AppState>) -> Result<Launc
                     } else {
                         strategy.to_string()
                     };
-                    cfg.sandbox_home = engine.profile.launch.sandbox_home;
+                    // 用户主动关闭或选择仅重定向 XDG 时保留其设置
+                    if cfg.sandbox_home == SandboxHome::Full {
+                        cfg.sandbox_home = engine.profile.launch.sandbox_home.into();
+                    }
                 } else {
                     cfg.runner = "bottles".to_string();
                 }
             }
         }
 
+        // 已配置 EasyRPG Player 时，RPG Maker 2000/2003 直接原生运行
+        if auto_runner && has_easyrpg && engine_type == EngineType::RpgMaker2000 {
+            cfg.runner = "native".to_string();
+            cfg.use_bottles = false;
+            cfg.bottle_name = None;
+        }
+
         if cfg.runner == "mkxpz" && mkxpz_runtime_dir.is_none() {
-            return Err(
+            return Err(AppError::RuntimeMissing(
                 "未安装 mkxp-z 运行时，请先下载并安装，或将启动方式改为 Bottles".to_string(),
-            );
+            ));
         }
 
         let mut db_lock = state.db.lock().await;
@@ -162,8 +385,26 @@ pub async fn launch_game(id: String, state: State<'_, AppState>) -> Result<Launc
                 cfg.use_bottles = true;
             }
             if !enabled {
+                // RGSS、WOLF RPG、RPG Maker 2000、吉里吉里游戏在 Linux 上可以改用 wine 直接运行
+                if auto_runner
+                    && cfg.runner == "bottles"
+                    && cfg!(target_os = "linux")
+                    && matches!(
+                        engine_type,
+                        EngineType::RpgMaker2000
+                            | EngineType::RpgMakerXP
+                            | EngineType::RpgMakerVX
+                            | EngineType::RpgMakerVXAce
+                            | EngineType::WolfRpg
+                            | EngineType::KiriKiri
+                    )
+                {
+                    cfg.runner = "native".to_string();
+                }
                 if cfg.runner == "bottles" {
-                    return Err("当前游戏指定使用 Bottles，但 Bottles 集成未启用".to_string());
+                    return Err(AppError::RuntimeMissing(
+                        "当前游戏指定使用 Bottles，但 Bottles 集成未启用".to_string(),
+                    ));
                 }
                 cfg.use_bottles = false;
                 cfg.bottle_name = None;
@@ -174,21 +415,214 @@ pub async fn launch_game(id: String, state: State<'_, AppState>) -> Result<Launc
                 if let Some(name) = default_bottle {
                     cfg.bottle_name = Some(name);
                 } else {
-                    return Err("请选择 Bottles bottle".to_string());
+                    return Err(AppError::Other(
+                        "已启用 Bottles，但未选择默认 bottle，请在设置中选择或为该游戏指定 bottle"
+                            .to_string(),
+                    ));
                 }
             }
         }
     }
 
+    // 启动钩子需在全局设置中允许；启动前钩子失败时中止启动
+    let allow_hooks = {
+        let mut db_lock = state.db.lock().await;
+        crate::db::get_setting(&mut *db_lock, SETTING_ALLOW_HOOKS)
+            .await?
+            .is_some_and(|v| v == "1")
+    };
+    let hook_ctx = HookContext {
+        game_id: game.id.clone(),
+        game_dir: game.game_path.clone(),
+        profile_dir: file_service
+            .game_profile_dir(&container_path, &game.profile_key)
+            .to_string_lossy()
+            .to_string(),
+    };
+    let (pre_launch, post_exit) = match config.as_ref() {
+        Some(cfg) if allow_hooks => (cfg.pre_launch.clone(), cfg.post_exit.clone()),
+        Some(cfg) => {
+            if cfg.pre_launch.is_some() || cfg.post_exit.is_some() {
+                tracing::warn!(game_id = %game.id, "启动钩子未在设置中允许，已跳过");
+            }
+            (None, None)
+        }
+        None => (None, None),
+    };
+    if let Some(command) = pre_launch {
+        let ctx = hook_ctx.clone();
+        tokio::task::spawn_blocking(move || hooks::run_hook("启动前钩子", &command, &ctx))
+            .await
+            .map_err(|e| format!("执行启动前钩子失败: {}", e))??;
+    }
+
+    let launch_settings = {
+        let mut db_lock = state.db.lock().await;
+        let default_args = load_engine_defaults(&mut *db_lock, &game.engine_type).await?;
+        let launch_in_terminal = crate::db::get_setting(&mut *db_lock, SETTING_LAUNCH_IN_TERMINAL)
+            .await?
+            .is_some_and(|v| v == "1");
+        LaunchSettings {
+            tools,
+            default_args,
+            launch_in_terminal,
+        }
+    };
+
+    // 终端模式下跟踪的是终端进程而不是游戏：不记录游玩时长、不占用同时运行名额、
+    // 不最小化主窗口，也不执行退出后钩子
+    let in_terminal = launch_settings.in_terminal(config.as_ref());
+    let post_exit = post_exit.filter(|_| {
+        if in_terminal {
+            tracing::warn!(game_id = %game.id, "终端模式下无法检测游戏退出，已跳过退出后钩子");
+        }
+        !in_terminal
+    });
+    let launch_slot = (!in_terminal).then_some(launch_slot);
+
     // 启动游戏
     let launcher_service = state.launcher_service.lock().await;
-    launcher_service
+    if let Some(cfg) = config.as_ref() {
+        for tool in launcher_service.missing_launch_tools(cfg) {
+            let mut warned = WARNED_LAUNCH_TOOLS.lock().unwrap();
+            if !warned.contains(&tool) {
+                warned.push(tool);
+                let _ = app.emit(
+                    "launch_tool_missing",
+                    LaunchToolMissingEvent {
+                        game_id: game.id.clone(),
+                        tool,
+                    },
+                );
+            }
+        }
+    }
+    let child = launcher_service
         .launch_game_with_runtimes(
             &game,
             &container_path,
-            nwjs_runtime_dir.as_deref(),
+            nwjs_runtime.as_ref(),
             mkxpz_runtime_dir.as_deref(),
+            &launch_settings,
             config.as_ref(),
         )
+        .await?;
+    drop(launcher_service);
+
+    let pid = session::track(&state.running_games, &game.id, child);
+    drop(reservation);
+    if queued {
+        let _ = app.emit(
+            "queued_launch_started",
+            QueuedLaunchStartedEvent {
+                game_id: game.id.clone(),
+                pid,
+            },
+        );
+    }
+
+    // 启动后最小化主窗口，最后一个游戏退出后恢复
+    let minimize = match config.as_ref().and_then(|c| c.minimize_on_launch) {
+        Some(minimize) => minimize,
+        None => crate::db::get_setting(&mut *state.db.lock().await, SETTING_MINIMIZE_ON_LAUNCH)
+            .await?
+            .is_some_and(|v| v == "1"),
+    };
+    let main_window = app
+        .get_webview_window(crate::services::window_state::MAIN_WINDOW)
+        .filter(|_| minimize && !in_terminal);
+    if let Some(window) = main_window.as_ref() {
+        let _ = window.minimize();
+    }
+
+    let game_service = state.game_service.lock().await.clone();
+    let running_games = state.running_games.clone();
+    session::spawn_exit_watcher(
+        app,
+        state.running_games.clone(),
+        game.id.clone(),
+        pid,
+        move |event| {
+            // 先释放名额，让排队的启动请求继续
+            drop(launch_slot);
+            if let Some(window) = main_window
+                && session::list(&running_games).is_empty()
+            {
+                let _ = window.unminimize();
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+            if in_terminal {
+                return;
+            }
+            // 游玩时长在进程退出时按本次会话的实际运行时间累加
+            let game_id = event.game_id.clone();
+            let duration_ms = event.duration_ms;
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = game_service.add_play_time(&game_id, duration_ms).await {
+                    tracing::warn!(game_id = %game_id, error = %e, "记录游玩时长失败");
+                }
+            });
+            // 退出后钩子放到独立线程执行，不延迟 `game_exited` 事件
+            if let Some(command) = post_exit {
+                tauri::async_runtime::spawn_blocking(move || {
+                    if let Err(e) = hooks::run_hook("退出后钩子", &command, &hook_ctx) {
+                        tracing::warn!(game_id = %hook_ctx.game_id, error = %e, "退出后钩子执行失败");
+                    }
+                });
+            }
+        },
+    );
+
+    Ok(LaunchResult {
+        pid,
+        devtools_port: nwjs_runtime.and_then(|r| r.devtools_port),
+        tracked: !in_terminal,
+    })
+}
+
+/// 向系统申请一个空闲的本地端口
+fn free_local_port() -> Option<u16> {
+    let listener = std::net::TcpListener::bind(("127.0.0.1", 0)).ok()?;
+    Some(listener.local_addr().ok()?.port())
+}
+
+/// 获取当前运行中的游戏实例
+///
+/// 除本次启动并跟踪的进程外，Linux 上还会找回应用重启前启动、仍在运行的游戏。
+#[tauri::command]
+pub async fn get_running_games(
+    state: State<'_, AppState>,
+) -> Result<Vec<RunningGameInfo>, AppError> {
+    let games: Vec<(String, PathBuf)> = {
+        let service = state.game_service.lock().await;
+        service
+            .get_all_games()
+            .await?
+            .into_iter()
+            .map(|g| {
+                let dir = crate::utils::path::canonicalize(Path::new(&g.game_path));
+                (g.id, dir)
+            })
+            .collect()
+    };
+    let running = state.running_games.clone();
+    let list = tokio::task::spawn_blocking(move || {
+        let mut list = session::list(&running);
+        list.extend(session::find_untracked(&running, &games));
+        list
+    })
+    .await
+    .map_err(|e| format!("获取运行中的游戏失败: {}", e))?;
+    Ok(list)
+}
+
+/// 终止正在运行的游戏
+#[tauri::command]
+pub async fn stop_game(id: String, state: State<'_, AppState>) -> Result<(), AppError> {
+    let running = state.running_games.clone();
+    tokio::task::spawn_blocking(move || session::stop(&running, &id))
         .await
+        .map_err(|e| format!("终止游戏失败: {}", e))??;
+    Ok(())
 }
@@ -0,0 +1,432 @@
+use super::game::{default_game_config, normalize_path};
+use crate::commands::error::AppError;
+use crate::commands::state::{AppState, cache_remove};
+use crate::db::schema::Game;
+use crate::models::{
+    AddGameInput, DeduplicateResult, GameStatus, ImportLibraryResult, LIBRARY_MANIFEST_VERSION,
+    LibraryEntry, LibraryManifest, UpdateGameInput,
+};
+use crate::services::{FileService, GameService};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+/// 导出游戏库（游戏记录 + settings.toml）到 JSON 清单，返回导出的游戏数
+#[tauri::command]
+pub async fn export_library(
+    dest_path: String,
+    state: State<'_, AppState>,
+) -> Result<u32, AppError> {
+    let (games, mut tags) = {
+        let service = state.game_service.lock().await;
+        (
+            service.get_all_games().await?,
+            service.get_all_tags().await?,
+        )
+    };
+    let root = state.container_root_path().await;
+    let file_service = FileService::new();
+
+    let entries: Vec<LibraryEntry> = games
+        .into_iter()
+        .map(|game| {
+            let config_path = file_service.game_config_path(&root, &game.profile_key);
+            let config = file_service.read_game_config(&config_path).ok();
+            let cover_file = config
+                .as_ref()
+                .and_then(|c| c.cover_file.clone())
+                .or_else(|| {
+                    game.cover_path.as_deref().and_then(|p| {
+                        Path::new(p)
+                            .file_name()
+                            .map(|n| n.to_string_lossy().to_string())
+                    })
+                });
+            LibraryEntry {
+                tags: tags.remove(&game.id).unwrap_or_default(),
+                title: game.title,
+                engine_type: game.engine_type,
+                path: game.game_path,
+                game_type: game.game_type,
+                detection_confidence: game.detection_confidence,
+                runtime_version: game.runtime_version,
+                cover_file,
+                favorite: game.favorite,
+                rating: game.rating,
+                status: Some(game.status),
+                play_count: game.play_count,
+                total_play_ms: game.total_play_ms,
+                last_played_at: game.last_played_at,
+                created_at: game.created_at,
+                metadata_json: game.metadata_json,
+                config,
+            }
+        })
+        .collect();
+
+    let count = entries.len() as u32;
+    let manifest = LibraryManifest {
+        version: LIBRARY_MANIFEST_VERSION,
+        exported_at: crate::utils::now_unix_ms(),
+        games: entries,
+    };
+    let json =
+        serde_json::to_string_pretty(&manifest).map_err(|e| format!("序列化游戏库失败: {}", e))?;
+
+    // 先写临时文件再重命名，避免中途失败留下半个清单
+    let dest = Path::new(&dest_path);
+    if let Some(parent) = dest.parent() {
+        crate::utils::path::ensure_dir(parent)?;
+    }
+    let tmp_path = dest.with_extension("json.tmp");
+    std::fs::write(&tmp_path, json).map_err(|e| format!("写入游戏库清单失败: {}", e))?;
+    std::fs::rename(&tmp_path, dest).map_err(|e| {
+        let _ = std::fs::remove_file(&tmp_path);
+        format!("写入游戏库清单失败: {}", e)
+    })?;
+
+    Ok(count)
+}
+
+/// 从 JSON 清单导入游戏库
+///
+/// `merge` 为 false 时先清空现有游戏库。路径已存在的游戏跳过；
+/// 路径不存在的游戏照常导入，由 `path_valid` 标记。
+#[tauri::command]
+pub async fn import_library(
+    src_path: String,
+    merge: bool,
+    state: State<'_, AppState>,
+) -> Result<ImportLibraryResult, AppError> {
+    let content = std::fs::read_to_string(&src_path)
+        .map_err(|e| AppError::Io(format!("读取游戏库清单失败: {}", e)))?;
+    let manifest: LibraryManifest =
+        serde_json::from_str(&content).map_err(|e| format!("解析游戏库清单失败: {}", e))?;
+    if manifest.version > LIBRARY_MANIFEST_VERSION {
+        return Err(AppError::Other(format!(
+            "游戏库清单版本 {} 高于当前支持的版本 {}",
+            manifest.version, LIBRARY_MANIFEST_VERSION
+        )));
+    }
+
+    let service = state.game_service.lock().await;
+    if !merge {
+        service.delete_all_games().await?;
+        state.config_cache.lock().unwrap().clear();
+    }
+
+    let mut existing_paths: HashSet<String> = service
+        .get_all_games()
+        .await?
+        .into_iter()
+        .map(|g| normalize_path(Path::new(&g.game_path)))
+        .collect();
+    let root = state.container_root_path().await;
+
+    let mut result = ImportLibraryResult {
+        added: 0,
+        skipped: 0,
+        failed: 0,
+    };
+    for entry in manifest.games {
+        let path = normalize_path(Path::new(&entry.path));
+        if existing_paths.contains(&path) {
+            result.skipped += 1;
+            continue;
+        }
+
+        match import_entry(&service, &root, entry).await {
+            Ok(()) => {
+                existing_paths.insert(path);
+                result.added += 1;
+            }
+            Err(e) => {
+                tracing::warn!(path = %path, error = %e, "导入游戏失败");
+                result.failed += 1;
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// 合并路径相同（规范化后）的重复游戏记录
+///
+/// 每组保留收藏的、其次是最早添加的记录，把游玩次数与时长累加、标签合并到该记录，
+/// 删除其余记录。保留记录没有 profile 目录时沿用重复记录的目录，否则删除重复记录的目录。
+#[tauri::command]
+pub async fn deduplicate_games(state: State<'_, AppState>) -> Result<DeduplicateResult, AppError> {
+    let service = state.game_service.lock().await;
+    let mut tags = service.get_all_tags().await?;
+
+    let mut groups: HashMap<String, Vec<Game>> = HashMap::new();
+    for game in service.get_all_games().await? {
+        groups.entry(dedupe_key(&game)).or_default().push(game);
+    }
+
+    let root = state.container_root_path().await;
+    let file_service = FileService::new();
+    let mut result = DeduplicateResult {
+        groups: 0,
+        merged: 0,
+        profiles_removed: 0,
+        save_backups: Vec::new(),
+    };
+    for (path, mut games) in groups {
+        if games.len() < 2 {
+            continue;
+        }
+        games.sort_by(|a, b| {
+            b.favorite
+                .cmp(&a.favorite)
+                .then(a.created_at.cmp(&b.created_at))
+                .then(a.id.cmp(&b.id))
+        });
+        let duplicates = games.split_off(1);
+        let keeper = games.remove(0);
+
+        let mut merged_tags = tags.remove(&keeper.id).unwrap_or_default();
+        for dup in &duplicates {
+            merged_tags.extend(tags.remove(&dup.id).unwrap_or_default());
+        }
+        service
+            .restore_stats(
+                &keeper.id,
+                keeper.favorite || duplicates.iter().any(|g| g.favorite),
+                keeper.play_count + duplicates.iter().map(|g| g.play_count).sum::<i64>(),
+                keeper.total_play_ms + duplicates.iter().map(|g| g.total_play_ms).sum::<i64>(),
+                duplicates
+                    .iter()
+                    .filter_map(|g| g.last_played_at)
+                    .chain(keeper.last_played_at)
+                    .max(),
+            )
+            .await?;
+        if keeper.rating.is_none()
+            && let Some(rating) = duplicates.iter().find_map(|g| g.rating)
+        {
+            service.set_rating(&keeper.id, Some(rating)).await?;
+        }
+        service.set_tags(&keeper.id, merged_tags).await?;
+
+        let keeper_profile = file_service.game_profile_dir(&root, &keeper.profile_key);
+        let keeper_backups = file_service.game_save_backups_dir(&root, &keeper.profile_key);
+        for dup in &duplicates {
+            service.delete_game(&dup.id).await?;
+            cache_remove(&state.config_cache, &dup.profile_key);
+            result.merged += 1;
+
+            if dup.profile_key.is_empty() || dup.profile_key == keeper.profile_key {
+                continue;
+            }
+            let dup_profile = file_service.game_profile_dir(&root, &dup.profile_key);
+            if !dup_profile.is_dir() {
+                continue;
+            }
+            if !keeper_profile.exists() && std::fs::rename(&dup_profile, &keeper_profile).is_ok() {
+                continue;
+            }
+            let (root, backups) = (root.clone(), keeper_backups.clone());
+            let retired = tokio::task::spawn_blocking(move || {
+                retire_duplicate_profile(&root, &dup_profile, &backups)
+            })
+            .await
+            .map_err(|e| format!("清理重复 profile 失败: {}", e))?;
+            match retired {
+                Ok((removed, backup)) => {
+                    if removed {
+                        result.profiles_removed += 1;
+                    }
+                    if let Some(backup) = backup {
+                        result
+                            .save_backups
+                            .push(backup.to_string_lossy().to_string());
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(game_id = %dup.id, error = %e, "备份重复记录的存档失败，保留其 profile 目录");
+                }
+            }
+        }
+
+        // 统一保留记录的路径写法，避免以后再次因写法不同而重复导入
+        let canonical = normalize_path(Path::new(&keeper.game_path));
+        if keeper.game_path != canonical {
+            let update = UpdateGameInput {
+                title: None,
+                engine_type: None,
+                path: Some(canonical),
+                game_type: None,
+                detection_confidence: None,
+                metadata_json: None,
+                runtime_version: None,
+            };
+            if let Err(e) = service.update_game(&keeper.id, update).await {
+                tracing::warn!(game_id = %keeper.id, error = %e, "更新游戏路径失败");
+            }
+        }
+        cache_remove(&state.config_cache, &keeper.profile_key);
+
+        tracing::info!(path = %path, merged = duplicates.len(), "已合并重复游戏");
+        result.groups += 1;
+    }
+
+    Ok(result)
+}
+
+/// 重复判定键：规范化路径（Windows 上不区分大小写）
+fn dedupe_key(game: &Game) -> String {
+    let path = normalize_path(Path::new(&game.game_path));
+    let path = path.trim_end_matches(['/', '\\']);
+    if cfg!(target_os = "windows") {
+        path.to_lowercase()
+    } else {
+        path.to_string()
+    }
+}
+
+/// 把重复记录的 profile 打包到保留记录的存档备份目录后再删除
+///
+/// 返回（是否已删除, 备份路径）；备份失败时不删除 profile。
+fn retire_duplicate_profile(
+    root: &Path,
+    dup_profile: &Path,
+    keeper_backups: &Path,
+) -> Result<(bool, Option<PathBuf>), String> {
+    let backup = super::saves::write_save_backup(dup_profile, keeper_backups, Vec::new())?;
+    Ok((remove_profile_dir(root, dup_profile), backup))
+}
+
+/// 删除容器内的 profile 目录；不在 `profiles/` 下的目录不删除
+fn remove_profile_dir(root: &Path, profile_dir: &Path) -> bool {
+    let profile_dir = crate::utils::path::canonicalize(profile_dir);
+    if !crate::utils::path::is_profile_dir(root, &profile_dir) {
+        tracing::warn!(path = %profile_dir.display(), "profile 目录不在容器内，跳过删除");
+        return false;
+    }
+    match std::fs::remove_dir_all(&profile_dir) {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::warn!(path = %profile_dir.display(), error = %e, "删除 profile 目录失败");
+            false
+        }
+    }
+}
+
+async fn import_entry(
+    service: &GameService,
+    root: &Path,
+    entry: LibraryEntry,
+) -> Result<(), String> {
+    let game = service
+        .add_game(AddGameInput {
+            title: Some(entry.title),
+            engine_type: entry.engine_type,
+            path: entry.path,
+            game_type: Some(entry.game_type),
+            detection_confidence: Some(entry.detection_confidence),
+            metadata_json: entry.metadata_json,
+            runtime_version: entry.runtime_version,
+        })
+        .await?;
+
+    service
+        .restore_stats(
+            &game.id,
+            entry.favorite,
+            entry.play_count,
+            entry.total_play_ms,
+            entry.last_played_at,
+        )
+        .await?;
+    if entry.rating.is_some() {
+        service.set_rating(&game.id, entry.rating).await?;
+    }
+    if let Some(status) = entry.status.as_deref() {
+        service
+            .set_status(&game.id, GameStatus::parse(status)?)
+            .await?;
+    }
+    if !entry.tags.is_empty() {
+        service.set_tags(&game.id, entry.tags).await?;
+    }
+
+    let file_service = FileService::new();
+    file_service.ensure_game_dirs(root, &game.profile_key)?;
+    let mut config = entry.config.unwrap_or_else(|| default_game_config(&game));
+    config.engine_type = game.engine_type.clone();
+    if config.cover_file.is_none() {
+        config.cover_file = entry.cover_file;
+    }
+    let config_path = file_service.game_config_path(root, &game.profile_key);
+    file_service.write_game_config(&config_path, &config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::ArchiveService;
+
+    #[test]
+    fn duplicate_save_survives_profile_removal() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let file_service = FileService::new();
+        let dup_profile = file_service.game_profile_dir(root, "dup");
+        let save_dir = file_service.game_user_data_dir(root, "dup").join("Default");
+        std::fs::create_dir_all(&save_dir).unwrap();
+        std::fs::write(save_dir.join("file1.rpgsave"), b"progress").unwrap();
+        std::fs::create_dir_all(dup_profile.join("Wine Prefix")).unwrap();
+        let keeper_backups = file_service.game_save_backups_dir(root, "keeper");
+
+        let (removed, backup) =
+            retire_duplicate_profile(root, &dup_profile, &keeper_backups).unwrap();
+        assert!(removed);
+        assert!(!dup_profile.exists());
+
+        let backup = backup.expect("重复记录的存档应被备份");
+        assert!(backup.starts_with(&keeper_backups));
+        let staging = tmp.path().join("staging");
+        ArchiveService::new()
+            .extract_zip(&backup, &staging, None)
+            .unwrap();
+        let restored = staging.join("profile/User Data/Default/file1.rpgsave");
+        assert_eq!(std::fs::read(restored).unwrap(), b"progress");
+        assert!(!staging.join("profile/Wine Prefix").exists());
+    }
+
+    #[test]
+    fn duplicate_backups_in_same_dir_do_not_overwrite() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let file_service = FileService::new();
+        let keeper_backups = file_service.game_save_backups_dir(root, "keeper");
+        let mut backups = Vec::new();
+        for key in ["dup-a", "dup-b"] {
+            let user_data = file_service.game_user_data_dir(root, key);
+            std::fs::create_dir_all(&user_data).unwrap();
+            std::fs::write(user_data.join("save.dat"), key).unwrap();
+            let profile = file_service.game_profile_dir(root, key);
+            let (_, backup) = retire_duplicate_profile(root, &profile, &keeper_backups).unwrap();
+            backups.push(backup.unwrap());
+        }
+        assert_ne!(backups[0], backups[1]);
+        assert!(backups.iter().all(|b| b.is_file()));
+    }
+
+    #[test]
+    fn empty_duplicate_profile_is_removed_without_backup() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let file_service = FileService::new();
+        let dup_profile = file_service.game_profile_dir(root, "dup");
+        std::fs::create_dir_all(&dup_profile).unwrap();
+        let keeper_backups = file_service.game_save_backups_dir(root, "keeper");
+
+        let (removed, backup) =
+            retire_duplicate_profile(root, &dup_profile, &keeper_backups).unwrap();
+        assert!(removed);
+        assert!(backup.is_none());
+        assert!(!keeper_backups.exists());
+    }
+}
@@ -1,4 +1,4 @@
-//! 游戏管理命令：CRUD、导入、启动、封面解析和游戏设置。
+//! 游戏管理命令：CRUD、导入、启动、封面解析、游戏设置、游戏库导入导出和存档备份。
 pub(crate) mod cover;
 pub(crate) mod cover_resolver;
 pub(crate) mod game;
@@ -6,6 +6,8 @@ pub(crate) mod game_executable;
 pub(crate) mod game_settings;
 pub(crate) mod import;
 pub(crate) mod launch;
+pub(crate) mod library;
+pub(crate) mod saves;
 
 // Tauri commands — glob re-exports carry generated __cmd__ / __tauri_command_name_ items
 pub use cover::*;
@@ -13,3 +15,5 @@ pub use game::*;
 pub use game_settings::*;
 pub use import::*;
 pub use launch::*;
+pub use library::*;
+pub use saves::*;
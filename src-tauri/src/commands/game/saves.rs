@@ -0,0 +1,226 @@
+use crate::commands::error::AppError;
+use crate::commands::state::{AppState, cache_remove};
+use crate::db::schema::Game;
+use crate::models::SaveBackupInfo;
+use crate::services::game::session;
+use crate::services::{ArchiveService, FileService};
+use std::path::{Path, PathBuf};
+use tauri::State;
+use uuid::Uuid;
+
+/// 存档备份文件名前缀，后接 Unix 毫秒时间戳
+const SAVE_BACKUP_PREFIX: &str = "save-";
+
+/// profile 中不属于存档、体积又可能很大的目录
+const PROFILE_EXCLUDED_DIRS: &[&str] = &["Wine Prefix", "Proton", "Crash Reports"];
+
+/// 备份游戏存档为带时间戳的 zip，返回压缩包路径
+///
+/// 包含 profile 目录（沙盒 HOME / User Data，排除 Wine/Proton 前缀与崩溃报告），
+/// 以及引擎插件 `preserve_dirs` 中声明的游戏目录内存档文件夹。
+#[tauri::command]
+pub async fn backup_save(id: String, state: State<'_, AppState>) -> Result<String, AppError> {
+    let game = find_game(&state, &id).await?;
+    let dest = create_save_backup(&state, &game)
+        .await?
+        .ok_or_else(|| "没有找到可备份的存档".to_string())?;
+
+    tracing::info!(game_id = %game.id, path = %dest.display(), "已备份游戏存档");
+    Ok(dest.to_string_lossy().to_string())
+}
+
+/// 从存档备份恢复，返回恢复的文件数
+///
+/// 备份必须位于容器的 `backups/` 目录内；游戏运行中时拒绝恢复。
+/// 恢复前会先为当前状态创建一份安全备份。
+#[tauri::command]
+pub async fn restore_save(
+    id: String,
+    backup_path: String,
+    state: State<'_, AppState>,
+) -> Result<usize, AppError> {
+    let game = find_game(&state, &id).await?;
+    if session::is_running(&state.running_games, &game.id) {
+        return Err(AppError::Other(
+            "游戏正在运行，请退出后再恢复存档".to_string(),
+        ));
+    }
+
+    let root = state.container_root_path().await;
+    let backup = PathBuf::from(&backup_path);
+    if !backup.is_file() {
+        return Err(AppError::PathInvalid(format!(
+            "备份文件不存在: {}",
+            backup_path
+        )));
+    }
+    if !crate::utils::path::is_within(&backup, &root.join("backups")) {
+        return Err(AppError::PathInvalid(
+            "只能从存档备份目录中恢复".to_string(),
+        ));
+    }
+
+    if let Some(safety) = create_save_backup(&state, &game).await? {
+        tracing::info!(game_id = %game.id, path = %safety.display(), "恢复存档前已创建安全备份");
+    }
+
+    let file_service = FileService::new();
+    let profile_dir = file_service.game_profile_dir(&root, &game.profile_key);
+    let game_dir = PathBuf::from(&game.game_path);
+    let staging = file_service
+        .game_save_backups_dir(&root, &game.profile_key)
+        .join(format!(".restore-{}", Uuid::new_v4()));
+
+    let restored = tokio::task::spawn_blocking(move || {
+        let archive_service = ArchiveService::new();
+        let result = archive_service
+            .extract_zip(&backup, &staging, None)
+            .and_then(|_| {
+                let profile_files = merge_dir(&staging.join("profile"), &profile_dir)?;
+                let game_files = merge_dir(&staging.join("game"), &game_dir)?;
+                Ok(profile_files + game_files)
+            });
+        let _ = archive_service.remove_dir_if_exists(&staging);
+        result
+    })
+    .await
+    .map_err(|e| format!("恢复存档失败: {}", e))??;
+    // 备份中的 settings.toml 已覆盖磁盘上的配置
+    cache_remove(&state.config_cache, &game.profile_key);
+
+    tracing::info!(game_id = %game.id, restored, "已恢复游戏存档");
+    Ok(restored)
+}
+
+/// 列出游戏的存档备份（最新的在前）
+#[tauri::command]
+pub async fn list_save_backups(
+    id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<SaveBackupInfo>, AppError> {
+    let game = find_game(&state, &id).await?;
+    let root = state.container_root_path().await;
+    let dir = FileService::new().game_save_backups_dir(&root, &game.profile_key);
+
+    let Ok(read_dir) = std::fs::read_dir(&dir) else {
+        return Ok(Vec::new());
+    };
+    let mut backups: Vec<SaveBackupInfo> = read_dir
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let file_name = path.file_name()?.to_str()?.to_string();
+            let created_at = file_name
+                .strip_prefix(SAVE_BACKUP_PREFIX)?
+                .strip_suffix(".zip")?
+                .parse::<i64>()
+                .ok()?;
+            let size = entry.metadata().ok().filter(|m| m.is_file())?.len();
+            Some(SaveBackupInfo {
+                path: path.to_string_lossy().to_string(),
+                file_name,
+                size,
+                created_at,
+            })
+        })
+        .collect();
+    backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(backups)
+}
+
+async fn find_game(state: &AppState, id: &str) -> Result<Game, AppError> {
+    let service = state.game_service.lock().await;
+    service
+        .get_game_by_id(id)
+        .await?
+        .ok_or_else(|| AppError::game_not_found(id))
+}
+
+/// 创建存档备份；没有任何可备份内容时返回 `None`
+async fn create_save_backup(state: &AppState, game: &Game) -> Result<Option<PathBuf>, String> {
+    let root = state.container_root_path().await;
+    let preserve_dirs = {
+        let registry = state.engine_registry.lock().await;
+        registry
+            .get_entry(&game.engine_type)
+            .map(|e| e.profile.launch.preserve_dirs.clone())
+            .unwrap_or_default()
+    };
+
+    let file_service = FileService::new();
+    let profile_dir = file_service.game_profile_dir(&root, &game.profile_key);
+    let backups_dir = file_service.game_save_backups_dir(&root, &game.profile_key);
+    let game_dir = PathBuf::from(&game.game_path);
+    let extra: Vec<(PathBuf, String)> = preserve_dirs
+        .iter()
+        .filter_map(|dir| {
+            let path = game_dir.join(dir);
+            path.is_dir()
+                .then(|| (path, format!("game/{}", dir.replace('\\', "/"))))
+        })
+        .collect();
+
+    tokio::task::spawn_blocking(move || write_save_backup(&profile_dir, &backups_dir, extra))
+        .await
+        .map_err(|e| format!("备份存档失败: {}", e))?
+}
+
+/// 把 profile 目录（及额外条目）打包为 `backups_dir` 下带时间戳的 zip
+///
+/// 没有任何可备份内容时返回 `None`；同一毫秒内的多次备份顺延时间戳，不会互相覆盖。
+pub(crate) fn write_save_backup(
+    profile_dir: &Path,
+    backups_dir: &Path,
+    extra: Vec<(PathBuf, String)>,
+) -> Result<Option<PathBuf>, String> {
+    let mut entries = collect_profile_entries(profile_dir);
+    entries.extend(extra);
+    if entries.is_empty() {
+        return Ok(None);
+    }
+
+    let mut stamp = crate::utils::now_unix_ms();
+    let mut dest = backups_dir.join(format!("{}{}.zip", SAVE_BACKUP_PREFIX, stamp));
+    while dest.exists() {
+        stamp += 1;
+        dest = backups_dir.join(format!("{}{}.zip", SAVE_BACKUP_PREFIX, stamp));
+    }
+    ArchiveService::new().create_zip(&dest, &entries)?;
+    Ok(Some(dest))
+}
+
+/// 把 `src` 中的文件逐个覆盖到 `dst`，返回复制的文件数（`src` 不存在时为 0）
+fn merge_dir(src: &Path, dst: &Path) -> Result<usize, String> {
+    if !src.is_dir() {
+        return Ok(0);
+    }
+    crate::utils::path::ensure_dir(dst)?;
+    let mut count = 0;
+    for entry in std::fs::read_dir(src).map_err(|e| format!("读取目录错误: {}", e))? {
+        let entry = entry.map_err(|e| format!("读取目录条目错误: {}", e))?;
+        let from = entry.path();
+        let to = dst.join(entry.file_name());
+        if from.is_dir() {
+            count += merge_dir(&from, &to)?;
+        } else {
+            std::fs::copy(&from, &to).map_err(|e| format!("复制文件错误: {}", e))?;
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// profile 目录中需要备份的条目（压缩包内置于 `profile/` 下）
+fn collect_profile_entries(profile_dir: &Path) -> Vec<(PathBuf, String)> {
+    let Ok(read_dir) = std::fs::read_dir(profile_dir) else {
+        return Vec::new();
+    };
+    read_dir
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            (!PROFILE_EXCLUDED_DIRS.contains(&name.as_str()))
+                .then(|| (entry.path(), format!("profile/{}", name)))
+        })
+        .collect()
+}
@@ -2,6 +2,7 @@
 // 定义Tauri可调用的命令接口
 
 pub mod engine;
+pub mod error;
 pub mod game;
 pub mod platform;
 pub mod scan;
@@ -13,6 +14,8 @@ pub mod extension {
     pub use crate::services::extension::integrations::*;
 }
 
+pub use error::AppError;
+
 // 重新导出命令
 pub use engine::*;
 pub use extension::*;
@@ -1,6 +1,12 @@
+use crate::commands::error::AppError;
+use crate::commands::state::AppState;
+use crate::models::{DiagnosticsInfo, SETTING_WINE_BINARY};
+use crate::services::extension::BottlesService;
+use tauri::{AppHandle, Manager, State};
+
 /// 检测当前运行的操作系统类型
 #[tauri::command]
-pub async fn get_platform() -> Result<String, String> {
+pub async fn get_platform() -> Result<String, AppError> {
     #[cfg(target_os = "linux")]
     {
         return Ok("linux".to_string());
@@ -21,7 +27,67 @@ pub async fn get_platform() -> Result<String, String> {
 
 /// 获取系统主题（由 Rust 从 OS 原生接口获取）
 #[tauri::command]
-pub async fn get_system_theme() -> Result<String, String> {
+pub async fn get_system_theme() -> Result<String, AppError> {
     let mode = dark_light::detect().unwrap_or(dark_light::Mode::Light);
     Ok(format!("{:?}", mode).to_lowercase())
 }
+
+/// 收集运行环境诊断信息；外部命令均为尽力而为，失败时对应字段为空
+#[tauri::command]
+pub async fn get_diagnostics(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<DiagnosticsInfo, AppError> {
+    let db_path = app
+        .path()
+        .app_data_dir()
+        .map(|dir| crate::db::database_path(&dir).to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let installed_runtimes = {
+        let service = state.engine_service.lock().await;
+        service
+            .get_all_engines()
+            .await?
+            .into_iter()
+            .map(|engine| service.to_dto(engine))
+            .collect()
+    };
+
+    let wine_binary = {
+        let mut db_lock = state.db.lock().await;
+        crate::db::get_setting(&mut *db_lock, SETTING_WINE_BINARY)
+            .await
+            .ok()
+            .flatten()
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| "wine".to_string())
+    };
+    let wine_version = tokio::task::spawn_blocking(move || {
+        let output = std::process::Command::new(&wine_binary)
+            .arg("--version")
+            .output()
+            .ok()?;
+        let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (output.status.success() && !version.is_empty()).then_some(version)
+    })
+    .await
+    .ok()
+    .flatten();
+
+    Ok(DiagnosticsInfo {
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        app_version: app.package_info().version.to_string(),
+        container_root: state
+            .container_root_path()
+            .await
+            .to_string_lossy()
+            .to_string(),
+        db_path,
+        installed_runtimes,
+        bottles_installed: BottlesService::detect_cli().await.is_some(),
+        wine_version,
+    })
+}
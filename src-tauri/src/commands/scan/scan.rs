@@ -1,22 +1,66 @@
+use crate::commands::error::AppError;
 use crate::commands::game::cover::update_game_cover;
 use crate::commands::game::game::{
-    default_game_config, is_linux_native_entry, is_nwjs_runtime_dir, normalize_path,
+    default_game_config, find_bundled_nwjs, is_linux_native_entry, is_nwjs_runtime_dir,
+    normalize_path,
 };
 use crate::commands::game::game_executable::find_renpy_launch_script;
+use crate::commands::settings::settings::load_scan_ignores;
 use crate::commands::state::{AppState, cached_write_config};
+use crate::db::scan_cache;
+use crate::engines::EngineRegistry;
 use crate::engines::context::FsDetectionContext;
 use crate::models::{
-    AddGameInput, EngineType, SETTING_BOTTLES_ENABLED, ScanGamesInput, ScanGamesResult,
+    AddGameInput, EngineType, SETTING_BOTTLES_ENABLED, SandboxHome, ScanCandidate, ScanGamesInput,
+    ScanGamesResult,
 };
 use crate::services::FileService;
-use std::collections::{HashSet, VecDeque};
+use crate::services::game::nwjs_entry::find_nwjs_entry;
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use tauri::{AppHandle, Emitter, State};
+use std::sync::atomic::{AtomicU32, Ordering};
+use tauri::{AppHandle, Emitter, Manager, State};
 use uuid::Uuid;
 
+/// 内置的扫描忽略规则（隐藏目录始终跳过），与用户设置合并使用
+const BUILTIN_SCAN_IGNORES: &[&str] = &[
+    "node_modules",
+    "__pycache__",
+    "$RECYCLE.BIN",
+    "System Volume Information",
+];
+
+/// 编译内置与用户自定义的忽略规则；无效的用户规则会被忽略
+fn compile_scan_ignores(user_patterns: &[String]) -> Vec<glob::Pattern> {
+    BUILTIN_SCAN_IGNORES
+        .iter()
+        .copied()
+        .chain(user_patterns.iter().map(String::as_str))
+        .filter_map(|pattern| match glob::Pattern::new(pattern.trim()) {
+            Ok(pattern) => Some(pattern),
+            Err(e) => {
+                tracing::warn!(pattern, error = %e, "忽略无效的扫描忽略规则");
+                None
+            }
+        })
+        .collect()
+}
+
+fn is_ignored_dir(name: &str, ignores: &[glob::Pattern]) -> bool {
+    let options = glob::MatchOptions {
+        case_sensitive: false,
+        ..Default::default()
+    };
+    name.starts_with('.')
+        || ignores
+            .iter()
+            .any(|pattern| pattern.matches_with(name, options))
+}
+
 /// 引擎的递归规则可能在集合目录中命中某个子游戏的特征。
 /// 若根目录下至少有两个可识别的直接子目录，则始终把它视为集合容器。
-fn is_game_collection_root(registry: &crate::engines::EngineRegistry, root: &Path) -> bool {
+fn is_game_collection_root(registry: &EngineRegistry, root: &Path) -> bool {
     let Ok(entries) = std::fs::read_dir(root) else {
         return false;
     };
@@ -44,13 +88,187 @@ fn is_game_collection_root(registry: &crate::engines::EngineRegistry, root: &Pat
     false
 }
 
+/// 发现阶段识别出的游戏目录
+struct DetectedGame {
+    dir: PathBuf,
+    engine_type: String,
+    confidence: i32,
+}
+
+/// 单个目录的检测结果
+enum DirOutcome {
+    Game(DetectedGame),
+    /// 待遍历的子目录及其规范化路径
+    Children(Vec<(PathBuf, PathBuf)>),
+    Skip,
+}
+
+/// 遍历选项
+struct ScanOptions<'a> {
+    max_depth: u32,
+    /// 不跟随符号链接进入目录
+    skip_symlinks: bool,
+    /// 名称匹配这些规则的子目录不会进入
+    ignores: &'a [glob::Pattern],
+    /// 增量扫描：上次扫描时记录的顶层目录修改时间（规范化路径 -> 毫秒）
+    unchanged: &'a HashMap<String, i64>,
+}
+
+/// 发现阶段的结果
+struct Discovery {
+    scanned_dirs: u32,
+    /// 按广度优先顺序排列的候选游戏
+    candidates: Vec<DetectedGame>,
+    /// 扫描根目录下各顶层目录的修改时间，供下次增量扫描使用
+    top_level: Vec<(String, i64)>,
+    /// 因修改时间未变化而跳过的顶层目录数
+    skipped_unchanged: u32,
+}
+
+/// 按层遍历目录树并识别游戏，同一层的兄弟目录并行检测。
+///
+/// 子目录按路径排序、每层结果按原顺序合并，因此候选列表与串行遍历一样稳定。
+/// 已访问过的规范化路径会被跳过，符号链接环不会导致无限遍历，
+/// 通过不同链接到达的同一目录也只检测一次。
+/// 顶层目录的修改时间与 `unchanged` 中记录的一致时，整个子树不再遍历。
+/// `on_progress(已扫描, 已发现)` 每扫描 20 个目录调用一次。
+fn discover_games(
+    registry: &EngineRegistry,
+    root: PathBuf,
+    options: &ScanOptions,
+    on_progress: impl Fn(u32, u32) + Sync,
+) -> Discovery {
+    let scanned = AtomicU32::new(0);
+    let queued = AtomicU32::new(1);
+    let mut candidates = Vec::new();
+    let mut top_level = Vec::new();
+    let mut skipped_unchanged = 0;
+    let mut visited: HashSet<PathBuf> = HashSet::new();
+    visited.insert(crate::utils::path::canonicalize(&root));
+    let mut level = vec![root];
+    let mut depth = 0;
+
+    while !level.is_empty() {
+        let outcomes: Vec<DirOutcome> = level
+            .par_iter()
+            .map(|dir| {
+                let outcome = inspect_dir(registry, dir, depth, options);
+                let count = scanned.fetch_add(1, Ordering::Relaxed) + 1;
+                if count % 20 == 0 {
+                    on_progress(count, queued.load(Ordering::Relaxed));
+                }
+                outcome
+            })
+            .collect();
+
+        let mut next = Vec::new();
+        for outcome in outcomes {
+            match outcome {
+                DirOutcome::Game(candidate) => candidates.push(candidate),
+                DirOutcome::Children(children) => {
+                    for (child, canonical) in children {
+                        if visited.insert(canonical) {
+                            next.push(child);
+                        }
+                    }
+                }
+                DirOutcome::Skip => {}
+            }
+        }
+        if depth == 0 {
+            next.retain(|dir| {
+                let Some(mtime) = dir_mtime_ms(dir) else {
+                    return true;
+                };
+                let key = normalize_path(dir);
+                let unchanged = options.unchanged.get(&key) == Some(&mtime);
+                top_level.push((key, mtime));
+                if unchanged {
+                    skipped_unchanged += 1;
+                }
+                !unchanged
+            });
+        }
+        queued.fetch_add(next.len() as u32, Ordering::Relaxed);
+        level = next;
+        depth += 1;
+    }
+
+    Discovery {
+        scanned_dirs: scanned.into_inner(),
+        candidates,
+        top_level,
+        skipped_unchanged,
+    }
+}
+
+fn dir_mtime_ms(dir: &Path) -> Option<i64> {
+    let modified = std::fs::metadata(dir).ok()?.modified().ok()?;
+    let elapsed = modified.duration_since(std::time::UNIX_EPOCH).ok()?;
+    Some(elapsed.as_millis() as i64)
+}
+
+fn inspect_dir(
+    registry: &EngineRegistry,
+    dir: &Path,
+    depth: u32,
+    options: &ScanOptions,
+) -> DirOutcome {
+    if is_nwjs_runtime_dir(dir) {
+        return DirOutcome::Skip;
+    }
+
+    let ctx = FsDetectionContext::new(dir.to_path_buf());
+    let detected = registry
+        .detect(&ctx)
+        .filter(|(id, _)| !registry.should_skip_scan(id));
+    if let Some((engine_type, confidence)) = detected
+        && !(depth == 0 && is_game_collection_root(registry, dir))
+    {
+        // 自动扫描中，一个游戏目录就是扫描边界。子目录只能通过手动导入添加。
+        return DirOutcome::Game(DetectedGame {
+            dir: dir.to_path_buf(),
+            engine_type: engine_type.to_string(),
+            confidence,
+        });
+    }
+
+    if depth >= options.max_depth {
+        return DirOutcome::Skip;
+    }
+
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return DirOutcome::Skip;
+    };
+    let mut children: Vec<(PathBuf, PathBuf)> = entries
+        .flatten()
+        .filter(|entry| !is_ignored_dir(&entry.file_name().to_string_lossy(), options.ignores))
+        .filter_map(|entry| {
+            let path = entry.path();
+            let ty = entry.file_type().ok()?;
+            let is_dir = if ty.is_symlink() {
+                !options.skip_symlinks && path.is_dir()
+            } else {
+                ty.is_dir()
+            };
+            if !is_dir {
+                return None;
+            }
+            let canonical = dunce::canonicalize(&path).ok()?;
+            Some((path, canonical))
+        })
+        .collect();
+    children.sort();
+    DirOutcome::Children(children)
+}
+
 /// 扫描游戏目录
 #[tauri::command]
 pub async fn scan_games(
     input: ScanGamesInput,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<ScanGamesResult, String> {
+) -> Result<ScanGamesResult, AppError> {
     let scan_start = std::time::Instant::now();
 
     // 记录扫描开始
@@ -67,173 +285,198 @@ pub async fn scan_games(
         .map(|g| normalize_path(Path::new(&g.game_path)))
         .collect();
 
+    let dry_run = input.dry_run;
     let root = PathBuf::from(input.root);
     if !root.exists() {
-        return Err("扫描根目录不存在".to_string());
+        return Err(AppError::PathInvalid("扫描根目录不存在".to_string()));
     }
 
-    let mut scanned_dirs: u32 = 0;
     let mut found_games: u32 = 0;
     let mut imported: u32 = 0;
     let mut skipped_existing: u32 = 0;
     let mut cover_jobs = Vec::new();
+    let mut scan_candidates = Vec::new();
+    let mut by_engine: HashMap<String, u32> = HashMap::new();
     let task_id = Uuid::new_v4().to_string();
 
-    let mut queue: VecDeque<(PathBuf, u32)> = VecDeque::new();
-    queue.push_back((root.clone(), 0));
-
-    while let Some((dir, depth)) = queue.pop_front() {
-        scanned_dirs += 1;
-
-        if scanned_dirs % 20 == 0 {
-            let pending = queue.len() as f64;
-            let progress =
-                ((scanned_dirs as f64 / (scanned_dirs as f64 + pending).max(1.0)) * 90.0) as u8;
-            let _ = app.emit(
-                "scan_progress",
-                serde_json::json!({
-                    "taskId": task_id,
-                    "label": format!("扫描中… 已扫描 {}", scanned_dirs),
-                    "progress": progress.min(90),
-                }),
-            );
-        }
+    let db_path = crate::db::database_path(
+        &app.path()
+            .app_data_dir()
+            .map_err(|e| format!("获取应用数据目录失败: {}", e))?,
+    );
+    let user_ignores = load_scan_ignores(&mut *state.db.lock().await).await?;
+    // 容器根目录、忽略规则、扫描深度或符号链接选项变化后，旧的增量扫描缓存不再适用；
+    // 否则浅层扫描记录的“未变化”会让之后更深的增量扫描跳过深层目录
+    let fingerprint = serde_json::json!([
+        root_path,
+        user_ignores,
+        input.skip_symlinks,
+        input.max_depth
+    ])
+    .to_string();
 
-        if depth > input.max_depth {
+    let discovery = {
+        let ignores = compile_scan_ignores(&user_ignores);
+        let registry = state.engine_registry.clone().lock_owned().await;
+        let max_depth = input.max_depth;
+        let skip_symlinks = input.skip_symlinks;
+        let incremental = input.incremental;
+        let cache_db_path = db_path.clone();
+        let cache_fingerprint = fingerprint.clone();
+        let progress_app = app.clone();
+        let progress_task_id = task_id.clone();
+        tokio::task::spawn_blocking(move || {
+            let unchanged = if incremental {
+                scan_cache::load(&cache_db_path, &cache_fingerprint).unwrap_or_else(|e| {
+                    tracing::warn!(error = %e, "读取扫描缓存失败，执行完整扫描");
+                    HashMap::new()
+                })
+            } else {
+                HashMap::new()
+            };
+            let options = ScanOptions {
+                max_depth,
+                skip_symlinks,
+                ignores: &ignores,
+                unchanged: &unchanged,
+            };
+            discover_games(&registry, root, &options, |scanned, queued| {
+                let progress = ((scanned as f64 / (queued as f64).max(1.0)) * 90.0) as u8;
+                let _ = progress_app.emit(
+                    "scan_progress",
+                    serde_json::json!({
+                        "taskId": progress_task_id,
+                        "label": format!("扫描中… 已扫描 {}", scanned),
+                        "progress": progress.min(90),
+                    }),
+                );
+            })
+        })
+        .await
+        .map_err(|e| format!("扫描目录失败: {}", e))?
+    };
+
+    for DetectedGame {
+        dir,
+        engine_type,
+        confidence,
+    } in discovery.candidates
+    {
+        found_games += 1;
+        *by_engine.entry(engine_type.clone()).or_default() += 1;
+        let path_str = normalize_path(&dir);
+        let existing = existing_paths.contains(&path_str);
+        scan_candidates.push(ScanCandidate {
+            path: path_str.clone(),
+            engine_type: engine_type.clone(),
+            confidence,
+            existing,
+        });
+        if existing {
+            skipped_existing += 1;
             continue;
         }
-
-        if is_nwjs_runtime_dir(&dir) {
+        // 预览模式只报告候选，不修改游戏库
+        if dry_run {
             continue;
         }
 
-        let detection = {
-            let registry = state.engine_registry.lock().await;
-            let ctx = FsDetectionContext::new(dir.clone());
-            let detected = registry
-                .detect(&ctx)
-                .filter(|(id, _)| !registry.should_skip_scan(id))
-                .map(|(id, confidence)| (id.to_string(), confidence));
-            if depth == 0 && is_game_collection_root(&registry, &dir) {
-                None
-            } else {
-                detected
-            }
+        let input = AddGameInput {
+            title: None,
+            engine_type: engine_type.clone(),
+            path: path_str.clone(),
+            game_type: None,
+            detection_confidence: Some(confidence),
+            metadata_json: None,
+            runtime_version: None,
         };
-        if let Some((engine_type, confidence)) = detection {
-            found_games += 1;
-            let path_str = normalize_path(&dir);
-            if existing_paths.contains(&path_str) {
-                skipped_existing += 1;
-            } else {
-                let input = AddGameInput {
-                    title: None,
-                    engine_type: engine_type.clone(),
-                    path: path_str.clone(),
-                    game_type: None,
-                    detection_confidence: Some(confidence),
-                    metadata_json: None,
-                    runtime_version: None,
-                };
 
-                let game = service.add_game(input).await?;
-                existing_paths.insert(path_str);
-                imported += 1;
+        let game = service.add_game(input).await?;
+        existing_paths.insert(path_str);
+        imported += 1;
 
-                let mut entry_exe: Option<PathBuf> = None;
-                if EngineType::from_str(&engine_type) == EngineType::RenPy {
-                    entry_exe = find_renpy_launch_script(&dir);
-                }
+        let mut entry_exe: Option<PathBuf> = None;
+        if EngineType::from_str(&engine_type) == EngineType::RenPy {
+            entry_exe = find_renpy_launch_script(&dir);
+        }
 
-                if entry_exe.is_none() {
-                    let registry = state.engine_registry.lock().await;
-                    if let Some(engine_entry) = registry.get_entry(&engine_type) {
-                        let patterns = &engine_entry.profile.launch.entry_patterns;
-                        let excludes = &engine_entry.profile.launch.exclude_patterns;
-                        entry_exe = crate::engines::find_executable(&dir, patterns, excludes);
-                    }
-                }
+        if entry_exe.is_none() {
+            let registry = state.engine_registry.lock().await;
+            if let Some(engine_entry) = registry.get_entry(&engine_type) {
+                let patterns = &engine_entry.profile.launch.entry_patterns;
+                let excludes = &engine_entry.profile.launch.exclude_patterns;
+                entry_exe = crate::engines::find_executable(&dir, patterns, excludes);
+            }
+        }
 
-                let config_path = file_service.game_config_path(&root_path, &game.profile_key);
-                if file_service
-                    .ensure_game_dirs(&root_path, &game.profile_key)
-                    .is_ok()
-                {
-                    let mut config = default_game_config(&game);
-                    let (entry_patterns, sandbox_home) = {
-                        let registry = state.engine_registry.lock().await;
-                        registry
-                            .get_entry(&engine_type)
-                            .map(|e| {
-                                (
-                                    e.profile.launch.entry_patterns.clone(),
-                                    e.profile.launch.sandbox_home,
-                                )
-                            })
-                            .unwrap_or_else(|| (Vec::new(), true))
-                    };
-                    config.runner = "auto".to_string();
-                    config.sandbox_home = sandbox_home;
-                    if let Some(entry) = entry_exe.as_deref()
-                        && is_linux_native_entry(entry)
-                    {
-                        config.runner = "native".to_string();
-                        config.sandbox_home = true;
-                    }
-                    if entry_patterns.is_empty() {
-                        if dir.join("www").join("package.json").exists() {
-                            config.entry_path = "www".to_string();
-                        } else {
-                            config.entry_path = "".to_string();
-                        }
-                    } else if let Some(entry) = entry_exe.as_deref() {
-                        config.entry_path = normalize_path(entry);
-                    }
+        let config_path = file_service.game_config_path(&root_path, &game.profile_key);
+        if file_service
+            .ensure_game_dirs(&root_path, &game.profile_key)
+            .is_ok()
+        {
+            let mut config = default_game_config(&game);
+            let (entry_patterns, sandbox_home, uses_nwjs) = {
+                let registry = state.engine_registry.lock().await;
+                registry
+                    .get_entry(&engine_type)
+                    .map(|e| {
+                        (
+                            e.profile.launch.entry_patterns.clone(),
+                            e.profile.launch.sandbox_home,
+                            e.profile.launch.strategy == "nwjs",
+                        )
+                    })
+                    .unwrap_or_else(|| (Vec::new(), true, false))
+            };
+            config.runner = "auto".to_string();
+            config.sandbox_home = sandbox_home.into();
+            if let Some(entry) = entry_exe.as_deref()
+                && is_linux_native_entry(entry)
+            {
+                config.runner = "native".to_string();
+                config.sandbox_home = SandboxHome::Full;
+            }
+            config.self_contained = uses_nwjs && find_bundled_nwjs(&dir).is_some();
+            if entry_patterns.is_empty() {
+                config.entry_path = find_nwjs_entry(&dir).unwrap_or_default();
+            } else if let Some(entry) = entry_exe.as_deref() {
+                config.entry_path = normalize_path(entry);
+            }
 
-                    // 继承全局 Bottles 设置（仅 .exe）
-                    if entry_exe.is_some() {
-                        let mut db_lock = state.db.lock().await;
-                        if let Ok(Some(val)) =
-                            crate::db::get_setting(&mut *db_lock, SETTING_BOTTLES_ENABLED).await
-                        {
-                            config.use_bottles = val == "1"
-                                && entry_exe
-                                    .as_deref()
-                                    .is_none_or(|entry| !is_linux_native_entry(entry));
-                        }
-                    }
-                    let _ = cached_write_config(
-                        &state.config_cache,
-                        &file_service,
-                        &config_path,
-                        &game.profile_key,
-                        &config,
-                    );
+            // 继承全局 Bottles 设置（仅 .exe）
+            if entry_exe.is_some() {
+                let mut db_lock = state.db.lock().await;
+                if let Ok(Some(val)) =
+                    crate::db::get_setting(&mut *db_lock, SETTING_BOTTLES_ENABLED).await
+                {
+                    config.use_bottles = val == "1"
+                        && entry_exe
+                            .as_deref()
+                            .is_none_or(|entry| !is_linux_native_entry(entry));
                 }
-
-                cover_jobs.push((game, engine_type.clone(), dir.clone(), entry_exe));
             }
-
-            // 自动扫描中，一个游戏目录就是扫描边界。子目录只能通过手动导入添加。
-            continue;
+            let _ = cached_write_config(
+                &state.config_cache,
+                &file_service,
+                &config_path,
+                &game.profile_key,
+                &config,
+            );
         }
 
-        if depth == input.max_depth {
-            continue;
-        }
+        cover_jobs.push((game, engine_type, dir, entry_exe));
+    }
 
-        if let Ok(entries) = std::fs::read_dir(&dir) {
-            for entry in entries.flatten() {
-                if let Ok(ty) = entry.file_type() {
-                    if ty.is_dir() {
-                        let name = entry.file_name().to_string_lossy().to_string();
-                        if name.starts_with('.') {
-                            continue;
-                        }
-                        queue.push_back((entry.path(), depth + 1));
-                    }
-                }
-            }
+    if !dry_run {
+        let _db_lock = state.db.lock().await;
+        let top_level = discovery.top_level;
+        let saved = tokio::task::spawn_blocking(move || {
+            scan_cache::save(&db_path, &fingerprint, &top_level)
+        })
+        .await
+        .unwrap_or_else(|e| Err(e.to_string()));
+        if let Err(e) = saved {
+            tracing::warn!(error = %e, "写入扫描缓存失败");
         }
     }
 
@@ -277,9 +520,120 @@ pub async fn scan_games(
     );
 
     Ok(ScanGamesResult {
-        scanned_dirs,
+        scanned_dirs: discovery.scanned_dirs,
         found_games,
         imported,
         skipped_existing,
+        skipped_unchanged: discovery.skipped_unchanged,
+        by_engine,
+        candidates: scan_candidates,
     })
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::symlink;
+
+    /// 只加载 RPG Maker MV/MZ 插件，候选游戏只可能来自 `write_game` 写出的目录
+    fn rpgmaker_registry() -> (tempfile::TempDir, EngineRegistry) {
+        let config_dir = tempfile::tempdir().unwrap();
+        std::fs::copy(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("engines/rpgmakermv.toml"),
+            config_dir.path().join("rpgmakermv.toml"),
+        )
+        .unwrap();
+        let mut registry = EngineRegistry::new();
+        let warnings = registry.load(config_dir.path(), &HashMap::new());
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        (config_dir, registry)
+    }
+
+    fn write_game(dir: &Path) {
+        for (path, content) in [
+            ("package.json", r#"{"main": "www/index.html"}"#),
+            ("www/index.html", ""),
+            ("www/js/rmmz_core.js", ""),
+            ("www/js/rmmz_managers.js", ""),
+            ("www/data/System.json", "{}"),
+        ] {
+            let path = dir.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+    }
+
+    /// 深度不设限，只靠已访问集合终止遍历
+    fn discover(root: &Path, skip_symlinks: bool) -> Discovery {
+        let (_config_dir, registry) = rpgmaker_registry();
+        let unchanged = HashMap::new();
+        let options = ScanOptions {
+            max_depth: u32::MAX,
+            skip_symlinks,
+            ignores: &[],
+            unchanged: &unchanged,
+        };
+        discover_games(&registry, root.to_path_buf(), &options, |_, _| {})
+    }
+
+    fn candidate_dirs(discovery: &Discovery) -> Vec<PathBuf> {
+        discovery.candidates.iter().map(|c| c.dir.clone()).collect()
+    }
+
+    #[test]
+    fn discover_games_terminates_on_symlink_loop() {
+        // `root/a/b -> ..` 构成符号链接环，环内的游戏仍然只报告一次
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().join("root");
+        write_game(&root.join("a").join("Game"));
+        symlink("..", root.join("a").join("b")).unwrap();
+
+        let discovery = discover(&root, false);
+        assert_eq!(
+            candidate_dirs(&discovery),
+            vec![root.join("a").join("Game")]
+        );
+        assert_eq!(discovery.scanned_dirs, 3);
+    }
+
+    #[test]
+    fn discover_games_follows_symlinked_game_dir() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().join("root");
+        std::fs::create_dir_all(&root).unwrap();
+        let game = tmp.path().join("elsewhere").join("Game");
+        write_game(&game);
+        symlink(&game, root.join("linked")).unwrap();
+
+        let discovery = discover(&root, false);
+        assert_eq!(candidate_dirs(&discovery), vec![root.join("linked")]);
+    }
+
+    #[test]
+    fn discover_games_skips_symlinks_when_requested() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().join("root");
+        write_game(&root.join("Real"));
+        let game = tmp.path().join("elsewhere").join("Game");
+        write_game(&game);
+        symlink(&game, root.join("linked")).unwrap();
+
+        let discovery = discover(&root, true);
+        assert_eq!(candidate_dirs(&discovery), vec![root.join("Real")]);
+    }
+
+    #[test]
+    fn discover_games_reports_dir_reached_via_two_links_once() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().join("root");
+        std::fs::create_dir_all(&root).unwrap();
+        let game = tmp.path().join("elsewhere").join("Game");
+        write_game(&game);
+        symlink(&game, root.join("first")).unwrap();
+        symlink(&game, root.join("second")).unwrap();
+
+        // 子目录按路径排序，先到达的 `first` 被保留
+        let discovery = discover(&root, false);
+        assert_eq!(candidate_dirs(&discovery), vec![root.join("first")]);
+    }
+}
@@ -1,8 +1,20 @@
+use crate::commands::error::AppError;
 use crate::db::schema::Engine;
 use crate::models::{
-    AppSettings, CleanupResult, SETTING_CONTAINER_ROOT, SetContainerRootInput,
+    AppSettings, CleanupResult, ExternalToolPaths, LaunchLimit, SETTING_ALLOW_HOOKS,
+    SETTING_CLOSE_TO_TRAY, SETTING_CONTAINER_ROOT, SETTING_EASYRPG_BINARY,
+    SETTING_ENGINE_DEFAULTS_PREFIX, SETTING_GLOBAL_SHORTCUT, SETTING_LAUNCH_IN_TERMINAL,
+    SETTING_LAUNCH_LIMIT, SETTING_LAUNCH_QUEUE, SETTING_MINIMIZE_ON_LAUNCH,
+    SETTING_NWJS_DEFAULT_FLAVOR, SETTING_NWJS_MIRROR, SETTING_PROFILE_KEYS_MIGRATED,
+    SETTING_SCAN_IGNORES, SETTING_WINE_BINARY, SetContainerRootInput, SettingsFieldError,
+};
+use crate::services::download::manager::{DownloadManager, DownloadTaskInfo};
+use crate::services::game::arg_template;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+use crate::services::global_shortcut;
+use crate::services::{
+    EngineService, GameService, download::ffmpeg, download::mkxpz, download::nwjs,
 };
-use crate::services::{EngineService, GameService, download::mkxpz, download::nwjs};
 use std::sync::Arc;
 use tauri::{AppHandle, Manager, State};
 use tokio::sync::Mutex;
@@ -17,7 +29,7 @@ pub struct SettingsState {
 
 /// 获取应用设置
 #[tauri::command]
-pub async fn get_app_settings(state: State<'_, SettingsState>) -> Result<AppSettings, String> {
+pub async fn get_app_settings(state: State<'_, SettingsState>) -> Result<AppSettings, AppError> {
     let container_root = state.container_root.lock().await;
     Ok(AppSettings {
         container_root: container_root.clone(),
@@ -29,7 +41,7 @@ pub async fn get_app_settings(state: State<'_, SettingsState>) -> Result<AppSett
 pub async fn set_container_root(
     input: SetContainerRootInput,
     state: State<'_, SettingsState>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     // 验证路径
     let path = std::path::Path::new(&input.container_root);
     if !path.exists() {
@@ -50,61 +62,653 @@ pub async fn set_container_root(
 
 /// 获取 NW.js 稳定版信息
 #[tauri::command]
-pub async fn get_nwjs_stable_info() -> Result<nwjs::NwjsStableInfo, String> {
-    nwjs::get_stable_info().await
+pub async fn get_nwjs_stable_info(
+    state: State<'_, SettingsState>,
+) -> Result<nwjs::NwjsStableInfo, AppError> {
+    let mirror = nwjs::resolve_mirror(&mut *state.db.lock().await).await?;
+    Ok(nwjs::get_stable_info(&mirror).await?)
 }
 
-/// 下载 NW.js 稳定版
+/// 取消正在进行或排队中的 NW.js 下载
 #[tauri::command]
-pub async fn download_nwjs_stable(
-    flavor: String,
-    app: AppHandle,
+pub async fn cancel_nwjs_download(
+    task_id: String,
+    downloads: State<'_, DownloadManager>,
+) -> Result<(), AppError> {
+    if downloads.cancel(&task_id) {
+        Ok(())
+    } else {
+        Err(AppError::NotFound(format!(
+            "下载任务不存在或已结束: {}",
+            task_id
+        )))
+    }
+}
+
+/// 列出进行中与排队中的下载任务
+#[tauri::command]
+pub async fn list_download_tasks(
+    downloads: State<'_, DownloadManager>,
+) -> Result<Vec<DownloadTaskInfo>, AppError> {
+    Ok(downloads.list())
+}
+
+/// 获取外部工具路径（wine、EasyRPG Player）
+#[tauri::command]
+pub async fn get_external_tools(
     state: State<'_, SettingsState>,
-) -> Result<nwjs::NwjsInstallResult, String> {
-    let info = nwjs::get_stable_info().await?;
-    let flavor = match flavor.as_str() {
-        "sdk" => nwjs::NwjsFlavor::Sdk,
-        _ => nwjs::NwjsFlavor::Normal,
+) -> Result<ExternalToolPaths, AppError> {
+    let mut db_lock = state.db.lock().await;
+    let non_empty = |value: Option<String>| value.filter(|v| !v.trim().is_empty());
+    Ok(ExternalToolPaths {
+        wine_binary: non_empty(crate::db::get_setting(&mut *db_lock, SETTING_WINE_BINARY).await?),
+        easyrpg_binary: non_empty(
+            crate::db::get_setting(&mut *db_lock, SETTING_EASYRPG_BINARY).await?,
+        ),
+    })
+}
+
+/// 设置外部工具路径；为空表示未配置，带目录的路径必须指向已存在的文件
+#[tauri::command]
+pub async fn set_external_tools(
+    tools: ExternalToolPaths,
+    state: State<'_, SettingsState>,
+) -> Result<(), AppError> {
+    let validate = |value: Option<String>| -> Result<String, String> {
+        let value = value.unwrap_or_default().trim().to_string();
+        let path = std::path::Path::new(&value);
+        if path.components().count() > 1 && !path.is_file() {
+            return Err(format!("文件不存在: {}", value));
+        }
+        Ok(value)
     };
+    let wine = validate(tools.wine_binary)?;
+    let easyrpg = validate(tools.easyrpg_binary)?;
 
-    let result = nwjs::download_and_install(&app, info.version, flavor, info.target).await?;
+    let mut db_lock = state.db.lock().await;
+    crate::db::set_setting(&mut *db_lock, SETTING_WINE_BINARY, &wine).await?;
+    Ok(crate::db::set_setting(&mut *db_lock, SETTING_EASYRPG_BINARY, &easyrpg).await?)
+}
 
-    let engine_service = state.engine_service.lock().await;
-    let all = engine_service.get_all_engines().await?;
-    let target_name = nwjs_flavor_name(result.flavor);
+/// 是否允许执行游戏启动钩子（默认不允许）
+#[tauri::command]
+pub async fn get_allow_hooks(state: State<'_, SettingsState>) -> Result<bool, AppError> {
+    let mut db_lock = state.db.lock().await;
+    Ok(crate::db::get_setting(&mut *db_lock, SETTING_ALLOW_HOOKS)
+        .await?
+        .is_some_and(|v| v == "1"))
+}
 
-    let mut current_id: Option<String> = None;
-    for engine in &all {
-        if engine.engine_type != "nwjs" {
+/// 设置是否允许执行游戏启动钩子
+#[tauri::command]
+pub async fn set_allow_hooks(allow: bool, state: State<'_, SettingsState>) -> Result<(), AppError> {
+    let mut db_lock = state.db.lock().await;
+    let value = if allow { "1" } else { "0" };
+    Ok(crate::db::set_setting(&mut *db_lock, SETTING_ALLOW_HOOKS, value).await?)
+}
+
+/// 关闭主窗口时是否隐藏到托盘（默认直接退出）
+#[tauri::command]
+pub async fn get_close_to_tray(state: State<'_, SettingsState>) -> Result<bool, AppError> {
+    let mut db_lock = state.db.lock().await;
+    Ok(crate::db::get_setting(&mut *db_lock, SETTING_CLOSE_TO_TRAY)
+        .await?
+        .is_some_and(|v| v == "1"))
+}
+
+/// 设置关闭主窗口时是否隐藏到托盘
+#[tauri::command]
+pub async fn set_close_to_tray(
+    enabled: bool,
+    state: State<'_, SettingsState>,
+    app_state: State<'_, crate::commands::state::AppState>,
+) -> Result<(), AppError> {
+    let mut db_lock = state.db.lock().await;
+    let value = if enabled { "1" } else { "0" };
+    crate::db::set_setting(&mut *db_lock, SETTING_CLOSE_TO_TRAY, value).await?;
+    app_state
+        .close_to_tray
+        .store(enabled, std::sync::atomic::Ordering::Relaxed);
+    Ok(())
+}
+
+/// 启动游戏后是否最小化主窗口（默认不最小化）
+#[tauri::command]
+pub async fn get_minimize_on_launch(state: State<'_, SettingsState>) -> Result<bool, AppError> {
+    let mut db_lock = state.db.lock().await;
+    Ok(
+        crate::db::get_setting(&mut *db_lock, SETTING_MINIMIZE_ON_LAUNCH)
+            .await?
+            .is_some_and(|v| v == "1"),
+    )
+}
+
+/// 设置启动游戏后是否最小化主窗口（游戏配置中的设置优先）
+#[tauri::command]
+pub async fn set_minimize_on_launch(
+    enabled: bool,
+    state: State<'_, SettingsState>,
+) -> Result<(), AppError> {
+    let mut db_lock = state.db.lock().await;
+    let value = if enabled { "1" } else { "0" };
+    Ok(crate::db::set_setting(&mut *db_lock, SETTING_MINIMIZE_ON_LAUNCH, value).await?)
+}
+
+/// 是否在终端窗口中启动游戏（默认不使用终端）
+#[tauri::command]
+pub async fn get_launch_in_terminal(state: State<'_, SettingsState>) -> Result<bool, AppError> {
+    let mut db_lock = state.db.lock().await;
+    Ok(
+        crate::db::get_setting(&mut *db_lock, SETTING_LAUNCH_IN_TERMINAL)
+            .await?
+            .is_some_and(|v| v == "1"),
+    )
+}
+
+/// 设置是否在终端窗口中启动游戏（游戏配置中的设置优先）
+#[tauri::command]
+pub async fn set_launch_in_terminal(
+    enabled: bool,
+    state: State<'_, SettingsState>,
+) -> Result<(), AppError> {
+    let mut db_lock = state.db.lock().await;
+    let value = if enabled { "1" } else { "0" };
+    Ok(crate::db::set_setting(&mut *db_lock, SETTING_LAUNCH_IN_TERMINAL, value).await?)
+}
+
+/// 同时运行游戏数的限制（默认不限制）
+#[tauri::command]
+pub async fn get_launch_limit(state: State<'_, SettingsState>) -> Result<LaunchLimit, AppError> {
+    Ok(load_launch_limit(&mut *state.db.lock().await).await?)
+}
+
+/// 设置同时运行游戏数的限制，对之后的启动生效
+#[tauri::command]
+pub async fn set_launch_limit(
+    limit: LaunchLimit,
+    state: State<'_, SettingsState>,
+) -> Result<(), AppError> {
+    let mut db_lock = state.db.lock().await;
+    let max = limit.max_concurrent.to_string();
+    crate::db::set_setting(&mut *db_lock, SETTING_LAUNCH_LIMIT, &max).await?;
+    let queue = if limit.queue { "1" } else { "0" };
+    Ok(crate::db::set_setting(&mut *db_lock, SETTING_LAUNCH_QUEUE, queue).await?)
+}
+
+/// 显示/隐藏主窗口的全局快捷键（未设置时为 None）
+#[tauri::command]
+pub async fn get_global_shortcut(
+    state: State<'_, SettingsState>,
+) -> Result<Option<String>, AppError> {
+    let mut db_lock = state.db.lock().await;
+    Ok(
+        crate::db::get_setting(&mut *db_lock, SETTING_GLOBAL_SHORTCUT)
+            .await?
+            .filter(|v| !v.is_empty()),
+    )
+}
+
+/// 设置全局快捷键（为空表示停用），注销旧快捷键后立即注册新的
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+#[tauri::command]
+pub async fn set_global_shortcut(
+    shortcut: Option<String>,
+    state: State<'_, SettingsState>,
+    app: AppHandle,
+) -> Result<(), AppError> {
+    let accelerator = shortcut
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty());
+    let new =
+        match accelerator.as_deref() {
+            Some(accelerator) => Some(global_shortcut::parse(accelerator).map_err(|message| {
+                AppError::Validation {
+                    fields: vec![SettingsFieldError {
+                        field: "shortcut".into(),
+                        message: message.clone(),
+                    }],
+                    message,
+                }
+            })?),
+            None => None,
+        };
+
+    let mut db_lock = state.db.lock().await;
+    let old = crate::db::get_setting(&mut *db_lock, SETTING_GLOBAL_SHORTCUT)
+        .await?
+        .and_then(|v| global_shortcut::parse(&v).ok());
+    global_shortcut::replace(&app, old, new)?;
+    Ok(crate::db::set_setting(
+        &mut *db_lock,
+        SETTING_GLOBAL_SHORTCUT,
+        accelerator.as_deref().unwrap_or_default(),
+    )
+    .await?)
+}
+
+/// 移动端没有全局快捷键
+#[cfg(any(target_os = "android", target_os = "ios"))]
+#[tauri::command]
+pub async fn set_global_shortcut(_shortcut: Option<String>) -> Result<(), AppError> {
+    Err(AppError::Other("当前平台不支持全局快捷键".to_string()))
+}
+
+/// 获取用户自定义的扫描忽略规则（不含内置规则）
+#[tauri::command]
+pub async fn get_scan_ignores(state: State<'_, SettingsState>) -> Result<Vec<String>, AppError> {
+    Ok(load_scan_ignores(&mut *state.db.lock().await).await?)
+}
+
+/// 设置扫描忽略规则；按目录名匹配，支持 `*`/`?` 通配符，不区分大小写
+#[tauri::command]
+pub async fn set_scan_ignores(
+    patterns: Vec<String>,
+    state: State<'_, SettingsState>,
+) -> Result<(), AppError> {
+    let mut normalized: Vec<String> = Vec::new();
+    for pattern in patterns {
+        let pattern = pattern.trim();
+        if pattern.is_empty() || normalized.iter().any(|p| p == pattern) {
             continue;
         }
-        if !is_same_nwjs_flavor(engine, result.flavor) {
-            continue;
+        glob::Pattern::new(pattern).map_err(|e| format!("忽略规则无效 {}: {}", pattern, e))?;
+        normalized.push(pattern.to_string());
+    }
+    let value = serde_json::to_string(&normalized).map_err(|e| format!("序列化失败: {}", e))?;
+    let mut db_lock = state.db.lock().await;
+    Ok(crate::db::set_setting(&mut *db_lock, SETTING_SCAN_IGNORES, &value).await?)
+}
+
+/// 读取用户自定义的扫描忽略规则
+pub(crate) async fn load_scan_ignores(db: &mut toasty::Db) -> Result<Vec<String>, String> {
+    Ok(crate::db::get_setting(db, SETTING_SCAN_IGNORES)
+        .await?
+        .and_then(|value| serde_json::from_str(&value).ok())
+        .unwrap_or_default())
+}
+
+/// 获取某个引擎类型的默认启动参数
+#[tauri::command]
+pub async fn get_engine_defaults(
+    engine_type: String,
+    state: State<'_, SettingsState>,
+) -> Result<Vec<String>, AppError> {
+    validate_engine_type_key(&engine_type)?;
+    Ok(load_engine_defaults(&mut *state.db.lock().await, &engine_type).await?)
+}
+
+/// 设置某个引擎类型的默认启动参数，启动时放在游戏自身参数之前（同名参数以游戏设置为准）
+#[tauri::command]
+pub async fn set_engine_defaults(
+    engine_type: String,
+    args: Vec<String>,
+    state: State<'_, SettingsState>,
+) -> Result<(), AppError> {
+    validate_engine_type_key(&engine_type)?;
+    let args: Vec<String> = args
+        .into_iter()
+        .filter(|arg| !arg.trim().is_empty())
+        .collect();
+    for arg in &args {
+        if arg.contains(['\0', '\n', '\r']) {
+            return Err(AppError::Other(format!(
+                "启动参数不能包含换行或空字符: {:?}",
+                arg
+            )));
         }
-        if engine.version == result.version {
-            current_id = Some(engine.id.clone());
-            break;
+        if let Some(token) = arg_template::unknown_tokens(arg).first() {
+            return Err(AppError::Other(format!(
+                "未知的占位符 ${{{}}}，可用: {}",
+                token,
+                arg_template::token_list()
+            )));
         }
     }
 
-    if current_id.is_none() {
-        let added = engine_service
-            .add_engine(
-                target_name.to_string(),
-                result.version.clone(),
-                "nwjs".to_string(),
-                result.install_dir.clone(),
-            )
-            .await?;
-        current_id = Some(added.id);
+    let key = format!("{}{}", SETTING_ENGINE_DEFAULTS_PREFIX, engine_type);
+    let mut db_lock = state.db.lock().await;
+    let value = serde_json::to_string(&args).map_err(|e| format!("序列化失败: {}", e))?;
+    Ok(crate::db::set_setting(&mut *db_lock, &key, &value).await?)
+}
+
+/// 读取同时运行游戏数的限制；未设置或无法解析时不限制
+pub(crate) async fn load_launch_limit(db: &mut toasty::Db) -> Result<LaunchLimit, String> {
+    let max_concurrent = crate::db::get_setting(db, SETTING_LAUNCH_LIMIT)
+        .await?
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(0);
+    let queue = crate::db::get_setting(db, SETTING_LAUNCH_QUEUE)
+        .await?
+        .is_some_and(|v| v == "1");
+    Ok(LaunchLimit {
+        max_concurrent,
+        queue,
+    })
+}
+
+/// 读取引擎类型的默认启动参数
+pub(crate) async fn load_engine_defaults(
+    db: &mut toasty::Db,
+    engine_type: &str,
+) -> Result<Vec<String>, String> {
+    let key = format!("{}{}", SETTING_ENGINE_DEFAULTS_PREFIX, engine_type);
+    Ok(crate::db::get_setting(db, &key)
+        .await?
+        .and_then(|value| serde_json::from_str(&value).ok())
+        .unwrap_or_default())
+}
+
+fn validate_engine_type_key(engine_type: &str) -> Result<(), String> {
+    let valid = !engine_type.is_empty()
+        && engine_type
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("无效的引擎类型: {}", engine_type))
+    }
+}
+
+/// 获取 NW.js 下载镜像（未设置时返回官方地址）
+#[tauri::command]
+pub async fn get_nwjs_mirror(state: State<'_, SettingsState>) -> Result<String, AppError> {
+    Ok(nwjs::resolve_mirror(&mut *state.db.lock().await).await?)
+}
+
+/// 获取启动游戏时默认使用的 NW.js flavor
+#[tauri::command]
+pub async fn get_nwjs_default_flavor(
+    state: State<'_, SettingsState>,
+) -> Result<nwjs::NwjsFlavor, AppError> {
+    Ok(nwjs::resolve_default_flavor(&mut *state.db.lock().await).await?)
+}
+
+/// 设置启动游戏时默认使用的 NW.js flavor（单个游戏可在设置中覆盖）
+#[tauri::command]
+pub async fn set_nwjs_default_flavor(
+    flavor: nwjs::NwjsFlavor,
+    state: State<'_, SettingsState>,
+) -> Result<(), AppError> {
+    let mut db_lock = state.db.lock().await;
+    Ok(crate::db::set_setting(&mut *db_lock, SETTING_NWJS_DEFAULT_FLAVOR, flavor.as_str()).await?)
+}
+
+/// 设置 NW.js 下载镜像，传入空字符串恢复官方地址
+#[tauri::command]
+pub async fn set_nwjs_mirror(
+    mirror: String,
+    state: State<'_, SettingsState>,
+) -> Result<(), AppError> {
+    let value = if mirror.trim().is_empty() {
+        String::new()
+    } else {
+        nwjs::normalize_mirror(&mirror)?
+    };
+    let mut db_lock = state.db.lock().await;
+    Ok(crate::db::set_setting(&mut *db_lock, SETTING_NWJS_MIRROR, &value).await?)
+}
+
+/// 获取 NW.js 全部版本列表
+#[tauri::command]
+pub async fn get_nwjs_versions(app: AppHandle) -> Result<Vec<nwjs::NwjsVersionInfo>, AppError> {
+    Ok(nwjs::list_versions(&app).await?)
+}
+
+/// 列出本地已安装的 NW.js 运行时，并与 engines 表中的登记记录对照
+#[tauri::command]
+pub async fn list_installed_runtimes(
+    app: AppHandle,
+    state: State<'_, SettingsState>,
+) -> Result<nwjs::NwjsInstalledRuntimes, AppError> {
+    let mut runtimes = tokio::task::spawn_blocking(move || nwjs::list_installed(&app))
+        .await
+        .map_err(|e| format!("扫描运行时失败: {}", e))??;
+
+    let engines: Vec<Engine> = {
+        let engine_service = state.engine_service.lock().await;
+        engine_service.list_engines_by_type("nwjs").await?
+    };
+    let engine_dirs: Vec<(String, std::path::PathBuf)> = engines
+        .into_iter()
+        .map(|e| {
+            let dir = crate::utils::path::canonicalize(std::path::Path::new(&e.engine_path));
+            (e.id, dir)
+        })
+        .collect();
+
+    let mut matched = std::collections::HashSet::new();
+    for runtime in &mut runtimes {
+        let dir = crate::utils::path::canonicalize(std::path::Path::new(&runtime.install_dir));
+        for (id, engine_dir) in &engine_dirs {
+            if *engine_dir == dir {
+                runtime.registered = true;
+                matched.insert(id.clone());
+            }
+        }
     }
+    let missing_engine_ids = engine_dirs
+        .into_iter()
+        .filter(|(id, dir)| !matched.contains(id) && !dir.is_dir())
+        .map(|(id, _)| id)
+        .collect();
+
+    Ok(nwjs::NwjsInstalledRuntimes {
+        total_bytes: runtimes.iter().map(|r| r.size_bytes).sum(),
+        runtimes,
+        missing_engine_ids,
+    })
+}
+
+/// 检查当前平台已安装的 NW.js 运行时是否完整（可执行文件与关键资源文件），供启动前提示重新下载
+#[tauri::command]
+pub async fn verify_runtime(
+    version: String,
+    flavor: nwjs::NwjsFlavor,
+    app: AppHandle,
+) -> Result<nwjs::NwjsRuntimeCheck, AppError> {
+    let version = nwjs::normalize_version(&version);
+    let target = nwjs::current_target()?;
+    let install_dir = nwjs::runtime_install_dir(&app, &version, flavor, &target)?;
+    tokio::task::spawn_blocking(move || nwjs::verify_install(&install_dir, &target))
+        .await
+        .map_err(|e| AppError::Other(format!("检查运行时失败: {}", e)))
+}
+
+/// 下载 NW.js 稳定版（`skip_checksum` 为 true 时跳过 SHA256 校验）
+#[tauri::command]
+pub async fn download_nwjs_stable(
+    flavor: String,
+    skip_checksum: Option<bool>,
+    app: AppHandle,
+    state: State<'_, SettingsState>,
+) -> Result<nwjs::NwjsInstallResult, AppError> {
+    let mirror = nwjs::resolve_mirror(&mut *state.db.lock().await).await?;
+    let info = nwjs::get_stable_info(&mirror).await?;
+    let flavor = nwjs::NwjsFlavor::parse(&flavor).unwrap_or(nwjs::NwjsFlavor::Normal);
+
+    let verify_checksum = !skip_checksum.unwrap_or(false);
+    let result = nwjs::download_and_install(
+        &app,
+        &mirror,
+        info.version,
+        flavor,
+        info.target,
+        verify_checksum,
+    )
+    .await
+    .map_err(AppError::Download)?;
+
+    let engine_service = state.engine_service.lock().await;
+    let current_id = register_nwjs_engine(&engine_service, &result).await?;
 
     // 默认清理旧版，仅保留最新版本
-    prune_old_nwjs_engines(&engine_service, &app, current_id.as_deref(), result.flavor).await?;
+    prune_old_nwjs_engines(&engine_service, &app, Some(&current_id), result.flavor).await?;
+
+    Ok(result)
+}
 
+/// 下载指定版本的 NW.js，不清理其它已安装版本（用于为个别游戏固定旧版本）
+///
+/// 可同时发起多个下载，超出并发上限的任务会排队。
+#[tauri::command]
+pub async fn download_nwjs_version(
+    version: String,
+    flavor: String,
+    skip_checksum: Option<bool>,
+    app: AppHandle,
+    state: State<'_, SettingsState>,
+) -> Result<nwjs::NwjsInstallResult, AppError> {
+    let version = nwjs::normalize_version(&version);
+    semver::Version::parse(&version).map_err(|_| format!("无效的 NW.js 版本号: {}", version))?;
+    let mirror = nwjs::resolve_mirror(&mut *state.db.lock().await).await?;
+    let flavor = nwjs::NwjsFlavor::parse(&flavor).unwrap_or(nwjs::NwjsFlavor::Normal);
+
+    let result = nwjs::download_and_install(
+        &app,
+        &mirror,
+        version,
+        flavor,
+        nwjs::current_target()?,
+        !skip_checksum.unwrap_or(false),
+    )
+    .await
+    .map_err(AppError::Download)?;
+
+    let engine_service = state.engine_service.lock().await;
+    register_nwjs_engine(&engine_service, &result).await?;
     Ok(result)
 }
 
+/// 为安装好的 NW.js 登记引擎记录（同版本、同 flavor 已登记时复用）并记录目标平台与占用空间，返回引擎 ID
+async fn register_nwjs_engine(
+    engine_service: &EngineService,
+    result: &nwjs::NwjsInstallResult,
+) -> Result<String, String> {
+    let existing = engine_service
+        .get_all_engines()
+        .await?
+        .into_iter()
+        .find(|engine| {
+            engine.engine_type == "nwjs"
+                && is_same_nwjs_flavor(engine, result.flavor)
+                && engine.version == result.version
+        });
+    let id = match existing {
+        Some(engine) => engine.id,
+        None => {
+            engine_service
+                .add_engine(
+                    nwjs_flavor_name(result.flavor).to_string(),
+                    result.version.clone(),
+                    "nwjs".to_string(),
+                    result.install_dir.clone(),
+                )
+                .await?
+                .id
+        }
+    };
+    engine_service
+        .update_engine_build(&id, &result.target, &result.install_dir)
+        .await?;
+    Ok(id)
+}
+
+/// 为已安装的 NW.js 运行时下载支持 MP4/H.264 的 ffmpeg，替换原始库
+///
+/// 该版本在当前平台安装的所有 flavor 都会替换，返回替换的运行时数量。
+#[tauri::command]
+pub async fn download_nwjs_ffmpeg(version: String, app: AppHandle) -> Result<usize, AppError> {
+    let version = nwjs::normalize_version(&version);
+    let target = nwjs::current_target()?;
+    let runtimes: Vec<nwjs::NwjsInstalledRuntime> =
+        tokio::task::spawn_blocking(move || nwjs::list_installed(&app))
+            .await
+            .map_err(|e| format!("扫描运行时失败: {}", e))??
+            .into_iter()
+            .filter(|r| r.version == version && r.target == target)
+            .collect();
+    if runtimes.is_empty() {
+        return Err(AppError::RuntimeMissing(format!(
+            "NW.js {} 未安装，请先在设置中下载该版本",
+            version
+        )));
+    }
+
+    for runtime in &runtimes {
+        let install_dir = std::path::Path::new(&runtime.install_dir);
+        let lib = ffmpeg::install_codec_ffmpeg(install_dir, &version, &target)
+            .await
+            .map_err(AppError::Download)?;
+        tracing::info!(version = %version, lib = %lib.display(), "已替换 NW.js ffmpeg");
+    }
+    Ok(runtimes.len())
+}
+
+/// 删除已安装的 NW.js 运行时（仅限应用数据目录内），同时移除对应的引擎记录
+#[tauri::command]
+pub async fn delete_nwjs_runtime(
+    version: String,
+    flavor: nwjs::NwjsFlavor,
+    app: AppHandle,
+    state: State<'_, SettingsState>,
+) -> Result<(), AppError> {
+    let in_use: Vec<String> = {
+        let service = state.game_service.lock().await;
+        service
+            .get_all_games()
+            .await?
+            .into_iter()
+            .filter(|g| g.runtime_version.as_deref() == Some(version.as_str()))
+            .map(|g| g.title)
+            .collect()
+    };
+    if !in_use.is_empty() {
+        return Err(AppError::Other(format!(
+            "以下游戏仍在使用 NW.js {}: {}",
+            version,
+            in_use.join("、")
+        )));
+    }
+
+    let target = nwjs::current_target()?;
+    let install_dir = nwjs::runtime_install_dir(&app, &version, flavor, &target)?;
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("获取应用数据目录失败: {}", e))?;
+    if !crate::utils::path::is_within(&install_dir, &app_data_dir) {
+        return Err(AppError::PathInvalid(format!(
+            "拒绝删除应用数据目录以外的路径: {}",
+            install_dir.display()
+        )));
+    }
+
+    if install_dir.exists() {
+        std::fs::remove_dir_all(&install_dir)
+            .map_err(|e| AppError::Io(format!("删除运行时失败: {}", e)))?;
+        // 清理空的 flavor / version 目录
+        if let Some(flavor_dir) = install_dir.parent() {
+            let _ = std::fs::remove_dir(flavor_dir);
+            if let Some(version_dir) = flavor_dir.parent() {
+                let _ = std::fs::remove_dir(version_dir);
+            }
+        }
+    }
+
+    let engine_service = state.engine_service.lock().await;
+    for engine in engine_service.get_all_engines().await? {
+        if engine.engine_type == "nwjs"
+            && engine.version == version
+            && is_same_nwjs_flavor(&engine, flavor)
+        {
+            engine_service.delete_engine(&engine.id).await?;
+        }
+    }
+
+    Ok(())
+}
+
 fn nwjs_flavor_name(flavor: nwjs::NwjsFlavor) -> &'static str {
     match flavor {
         nwjs::NwjsFlavor::Sdk => "NW.js (SDK)",
@@ -113,11 +717,7 @@ fn nwjs_flavor_name(flavor: nwjs::NwjsFlavor) -> &'static str {
 }
 
 fn is_same_nwjs_flavor(engine: &Engine, flavor: nwjs::NwjsFlavor) -> bool {
-    let lower = engine.name.to_lowercase();
-    match flavor {
-        nwjs::NwjsFlavor::Sdk => lower.contains("sdk"),
-        nwjs::NwjsFlavor::Normal => !lower.contains("sdk"),
-    }
+    flavor.matches_engine_name(&engine.name)
 }
 
 /// 清理旧版 NW.js（按 flavor 仅保留最新）。
@@ -162,11 +762,91 @@ fn remove_engine_path_if_owned(app: &AppHandle, path: &str) {
     }
 }
 
+/// 备份数据库；未指定目标时写入应用数据目录的备份目录，返回备份文件路径
+#[tauri::command]
+pub async fn backup_database(
+    dest_path: Option<String>,
+    app: AppHandle,
+) -> Result<String, AppError> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("获取应用数据目录失败: {}", e))?;
+    let db_path = crate::db::database_path(&app_data_dir);
+
+    tokio::task::spawn_blocking(move || {
+        let dest = match dest_path {
+            Some(dest) => {
+                let dest = std::path::PathBuf::from(dest);
+                crate::db::backup::backup_to(&db_path, &dest)?;
+                dest
+            }
+            None => crate::db::backup::create_timestamped_backup(
+                &db_path,
+                &crate::db::backups_dir(&app_data_dir),
+                crate::db::backup::KEEP_BACKUPS,
+            )?,
+        };
+        Ok(dest.to_string_lossy().to_string())
+    })
+    .await
+    .map_err(|e| format!("备份数据库失败: {}", e))?
+    .map_err(AppError::Db)
+}
+
+/// 从备份恢复数据库；恢复前会先为当前数据库创建一份自动备份
+#[tauri::command]
+pub async fn restore_database(
+    src_path: String,
+    app: AppHandle,
+    state: State<'_, crate::commands::state::AppState>,
+) -> Result<(), AppError> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("获取应用数据目录失败: {}", e))?;
+    let db_path = crate::db::database_path(&app_data_dir);
+    let src = std::path::PathBuf::from(src_path);
+
+    // 持有 toasty 连接锁，恢复期间不会有其它查询写入
+    let _db_lock = state.db.lock().await;
+    tokio::task::spawn_blocking(move || {
+        crate::db::backup::validate(&src)?;
+        crate::db::backup::create_timestamped_backup(
+            &db_path,
+            &crate::db::backups_dir(&app_data_dir),
+            crate::db::backup::KEEP_BACKUPS,
+        )?;
+        crate::db::backup::restore_from(&db_path, &src)
+    })
+    .await
+    .map_err(|e| format!("恢复数据库失败: {}", e))?
+    .map_err(AppError::Db)?;
+
+    state.config_cache.lock().unwrap().clear();
+    Ok(())
+}
+
+/// 把 UUID 形式的 profile 目录迁移为按游戏标题命名，返回重命名的数量
+#[tauri::command]
+pub async fn migrate_profiles(state: State<'_, SettingsState>) -> Result<u32, AppError> {
+    let root = std::path::PathBuf::from(state.container_root.lock().await.as_str());
+    let migrated = state
+        .game_service
+        .lock()
+        .await
+        .migrate_profile_keys(&root)
+        .await?;
+    let mut db_lock = state.db.lock().await;
+    crate::db::set_setting(&mut *db_lock, SETTING_PROFILE_KEYS_MIGRATED, "1").await?;
+    Ok(migrated)
+}
+
 /// 清理无用容器
 #[tauri::command]
 pub async fn cleanup_unused_containers(
     state: State<'_, SettingsState>,
-) -> Result<CleanupResult, String> {
+) -> Result<CleanupResult, AppError> {
     let container_root = state.container_root.lock().await;
     let root = std::path::PathBuf::from(container_root.as_str());
     drop(container_root);
@@ -207,7 +887,7 @@ pub async fn import_mkxpz_archive(
     archive_path: String,
     app: AppHandle,
     state: State<'_, SettingsState>,
-) -> Result<mkxpz::MkxpzImportResult, String> {
+) -> Result<mkxpz::MkxpzImportResult, AppError> {
     let result = mkxpz::import_from_archive(&app, std::path::Path::new(&archive_path))?;
 
     let engine_service = state.engine_service.lock().await;
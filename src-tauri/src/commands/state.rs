@@ -1,8 +1,10 @@
 use crate::engines::EngineRegistry;
 use crate::models::GameConfig;
+use crate::services::game::session::{LaunchSlots, LaunchingGames, RunningGames};
 use crate::services::{EngineService, FileService, GameService, LauncherService};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
 use std::sync::{Arc, Mutex as StdMutex};
 use tokio::sync::Mutex;
 
@@ -17,6 +19,14 @@ pub struct AppState {
     pub container_root: Arc<Mutex<String>>,
     pub engine_registry: Arc<Mutex<EngineRegistry>>,
     pub config_cache: ConfigCache,
+    /// 运行中的游戏进程（key = 游戏 ID）
+    pub running_games: RunningGames,
+    /// 正在启动的游戏，与运行表一起防止重复启动
+    pub launching_games: LaunchingGames,
+    /// 同时运行游戏数的名额（设置了上限时使用）
+    pub launch_slots: LaunchSlots,
+    /// 关闭主窗口时是否隐藏到托盘，窗口事件中直接读取，不访问数据库
+    pub close_to_tray: AtomicBool,
 }
 
 impl AppState {
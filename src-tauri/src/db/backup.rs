@@ -0,0 +1,121 @@
+//! 数据库备份与恢复。
+//!
+//! 通过独立的 rusqlite 连接操作，toasty 连接保持打开也不影响：备份使用
+//! `VACUUM INTO` 生成一致的快照，恢复使用 SQLite 在线备份 API 逐页写回。
+
+use std::path::{Path, PathBuf};
+
+/// 自动备份保留的份数
+pub const KEEP_BACKUPS: usize = 10;
+
+const BACKUP_PREFIX: &str = "app-";
+const BACKUP_EXT: &str = "sqlite";
+
+/// 将数据库快照写入 `dest`（先写临时文件再重命名）
+pub fn backup_to(db_path: &Path, dest: &Path) -> Result<(), String> {
+    if let Some(parent) = dest.parent() {
+        crate::utils::path::ensure_dir(parent)?;
+    }
+    let tmp = dest.with_extension("sqlite.tmp");
+    let _ = std::fs::remove_file(&tmp);
+
+    let conn = open(db_path)?;
+    conn.execute("VACUUM INTO ?1", [tmp.to_string_lossy().as_ref()])
+        .map_err(|e| format!("备份数据库失败: {}", e))?;
+    drop(conn);
+
+    std::fs::rename(&tmp, dest).map_err(|e| {
+        let _ = std::fs::remove_file(&tmp);
+        format!("备份数据库失败: {}", e)
+    })
+}
+
+/// 在 `backups_dir` 中创建带时间戳的备份，并只保留最近 `keep` 份
+pub fn create_timestamped_backup(
+    db_path: &Path,
+    backups_dir: &Path,
+    keep: usize,
+) -> Result<PathBuf, String> {
+    let dest = backups_dir.join(format!(
+        "{}{}.{}",
+        BACKUP_PREFIX,
+        crate::utils::now_unix_ms(),
+        BACKUP_EXT
+    ));
+    backup_to(db_path, &dest)?;
+    prune_backups(backups_dir, keep);
+    Ok(dest)
+}
+
+/// 删除超出保留份数的旧备份（文件名中的时间戳越大越新）
+fn prune_backups(backups_dir: &Path, keep: usize) {
+    let Ok(entries) = std::fs::read_dir(backups_dir) else {
+        return;
+    };
+    let mut backups: Vec<(i64, PathBuf)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let name = path.file_name()?.to_str()?;
+            let stamp = name
+                .strip_prefix(BACKUP_PREFIX)?
+                .strip_suffix(BACKUP_EXT)?
+                .strip_suffix('.')?
+                .parse::<i64>()
+                .ok()?;
+            Some((stamp, path))
+        })
+        .collect();
+    backups.sort_by(|a, b| b.0.cmp(&a.0));
+
+    for (_, path) in backups.into_iter().skip(keep) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// 校验文件是完整的 GameManager 数据库
+pub fn validate(src: &Path) -> Result<(), String> {
+    let conn =
+        rusqlite::Connection::open_with_flags(src, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .map_err(|e| format!("无法打开数据库文件: {}", e))?;
+
+    let check: String = conn
+        .query_row("PRAGMA integrity_check", [], |row| row.get(0))
+        .map_err(|_| "文件不是有效的 SQLite 数据库".to_string())?;
+    if check != "ok" {
+        return Err(format!("数据库文件已损坏: {}", check));
+    }
+
+    let has_games: bool = conn
+        .query_row(
+            "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'games')",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("读取数据库结构失败: {}", e))?;
+    if !has_games {
+        return Err("文件不是 GameManager 数据库".to_string());
+    }
+
+    Ok(())
+}
+
+/// 用 `src` 的内容覆盖当前数据库，并补齐迁移
+pub fn restore_from(db_path: &Path, src: &Path) -> Result<(), String> {
+    validate(src)?;
+
+    let mut conn = open(db_path)?;
+    conn.restore(
+        rusqlite::DatabaseName::Main,
+        src,
+        None::<fn(rusqlite::backup::Progress)>,
+    )
+    .map_err(|e| format!("恢复数据库失败: {}", e))?;
+    drop(conn);
+
+    super::migrations::migrate(db_path)
+}
+
+fn open(db_path: &Path) -> Result<rusqlite::Connection, String> {
+    rusqlite::Connection::open(db_path).map_err(|e| format!("打开数据库失败: {}", e))
+}
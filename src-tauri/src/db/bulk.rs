@@ -0,0 +1,84 @@
+//! toasty 无法表达的批量写操作与查询，使用独立的 rusqlite 连接执行，写操作在单个事务中完成。
+//!
+//! 调用方需持有 toasty 连接锁，避免与其它写入交错。
+
+use std::path::Path;
+
+/// 删除指定游戏及其标签，返回实际删除的游戏数
+pub fn delete_games(db_path: &Path, ids: &[String]) -> Result<usize, String> {
+    if ids.is_empty() {
+        return Ok(0);
+    }
+
+    let mut conn =
+        rusqlite::Connection::open(db_path).map_err(|e| format!("打开数据库失败: {}", e))?;
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("开启事务失败: {}", e))?;
+
+    let placeholders = vec!["?"; ids.len()].join(", ");
+    tx.execute(
+        &format!("DELETE FROM game_tags WHERE game_id IN ({})", placeholders),
+        rusqlite::params_from_iter(ids.iter()),
+    )
+    .map_err(|e| format!("删除游戏标签失败: {}", e))?;
+    let removed = tx
+        .execute(
+            &format!("DELETE FROM games WHERE id IN ({})", placeholders),
+            rusqlite::params_from_iter(ids.iter()),
+        )
+        .map_err(|e| format!("批量删除游戏失败: {}", e))?;
+
+    tx.commit().map_err(|e| format!("提交事务失败: {}", e))?;
+    Ok(removed)
+}
+
+/// 替换游戏的全部标签，`tags` 为 (id, 归一化标签, 显示标签)
+pub fn replace_tags(
+    db_path: &Path,
+    game_id: &str,
+    tags: &[(String, String, String)],
+) -> Result<(), String> {
+    let mut conn =
+        rusqlite::Connection::open(db_path).map_err(|e| format!("打开数据库失败: {}", e))?;
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("开启事务失败: {}", e))?;
+
+    tx.execute("DELETE FROM game_tags WHERE game_id = ?1", [game_id])
+        .map_err(|e| format!("删除游戏标签失败: {}", e))?;
+    for (id, tag, display_tag) in tags {
+        tx.execute(
+            "INSERT INTO game_tags (id, game_id, tag, display_tag) VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![id, game_id, tag, display_tag],
+        )
+        .map_err(|e| format!("保存游戏标签失败: {}", e))?;
+    }
+
+    tx.commit().map_err(|e| format!("提交事务失败: {}", e))?;
+    Ok(())
+}
+
+/// 标题或路径包含 `needle` 的游戏 ID
+///
+/// `needle` 应已转为小写；SQLite 的 `lower` / `LIKE` 只对 ASCII 字母不区分大小写。
+pub fn search_game_ids(db_path: &Path, needle: &str) -> Result<Vec<String>, String> {
+    let conn = rusqlite::Connection::open(db_path).map_err(|e| format!("打开数据库失败: {}", e))?;
+    let escaped = needle
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    let pattern = format!("%{}%", escaped);
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id FROM games
+                WHERE lower(title) LIKE ?1 ESCAPE '\\' OR lower(path) LIKE ?1 ESCAPE '\\'",
+        )
+        .map_err(|e| format!("搜索游戏失败: {}", e))?;
+    let ids = stmt
+        .query_map([pattern], |row| row.get(0))
+        .and_then(|rows| rows.collect::<Result<Vec<String>, _>>())
+        .map_err(|e| format!("搜索游戏失败: {}", e))?;
+    Ok(ids)
+}
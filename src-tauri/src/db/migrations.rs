@@ -0,0 +1,142 @@
+//! 数据库结构迁移。
+//!
+//! toasty 的 `push_schema` 只在首次创建数据库时按模型建表，已有数据库新增的
+//! 列/表需要在连接前按版本顺序执行下列 SQL。当前版本记录在 `PRAGMA user_version`。
+
+use crate::models::SETTING_RENAMED_PROFILE_KEYS;
+use std::path::Path;
+
+/// 迁移列表：下标 + 1 即目标版本号，只能追加不能修改。
+const MIGRATIONS: &[&str] = &[
+    // 1: 累计游玩时长
+    "ALTER TABLE games ADD COLUMN total_play_ms INTEGER NOT NULL DEFAULT 0;",
+    // 2: 游戏标签（tag 为归一化形式，display_tag 保留原始写法）
+    "CREATE TABLE IF NOT EXISTS game_tags (
+        id TEXT NOT NULL PRIMARY KEY,
+        game_id TEXT NOT NULL,
+        tag TEXT NOT NULL,
+        display_tag TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS index_game_tags_by_game_id ON game_tags (game_id);
+    CREATE INDEX IF NOT EXISTS index_game_tags_by_tag ON game_tags (tag);",
+    // 3: 收藏
+    "ALTER TABLE games ADD COLUMN favorite BOOLEAN NOT NULL DEFAULT 0;",
+    // 4: 评分（0-5，未评分为 NULL）
+    "ALTER TABLE games ADD COLUMN rating INTEGER;",
+    // 5: 完成状态
+    "ALTER TABLE games ADD COLUMN status TEXT NOT NULL DEFAULT '未开始';",
+    // 6: 增量扫描缓存
+    "CREATE TABLE IF NOT EXISTS scan_cache (
+        path TEXT NOT NULL PRIMARY KEY,
+        fingerprint TEXT NOT NULL,
+        mtime_ms INTEGER NOT NULL,
+        scanned_at INTEGER NOT NULL
+    );",
+    // 7: 路径、引擎类型索引与 profile_key 唯一约束。
+    //    历史上并发导入可能生成重复的 profile_key，先为较晚的记录追加 ID 前缀区分。
+    "CREATE INDEX IF NOT EXISTS index_games_by_path ON games (path);
+    CREATE INDEX IF NOT EXISTS index_games_by_engine_type ON games (engine_type);
+    UPDATE games SET profile_key = profile_key || '-' || substr(id, 1, 8)
+        WHERE rowid NOT IN (SELECT MIN(rowid) FROM games GROUP BY profile_key);
+    CREATE UNIQUE INDEX IF NOT EXISTS index_games_by_profile_key ON games (profile_key);",
+    // 8: 去掉 Windows verbatim 路径前缀（`\\?\UNC\` -> `\\`，`\\?\` -> 空）。
+    //    两种写法同时存在的记录保持原样，可通过合并重复游戏处理。
+    r"UPDATE games SET path = '\\' || substr(path, 9) WHERE path LIKE '\\?\UNC\%';
+    UPDATE games SET path = substr(path, 5) WHERE path LIKE '\\?\%';
+    UPDATE OR IGNORE games SET normalized_path = '\\' || substr(normalized_path, 9)
+        WHERE normalized_path LIKE '\\?\UNC\%';
+    UPDATE OR IGNORE games SET normalized_path = substr(normalized_path, 5)
+        WHERE normalized_path LIKE '\\?\%';",
+    // 9: 引擎类型是否由用户手动指定
+    "ALTER TABLE games ADD COLUMN engine_user_set BOOLEAN NOT NULL DEFAULT 0;",
+    // 10: 运行时的目标平台与占用空间
+    "ALTER TABLE engines ADD COLUMN target TEXT;
+    ALTER TABLE engines ADD COLUMN size_bytes INTEGER;",
+];
+
+/// 为重复 profile_key 追加后缀的迁移版本
+const PROFILE_KEY_DEDUP_VERSION: i64 = 7;
+
+/// 对已有数据库执行未应用的迁移。
+///
+/// 每个版本的 SQL、附带记录与版本号在同一事务中提交，中途失败不会留下半完成的迁移。
+pub fn migrate(db_path: &Path) -> Result<(), String> {
+    let mut conn = open(db_path)?;
+    let current = user_version(&conn)?;
+
+    for (index, sql) in MIGRATIONS.iter().enumerate().skip(current as usize) {
+        let version = index as i64 + 1;
+        let tx = conn
+            .transaction()
+            .map_err(|e| format!("数据库迁移 {} 失败: {}", version, e))?;
+        let renamed = if version == PROFILE_KEY_DEDUP_VERSION {
+            duplicate_profile_keys(&tx)?
+        } else {
+            Vec::new()
+        };
+        tx.execute_batch(sql)
+            .map_err(|e| format!("数据库迁移 {} 失败: {}", version, e))?;
+        if !renamed.is_empty() {
+            record_renamed_profile_keys(&tx, &renamed)?;
+        }
+        set_user_version(&tx, version)?;
+        tx.commit()
+            .map_err(|e| format!("数据库迁移 {} 失败: {}", version, e))?;
+        tracing::info!(version, "数据库迁移完成");
+    }
+
+    Ok(())
+}
+
+/// 新建数据库已由模型直接生成最新结构，仅记录版本号。
+pub fn mark_latest(db_path: &Path) -> Result<(), String> {
+    let conn = open(db_path)?;
+    set_user_version(&conn, MIGRATIONS.len() as i64)
+}
+
+/// 迁移 7 将要重命名的 (原 key, 新 key)，与迁移中的 UPDATE 使用相同条件
+fn duplicate_profile_keys(conn: &rusqlite::Connection) -> Result<Vec<(String, String)>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT profile_key, profile_key || '-' || substr(id, 1, 8) FROM games
+                WHERE rowid NOT IN (SELECT MIN(rowid) FROM games GROUP BY profile_key)",
+        )
+        .map_err(|e| format!("查询重复的 profile_key 失败: {}", e))?;
+    let renamed = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .and_then(|rows| rows.collect::<Result<Vec<(String, String)>, _>>())
+        .map_err(|e| format!("查询重复的 profile_key 失败: {}", e))?;
+    Ok(renamed)
+}
+
+/// 记录重命名的 profile_key，profile 目录在启动解析出容器根目录后再复制
+fn record_renamed_profile_keys(
+    conn: &rusqlite::Connection,
+    renamed: &[(String, String)],
+) -> Result<(), String> {
+    for (old_key, new_key) in renamed {
+        tracing::warn!(old_key, new_key, "重复的 profile_key 已重命名");
+    }
+    let value = serde_json::to_string(renamed)
+        .map_err(|e| format!("序列化重命名的 profile_key 失败: {}", e))?;
+    conn.execute(
+        "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+        rusqlite::params![SETTING_RENAMED_PROFILE_KEYS, value],
+    )
+    .map_err(|e| format!("记录重命名的 profile_key 失败: {}", e))?;
+    Ok(())
+}
+
+fn open(db_path: &Path) -> Result<rusqlite::Connection, String> {
+    rusqlite::Connection::open(db_path).map_err(|e| format!("打开数据库失败: {}", e))
+}
+
+fn user_version(conn: &rusqlite::Connection) -> Result<i64, String> {
+    conn.query_row("PRAGMA user_version", [], |row| row.get(0))
+        .map_err(|e| format!("读取数据库版本失败: {}", e))
+}
+
+fn set_user_version(conn: &rusqlite::Connection, version: i64) -> Result<(), String> {
+    conn.pragma_update(None, "user_version", version)
+        .map_err(|e| format!("写入数据库版本失败: {}", e))
+}
@@ -1,6 +1,20 @@
+pub mod backup;
+pub mod bulk;
+pub mod migrations;
+pub mod scan_cache;
 pub mod schema;
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// 数据库文件路径
+pub fn database_path(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join("db").join("app.sqlite")
+}
+
+/// 自动备份目录
+pub fn backups_dir(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join("db").join("backups")
+}
 
 pub async fn init_db(db_path: &Path) -> Result<toasty::Db, String> {
     if let Some(parent) = db_path.parent() {
@@ -8,13 +22,18 @@ pub async fn init_db(db_path: &Path) -> Result<toasty::Db, String> {
     }
 
     let db_exists = db_path.exists();
+    if db_exists {
+        migrations::migrate(db_path)?;
+    }
     let conn_str = format!("sqlite://{}", db_path.display());
 
     let db = toasty::Db::builder()
         .models(toasty::models!(
             crate::db::schema::Game,
+            crate::db::schema::GameTag,
             crate::db::schema::Engine,
             crate::db::schema::Setting,
+            crate::db::schema::ScanCache,
         ))
         .connect(&conn_str)
         .await
@@ -24,6 +43,7 @@ pub async fn init_db(db_path: &Path) -> Result<toasty::Db, String> {
         db.push_schema()
             .await
             .map_err(|e| format!("数据库迁移失败: {}", e))?;
+        migrations::mark_latest(db_path)?;
     }
 
     Ok(db)
@@ -0,0 +1,52 @@
+//! 增量扫描缓存：记录扫描根目录下各顶层目录上次扫描时的修改时间。
+//!
+//! 每条记录附带生成时的扫描配置指纹（容器根目录、忽略规则等），
+//! 配置变化后旧记录不再命中，下次扫描时被覆盖。
+
+use std::collections::HashMap;
+use std::path::Path;
+
+/// 读取与 `fingerprint` 匹配的缓存记录（规范化路径 -> 修改时间毫秒）
+pub fn load(db_path: &Path, fingerprint: &str) -> Result<HashMap<String, i64>, String> {
+    let conn = open(db_path)?;
+    let mut stmt = conn
+        .prepare("SELECT path, mtime_ms FROM scan_cache WHERE fingerprint = ?1")
+        .map_err(|e| format!("查询扫描缓存失败: {}", e))?;
+    let rows = stmt
+        .query_map([fingerprint], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| format!("查询扫描缓存失败: {}", e))?;
+    rows.collect::<Result<_, _>>()
+        .map_err(|e| format!("读取扫描缓存失败: {}", e))
+}
+
+/// 写入（覆盖）顶层目录的修改时间
+///
+/// 调用方需持有 toasty 连接锁，避免与其它写入交错。
+pub fn save(db_path: &Path, fingerprint: &str, entries: &[(String, i64)]) -> Result<(), String> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let mut conn = open(db_path)?;
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("开启事务失败: {}", e))?;
+    let scanned_at = crate::utils::now_unix_ms();
+    {
+        let mut stmt = tx
+            .prepare(
+                "INSERT OR REPLACE INTO scan_cache (path, fingerprint, mtime_ms, scanned_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )
+            .map_err(|e| format!("写入扫描缓存失败: {}", e))?;
+        for (path, mtime_ms) in entries {
+            stmt.execute(rusqlite::params![path, fingerprint, mtime_ms, scanned_at])
+                .map_err(|e| format!("写入扫描缓存失败: {}", e))?;
+        }
+    }
+    tx.commit().map_err(|e| format!("提交事务失败: {}", e))
+}
+
+fn open(db_path: &Path) -> Result<rusqlite::Connection, String> {
+    rusqlite::Connection::open(db_path).map_err(|e| format!("打开数据库失败: {}", e))
+}
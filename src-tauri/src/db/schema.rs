@@ -5,10 +5,13 @@ use toasty::Model;
 pub struct Game {
     #[key]
     pub id: String,
+    #[unique]
     pub profile_key: String,
     pub title: String,
+    #[index]
     pub engine_type: String,
     #[column("path")]
+    #[index]
     pub game_path: String,
     #[unique]
     pub normalized_path: String,
@@ -16,16 +19,38 @@ pub struct Game {
     pub game_type: String,
     #[default(0)]
     pub detection_confidence: i32,
+    /// 引擎类型由用户手动指定，批量重新检测时不覆盖
+    #[default(false)]
+    pub engine_user_set: bool,
     pub runtime_version: Option<String>,
     pub cover_path: Option<String>,
     #[default(0)]
     pub play_count: i64,
+    #[default(0)]
+    pub total_play_ms: i64,
+    #[default(false)]
+    pub favorite: bool,
+    pub rating: Option<i32>,
+    #[default("未开始".to_string())]
+    pub status: String,
     pub metadata_json: Option<String>,
     pub created_at: i64,
     pub last_played_at: Option<i64>,
     pub updated_at: i64,
 }
 
+#[derive(Debug, Clone, Model)]
+#[table = "game_tags"]
+pub struct GameTag {
+    #[key]
+    pub id: String,
+    #[index]
+    pub game_id: String,
+    #[index]
+    pub tag: String,
+    pub display_tag: String,
+}
+
 #[derive(Debug, Clone, Model)]
 #[table = "engines"]
 pub struct Engine {
@@ -37,6 +62,10 @@ pub struct Engine {
     #[column("path")]
     pub engine_path: String,
     pub installed_at: i64,
+    /// 运行时构建的目标平台（如 `linux-x64`），未知时为空
+    pub target: Option<String>,
+    /// 安装目录占用空间（字节）
+    pub size_bytes: Option<i64>,
 }
 
 #[derive(Debug, Clone, Model)]
@@ -46,3 +75,13 @@ pub struct Setting {
     pub key: String,
     pub value: String,
 }
+
+#[derive(Debug, Clone, Model)]
+#[table = "scan_cache"]
+pub struct ScanCache {
+    #[key]
+    pub path: String,
+    pub fingerprint: String,
+    pub mtime_ms: i64,
+    pub scanned_at: i64,
+}
@@ -4,7 +4,7 @@
 //! 实际执行由 Rust 端的 trait 实现控制。
 
 use glob::Pattern;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::sync::OnceLock;
 
 // ─── 检测上下文 ──────────────────────────────────────────
@@ -91,10 +91,14 @@ pub trait ResourceContext {
 // ─── 默认实现（用于测试和开发） ──────────────────────────
 
 /// 基于真实文件系统的 DetectionContext 实现
+///
+/// NW.js 部署布局（根目录 package.json 的 `main` 为 `www/index.html`）中游戏内容位于子目录，
+/// `file_exists` / `dir_exists` 在根目录找不到时再到该内容目录中查找。
 pub struct FsDetectionContext {
     game_dir: PathBuf,
     direct_entries: OnceLock<Vec<PathBuf>>,
     recursive_names: OnceLock<Vec<(String, u32)>>,
+    content_dir: OnceLock<Option<PathBuf>>,
 }
 
 impl FsDetectionContext {
@@ -103,9 +107,32 @@ impl FsDetectionContext {
             game_dir,
             direct_entries: OnceLock::new(),
             recursive_names: OnceLock::new(),
+            content_dir: OnceLock::new(),
         }
     }
 
+    /// 根目录有 package.json 时的游戏内容目录：`main` 所在的子目录，`main` 在根目录时回退到 `www/`
+    fn content_dir(&self) -> Option<&Path> {
+        self.content_dir
+            .get_or_init(|| {
+                let content = std::fs::read_to_string(self.game_dir.join("package.json")).ok()?;
+                let main = serde_json::from_str::<serde_json::Value>(
+                    content.trim_start_matches('\u{feff}'),
+                )
+                .ok()
+                .and_then(|value| value.get("main")?.as_str().map(str::to_string))
+                .unwrap_or_default();
+                Path::new(main.trim().trim_start_matches("./"))
+                    .parent()
+                    .filter(|dir| !dir.as_os_str().is_empty())
+                    .filter(|dir| dir.components().all(|c| matches!(c, Component::Normal(_))))
+                    .map(|dir| self.game_dir.join(dir))
+                    .filter(|dir| dir.is_dir())
+                    .or_else(|| Some(self.game_dir.join("www")).filter(|dir| dir.is_dir()))
+            })
+            .as_deref()
+    }
+
     fn direct_entries(&self) -> &[PathBuf] {
         self.direct_entries.get_or_init(|| {
             std::fs::read_dir(&self.game_dir)
@@ -136,10 +163,16 @@ impl FsDetectionContext {
 impl DetectionContext for FsDetectionContext {
     fn file_exists(&self, relative_path: &str) -> bool {
         self.game_dir.join(relative_path).is_file()
+            || self
+                .content_dir()
+                .is_some_and(|dir| dir.join(relative_path).is_file())
     }
 
     fn dir_exists(&self, relative_path: &str) -> bool {
         self.game_dir.join(relative_path).is_dir()
+            || self
+                .content_dir()
+                .is_some_and(|dir| dir.join(relative_path).is_dir())
     }
 
     fn glob_match(&self, pattern: &str) -> bool {
@@ -181,3 +214,113 @@ pub fn simple_glob_match(pattern: &str, name: &str) -> bool {
     let name = name.to_lowercase();
     Pattern::new(&pattern).map_or(false, |p| p.matches(&name))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::registry::EngineRegistry;
+    use std::collections::HashMap;
+
+    /// 在临时目录中按 (相对路径, 内容) 创建文件
+    fn layout(files: &[(&str, &str)]) -> tempfile::TempDir {
+        let tmp = tempfile::tempdir().unwrap();
+        for (path, content) in files {
+            let path = tmp.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        tmp
+    }
+
+    /// 只加载 RPG Maker MV/MZ 插件，避免根目录 index.html 命中 HTML 等其他插件
+    fn rpgmaker_registry() -> (tempfile::TempDir, EngineRegistry) {
+        let config_dir = tempfile::tempdir().unwrap();
+        std::fs::copy(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("engines/rpgmakermv.toml"),
+            config_dir.path().join("rpgmakermv.toml"),
+        )
+        .unwrap();
+        let mut registry = EngineRegistry::new();
+        let warnings = registry.load(config_dir.path(), &HashMap::new());
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        (config_dir, registry)
+    }
+
+    fn assert_rpgmaker(files: &[(&str, &str)], core: &str) {
+        let (_config_dir, registry) = rpgmaker_registry();
+        let game = layout(files);
+        let ctx = FsDetectionContext::new(game.path().to_path_buf());
+        assert_eq!(registry.detect(&ctx).map(|(id, _)| id), Some("rpgmakermv"));
+        assert!(ctx.file_exists(core));
+        let other = if core == "js/rmmz_core.js" {
+            "js/rpg_core.js"
+        } else {
+            "js/rmmz_core.js"
+        };
+        assert!(!ctx.file_exists(other));
+    }
+
+    #[test]
+    fn detects_mz_in_root_www_layout() {
+        assert_rpgmaker(
+            &[
+                ("package.json", r#"{"main": "www/index.html"}"#),
+                ("www/index.html", ""),
+                ("www/js/rmmz_core.js", ""),
+                ("www/js/rmmz_managers.js", ""),
+                ("www/data/System.json", "{}"),
+            ],
+            "js/rmmz_core.js",
+        );
+    }
+
+    #[test]
+    fn detects_mv_in_root_www_layout() {
+        assert_rpgmaker(
+            &[
+                ("package.json", r#"{"main": "www/index.html"}"#),
+                ("www/index.html", ""),
+                ("www/js/rpg_core.js", ""),
+                ("www/js/rpg_managers.js", ""),
+                ("www/data/System.json", "{}"),
+            ],
+            "js/rpg_core.js",
+        );
+    }
+
+    #[test]
+    fn detects_mz_in_flat_layout() {
+        assert_rpgmaker(
+            &[
+                ("package.json", r#"{"main": "index.html"}"#),
+                ("index.html", ""),
+                ("js/rmmz_core.js", ""),
+                ("js/rmmz_managers.js", ""),
+                ("data/System.json", "{}"),
+            ],
+            "js/rmmz_core.js",
+        );
+    }
+
+    #[test]
+    fn detects_mv_in_flat_layout() {
+        assert_rpgmaker(
+            &[
+                ("package.json", r#"{"main": "index.html"}"#),
+                ("index.html", ""),
+                ("js/rpg_core.js", ""),
+                ("js/rpg_managers.js", ""),
+                ("data/System.json", "{}"),
+            ],
+            "js/rpg_core.js",
+        );
+    }
+
+    #[test]
+    fn ignores_www_without_root_package() {
+        let (_config_dir, registry) = rpgmaker_registry();
+        let game = layout(&[("www/js/rmmz_core.js", ""), ("www/data/System.json", "{}")]);
+        let ctx = FsDetectionContext::new(game.path().to_path_buf());
+        assert_eq!(registry.detect(&ctx), None);
+    }
+}
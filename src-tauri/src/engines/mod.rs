@@ -5,5 +5,8 @@ pub mod profile;
 pub mod registry;
 
 pub use detection::find_executable;
-pub use profile::{EngineDetailDto, EngineMetaDto, EngineProfileDetailDto};
+pub use profile::{
+    EngineCandidateDto, EngineDetailDto, EngineDetectionDto, EngineMarkerDto, EngineMetaDto,
+    EngineNearMissDto, EngineProfileDetailDto,
+};
 pub use registry::EngineRegistry;
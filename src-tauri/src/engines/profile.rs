@@ -265,6 +265,55 @@ impl From<&EngineProfile> for EngineMetaDto {
     }
 }
 
+/// 引擎检测结果（导入时供前端提示或让用户选择）
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineDetectionDto {
+    /// 最佳匹配的引擎 ID（未命中任何引擎时为空）
+    pub engine_type: Option<String>,
+    /// 最佳匹配的置信度（0-100）
+    pub confidence: i32,
+    /// 所有命中的引擎，按匹配程度从高到低排序
+    pub candidates: Vec<EngineCandidateDto>,
+}
+
+/// 单个候选引擎
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineCandidateDto {
+    pub engine_type: String,
+    pub name: String,
+    /// 置信度（0-100）
+    pub confidence: i32,
+    /// 加分项原始得分
+    pub score: i32,
+}
+
+/// 未识别目录时单个引擎的规则命中情况
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineNearMissDto {
+    pub engine_type: String,
+    pub name: String,
+    /// 加分项得分
+    pub score: i32,
+    /// 匹配所需的最低加分项得分
+    pub min_score: i32,
+    pub markers: Vec<EngineMarkerDto>,
+}
+
+/// 检测规则及其是否在目录中找到
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineMarkerDto {
+    /// `required` / `optional` / `forbidden`
+    pub group: &'static str,
+    pub rule_type: String,
+    /// 规则检查的路径、模式或扩展名
+    pub target: String,
+    pub found: bool,
+}
+
 /// 插件管理面板用的完整信息
 #[derive(Debug, Clone, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
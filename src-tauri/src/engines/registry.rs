@@ -5,8 +5,8 @@ use super::context::DetectionContext;
 use super::detection::{DetectionRule, build_rule, confidence_score, score_game};
 use super::launch::{LaunchStrategy, build_strategy};
 use super::profile::{
-    DetectionDetail, EngineDetailDto, EngineMetaDto, EngineProfile, EngineProfileDetailDto,
-    LaunchDetail, RuleDetail,
+    DetectionDetail, DetectionRuleDef, EngineDetailDto, EngineMarkerDto, EngineMetaDto,
+    EngineNearMissDto, EngineProfile, EngineProfileDetailDto, LaunchDetail, RuleDetail,
 };
 
 pub struct EngineEntry {
@@ -21,6 +21,17 @@ pub struct EngineEntry {
     pub errors: Vec<String>,
 }
 
+/// 单个引擎的检测结果
+pub struct DetectionCandidate<'a> {
+    pub id: &'a str,
+    pub name: &'a str,
+    /// 置信度（0-100）
+    pub confidence: i32,
+    /// 加分项原始得分
+    pub raw_score: i32,
+    pub priority: i32,
+}
+
 pub struct EngineRegistry {
     entries: HashMap<String, EngineEntry>,
 }
@@ -168,71 +179,113 @@ impl EngineRegistry {
     /// 返回 `(engine_id, confidence)`，confidence 为 0-100。
     /// 只检查 `enabled && valid` 的引擎。
     pub fn detect(&self, ctx: &dyn DetectionContext) -> Option<(&str, i32)> {
-        let mut best_specific: Option<(&str, i32, i32, i32)> = None;
-        let mut best_other: Option<(&str, i32, i32, i32)> = None;
+        self.candidates(ctx)
+            .into_iter()
+            .next()
+            .map(|c| (c.id, c.confidence))
+    }
 
-        for entry in self.entries.values() {
-            if !entry.enabled || !entry.valid {
-                continue;
-            }
+    /// 列出所有命中的引擎，按匹配程度从高到低排序（第一个即 `detect` 的结果）。
+    ///
+    /// 排序：具体引擎优先于 `other`，其后依次比较置信度、原始得分、优先级。
+    pub fn candidates(&self, ctx: &dyn DetectionContext) -> Vec<DetectionCandidate<'_>> {
+        let mut candidates: Vec<DetectionCandidate<'_>> = self
+            .entries
+            .values()
+            .filter_map(|entry| Self::evaluate(entry, ctx))
+            .collect();
+        candidates.sort_by(|a, b| {
+            (a.id == "other")
+                .cmp(&(b.id == "other"))
+                .then_with(|| b.confidence.cmp(&a.confidence))
+                .then_with(|| b.raw_score.cmp(&a.raw_score))
+                .then_with(|| a.priority.cmp(&b.priority))
+                .then_with(|| a.id.cmp(b.id))
+        });
+        candidates
+    }
 
-            if entry.required_rules.iter().any(|rule| !rule.evaluate(ctx))
-                || entry.forbidden_rules.iter().any(|rule| rule.evaluate(ctx))
-            {
-                continue;
-            }
+    /// 目录未命中任何引擎时的诊断：列出各引擎检查了哪些规则、哪些已找到。
+    ///
+    /// 必选项全部命中的引擎在前，其后按加分项得分从高到低排序；不含兜底的 `other`。
+    pub fn explain(&self, ctx: &dyn DetectionContext) -> Vec<EngineNearMissDto> {
+        let mut near_misses: Vec<(bool, EngineNearMissDto)> = self
+            .entries
+            .values()
+            .filter(|entry| entry.enabled && entry.valid && entry.profile.meta.id != "other")
+            .map(|entry| {
+                let detection = &entry.profile.detection;
+                let mut markers = Vec::new();
+                for (group, defs, rules) in [
+                    ("required", &detection.required, &entry.required_rules),
+                    ("optional", &detection.optional, &entry.optional_rules),
+                    ("forbidden", &detection.forbidden, &entry.forbidden_rules),
+                ] {
+                    markers.extend(defs.iter().zip(rules).map(|(def, rule)| EngineMarkerDto {
+                        group,
+                        rule_type: def.rule_type.clone(),
+                        target: rule_target(def),
+                        found: rule.evaluate(ctx),
+                    }));
+                }
+                let required_met = markers
+                    .iter()
+                    .filter(|marker| marker.group == "required")
+                    .all(|marker| marker.found);
+                let dto = EngineNearMissDto {
+                    engine_type: entry.profile.meta.id.clone(),
+                    name: entry.profile.meta.name.clone(),
+                    score: score_game(&entry.optional_rules, ctx),
+                    min_score: detection.min_score,
+                    markers,
+                };
+                (required_met, dto)
+            })
+            .collect();
+        near_misses.sort_by(|(a_met, a), (b_met, b)| {
+            b_met
+                .cmp(a_met)
+                .then_with(|| b.score.cmp(&a.score))
+                .then_with(|| a.engine_type.cmp(&b.engine_type))
+        });
+        near_misses.into_iter().map(|(_, dto)| dto).collect()
+    }
 
-            let raw_score = score_game(&entry.optional_rules, ctx);
-            let min_score = entry.profile.detection.min_score;
+    /// 对单个引擎求值；未启用、未通过校验或未命中时返回 `None`。
+    fn evaluate<'a>(
+        entry: &'a EngineEntry,
+        ctx: &dyn DetectionContext,
+    ) -> Option<DetectionCandidate<'a>> {
+        if !entry.enabled || !entry.valid {
+            return None;
+        }
 
-            if raw_score < min_score {
-                continue;
-            }
+        if entry.required_rules.iter().any(|rule| !rule.evaluate(ctx))
+            || entry.forbidden_rules.iter().any(|rule| rule.evaluate(ctx))
+        {
+            return None;
+        }
 
-            let priority = entry.profile.meta.priority;
-            let confidence = if entry.required_rules.is_empty() {
-                confidence_score(&entry.optional_rules, raw_score).max(1)
-            } else if entry.optional_rules.is_empty() {
-                100
-            } else {
-                60 + confidence_score(&entry.optional_rules, raw_score) * 40 / 100
-            };
-
-            let target = if entry.profile.meta.id == "other" {
-                &mut best_other
-            } else {
-                &mut best_specific
-            };
-            match *target {
-                None => {
-                    *target = Some((
-                        entry.profile.meta.id.as_str(),
-                        confidence,
-                        raw_score,
-                        priority,
-                    ))
-                }
-                Some((_, best_confidence, best_score, best_priority)) => {
-                    if confidence > best_confidence
-                        || (confidence == best_confidence && raw_score > best_score)
-                        || (confidence == best_confidence
-                            && raw_score == best_score
-                            && priority < best_priority)
-                    {
-                        *target = Some((
-                            entry.profile.meta.id.as_str(),
-                            confidence,
-                            raw_score,
-                            priority,
-                        ));
-                    }
-                }
-            }
+        let raw_score = score_game(&entry.optional_rules, ctx);
+        if raw_score < entry.profile.detection.min_score {
+            return None;
         }
 
-        best_specific
-            .or(best_other)
-            .map(|(id, confidence, _, _)| (id, confidence))
+        let confidence = if entry.required_rules.is_empty() {
+            confidence_score(&entry.optional_rules, raw_score).max(1)
+        } else if entry.optional_rules.is_empty() {
+            100
+        } else {
+            60 + confidence_score(&entry.optional_rules, raw_score) * 40 / 100
+        };
+
+        Some(DetectionCandidate {
+            id: entry.profile.meta.id.as_str(),
+            name: entry.profile.meta.name.as_str(),
+            confidence,
+            raw_score,
+            priority: entry.profile.meta.priority,
+        })
     }
 
     /// 获取前端的引擎摘要列表。
@@ -378,3 +431,12 @@ impl Default for EngineRegistry {
         Self::new()
     }
 }
+
+/// 规则检查的对象：路径、glob 模式或扩展名
+fn rule_target(def: &DetectionRuleDef) -> String {
+    [&def.path, &def.pattern, &def.ext]
+        .into_iter()
+        .find(|value| !value.is_empty())
+        .cloned()
+        .unwrap_or_default()
+}
@@ -35,8 +35,19 @@ async fn init_database(app: &tauri::AppHandle) -> Result<toasty::Db, String> {
         .app_data_dir()
         .map_err(|e| format!("获取应用数据目录失败: {}", e))?;
 
-    let db_path = app_data_dir.join("db").join("app.sqlite");
-    crate::db::init_db(&db_path).await
+    let db_path = crate::db::database_path(&app_data_dir);
+    let db = crate::db::init_db(&db_path).await?;
+
+    // 每次启动保留一份快照，防止异常退出后游戏库丢失
+    if let Err(e) = crate::db::backup::create_timestamped_backup(
+        &db_path,
+        &crate::db::backups_dir(&app_data_dir),
+        crate::db::backup::KEEP_BACKUPS,
+    ) {
+        tracing::warn!(error = %e, "启动时备份数据库失败");
+    }
+
+    Ok(db)
 }
 
 /// 获取默认容器根目录
@@ -62,9 +73,68 @@ async fn resolve_container_root(
 /// 应用程序入口
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    tauri::Builder::default()
+    let builder = tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
-        .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_dialog::init());
+    // 全局快捷键插件仅支持桌面平台
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    let builder = builder.plugin(
+        tauri_plugin_global_shortcut::Builder::new()
+            .with_handler(|app, _shortcut, event| {
+                if event.state() == tauri_plugin_global_shortcut::ShortcutState::Pressed {
+                    services::window_state::toggle_main_window(app);
+                }
+            })
+            .build(),
+    );
+    builder
+        .register_asynchronous_uri_scheme_protocol(
+            services::cover_protocol::COVER_SCHEME,
+            |ctx, request, responder| {
+                let app = ctx.app_handle().clone();
+                let path = request.uri().path().to_string();
+                tauri::async_runtime::spawn(async move {
+                    let Some(state) = app.try_state::<commands::state::AppState>() else {
+                        responder.respond(services::cover_protocol::error_response(
+                            tauri::http::StatusCode::SERVICE_UNAVAILABLE,
+                        ));
+                        return;
+                    };
+                    let root = state.container_root_path().await;
+                    let response = tauri::async_runtime::spawn_blocking(move || {
+                        services::cover_protocol::respond(&root, &path)
+                    })
+                    .await
+                    .unwrap_or_else(|_| {
+                        services::cover_protocol::error_response(
+                            tauri::http::StatusCode::INTERNAL_SERVER_ERROR,
+                        )
+                    });
+                    responder.respond(response);
+                });
+            },
+        )
+        .on_window_event(|window, event| {
+            if window.label() != services::window_state::MAIN_WINDOW {
+                return;
+            }
+            match event {
+                tauri::WindowEvent::CloseRequested { api, .. } => {
+                    api.prevent_close();
+                    if services::window_state::close_to_tray(window) {
+                        let _ = services::window_state::save(window);
+                        let _ = window.hide();
+                    } else {
+                        // 窗口状态在后台写入，完成后再退出
+                        services::window_state::save_and_exit(window.app_handle());
+                    }
+                }
+                tauri::WindowEvent::Resized(_) | tauri::WindowEvent::Moved(_) => {
+                    services::window_state::schedule_save(window);
+                }
+                _ => {}
+            }
+        })
         .setup(|app| {
             // 初始化日志系统（最先执行）
             if let Err(e) = init_logger(app.handle()) {
@@ -83,6 +153,12 @@ pub fn run() {
 
             tracing::info!("数据库初始化完成");
 
+            let db_path = crate::db::database_path(
+                &app.path()
+                    .app_data_dir()
+                    .expect("获取应用数据目录失败"),
+            );
+
             // 解析容器根目录
             let db2 = db.clone();
             let handle2 = app.handle().clone();
@@ -94,16 +170,50 @@ pub fn run() {
 
             tracing::debug!(container_root = %container_root.display(), "容器根目录");
 
-            // 迁移profile目录命名
+            // 迁移profile目录命名（仅首次启动，之后可通过 migrate_profiles 手动执行）
             let db3 = db.clone();
             let migrate_root = container_root.clone();
+            let migrate_db_path = db_path.clone();
             tauri::async_runtime::block_on(async move {
-                let service = crate::services::GameService::new(db3);
-                let _ = service.migrate_profile_keys(&migrate_root).await;
+                let service = crate::services::GameService::new(db3.clone(), migrate_db_path);
+                // 数据库迁移重命名的重复 profile_key 沿用原先共用目录的副本
+                match service.copy_renamed_profiles(&migrate_root).await {
+                    Ok(0) => {}
+                    Ok(count) => tracing::info!(count, "已复制重命名游戏的 profile 目录"),
+                    Err(e) => tracing::warn!(error = %e, "复制重命名游戏的 profile 目录失败"),
+                }
+
+                let migrated = {
+                    let mut db_lock = db3.lock().await;
+                    crate::db::get_setting(
+                        &mut *db_lock,
+                        crate::models::SETTING_PROFILE_KEYS_MIGRATED,
+                    )
+                    .await
+                    .ok()
+                    .flatten()
+                    .is_some_and(|v| v == "1")
+                };
+                if migrated {
+                    return;
+                }
+                match service.migrate_profile_keys(&migrate_root).await {
+                    Ok(count) => {
+                        tracing::info!(count, "profile 目录迁移完成");
+                        let mut db_lock = db3.lock().await;
+                        let _ = crate::db::set_setting(
+                            &mut *db_lock,
+                            crate::models::SETTING_PROFILE_KEYS_MIGRATED,
+                            "1",
+                        )
+                        .await;
+                    }
+                    Err(e) => tracing::warn!(error = %e, "profile 目录迁移失败"),
+                }
             });
 
             // 创建服务
-            let game_service = crate::services::GameService::new(db.clone());
+            let game_service = crate::services::GameService::new(db.clone(), db_path.clone());
             let engine_service = crate::services::EngineService::new(db.clone());
             let launcher_service = crate::services::LauncherService::new();
 
@@ -156,6 +266,15 @@ pub fn run() {
             };
 
             // 管理状态
+            let close_to_tray = tauri::async_runtime::block_on(async {
+                let mut db_lock = db.lock().await;
+                crate::db::get_setting(&mut *db_lock, crate::models::SETTING_CLOSE_TO_TRAY)
+                    .await
+                    .ok()
+                    .flatten()
+                    .is_some_and(|v| v == "1")
+            });
+
             app.manage(commands::state::AppState {
                 game_service: Arc::new(Mutex::new(game_service)),
                 engine_service: Arc::new(Mutex::new(crate::services::EngineService::new(
@@ -166,15 +285,31 @@ pub fn run() {
                 container_root: Arc::new(Mutex::new(container_root.to_string_lossy().to_string())),
                 engine_registry,
                 config_cache: Arc::new(std::sync::Mutex::new(std::collections::HashMap::new())),
+                running_games: Arc::new(std::sync::Mutex::new(std::collections::HashMap::new())),
+                launching_games: Default::default(),
+                launch_slots: Default::default(),
+                close_to_tray: close_to_tray.into(),
             });
 
+            app.manage(crate::services::download::manager::DownloadManager::new());
+
+            if let Some(window) = app.get_webview_window(services::window_state::MAIN_WINDOW) {
+                services::window_state::restore(&window, &db);
+            }
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            services::global_shortcut::register_saved(app.handle(), &db);
+
             app.manage(commands::engine::EngineState {
                 engine_service: Arc::new(Mutex::new(engine_service)),
+                db: db.clone(),
             });
 
             app.manage(commands::settings::SettingsState {
                 db: db.clone(),
-                game_service: Arc::new(Mutex::new(crate::services::GameService::new(db.clone()))),
+                game_service: Arc::new(Mutex::new(crate::services::GameService::new(
+                    db.clone(),
+                    db_path.clone(),
+                ))),
                 engine_service: Arc::new(Mutex::new(crate::services::EngineService::new(
                     db.clone(),
                 ))),
@@ -195,40 +330,108 @@ pub fn run() {
             // 游戏相关命令
             commands::get_games,
             commands::get_game,
+            commands::get_game_by_path,
+            commands::search_games,
             commands::add_game,
             commands::update_game,
+            commands::relocate_game,
+            commands::find_relocated,
             commands::delete_game,
             commands::remove_all_games,
+            commands::delete_games,
             commands::launch_game,
+            commands::backup_save,
+            commands::list_save_backups,
+            commands::restore_save,
+            commands::stop_game,
+            commands::get_running_games,
+            commands::set_game_favorite,
+            commands::set_game_rating,
+            commands::set_game_runtime,
+            commands::set_game_status,
+            commands::list_games_sorted,
+            commands::set_game_tags,
+            commands::list_games_by_tag,
             commands::import_game_dir,
+            commands::import_game_archive,
+            commands::import_game_dirs,
             commands::scan_games,
             commands::get_game_settings,
             commands::save_game_settings,
+            commands::list_executables,
+            commands::set_game_entry,
             commands::refresh_game_cover,
+            commands::set_cover_from_url,
+            commands::capture_window_cover,
             commands::get_game_profile_dir,
             commands::open_path,
+            commands::open_game_folder,
+            commands::open_game_save_folder,
+            commands::get_game_disk_usage,
+            commands::export_library,
+            commands::import_library,
+            commands::deduplicate_games,
             // 引擎相关命令
             commands::get_engines,
             commands::find_engine,
             commands::add_engine,
             commands::delete_engine,
             commands::get_engine_update_info,
+            commands::check_all_engine_updates,
             commands::update_engine,
             commands::get_engine_registry,
             commands::get_engine_registry_detail,
+            commands::detect_engine,
+            commands::redetect_engines,
             commands::set_engine_enabled,
             commands::get_engine_profile_detail,
             // 设置相关命令
             commands::get_app_settings,
             commands::set_container_root,
             commands::get_platform,
+            commands::get_diagnostics,
             commands::get_system_theme,
             commands::get_capabilities,
             commands::get_integration_status,
+            commands::list_proton_versions,
+            commands::create_bottle,
             commands::set_integration_settings,
             commands::get_nwjs_stable_info,
+            commands::get_nwjs_versions,
+            commands::list_installed_runtimes,
+            commands::verify_runtime,
             commands::download_nwjs_stable,
+            commands::cancel_nwjs_download,
+            commands::list_download_tasks,
+            commands::download_nwjs_version,
+            commands::delete_nwjs_runtime,
+            commands::download_nwjs_ffmpeg,
+            commands::get_nwjs_mirror,
+            commands::get_external_tools,
+            commands::set_external_tools,
+            commands::get_allow_hooks,
+            commands::set_allow_hooks,
+            commands::get_close_to_tray,
+            commands::set_close_to_tray,
+            commands::get_minimize_on_launch,
+            commands::set_minimize_on_launch,
+            commands::get_launch_in_terminal,
+            commands::set_launch_in_terminal,
+            commands::get_launch_limit,
+            commands::set_launch_limit,
+            commands::get_global_shortcut,
+            commands::set_global_shortcut,
+            commands::get_scan_ignores,
+            commands::set_scan_ignores,
+            commands::get_engine_defaults,
+            commands::set_engine_defaults,
+            commands::set_nwjs_mirror,
+            commands::get_nwjs_default_flavor,
+            commands::set_nwjs_default_flavor,
             commands::cleanup_unused_containers,
+            commands::migrate_profiles,
+            commands::backup_database,
+            commands::restore_database,
             commands::import_mkxpz_archive,
         ])
         .run(tauri::generate_context!())
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// 游戏数据传输对象（用于前端展示）
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,10 +21,22 @@ pub struct GameDto {
     pub path_valid: bool,
     /// 运行时版本
     pub runtime_version: Option<String>,
-    /// 封面路径
+    /// 封面 URL（`gmcover://` 协议）
     pub cover_path: Option<String>,
+    /// 封面缩略图 URL（用于网格视图，详情页使用完整封面）
+    pub cover_thumb_path: Option<String>,
     /// 游玩次数
     pub play_count: i64,
+    /// 累计游玩时长（毫秒）
+    pub total_play_ms: i64,
+    /// 标签（显示形式）
+    pub tags: Vec<String>,
+    /// 是否收藏
+    pub favorite: bool,
+    /// 评分（0-5，未评分为空）
+    pub rating: Option<i32>,
+    /// 完成状态（未开始/进行中/已通关/弃坑）
+    pub status: String,
     /// 创建时间
     pub created_at: i64,
     /// 最后游玩时间
@@ -52,6 +65,28 @@ pub struct AddGameInput {
     pub runtime_version: Option<String>,
 }
 
+/// 游戏列表排序字段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GameSortBy {
+    /// 评分
+    Rating,
+    /// 标题
+    Title,
+    /// 最后游玩时间
+    LastPlayed,
+    /// 添加时间
+    CreatedAt,
+}
+
+/// 排序方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
 /// 导入游戏目录输入
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -62,8 +97,19 @@ pub struct ImportGameInput {
     pub engine_type: String,
 }
 
+/// 批量导入游戏目录结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportGameDirsResult {
+    pub imported: Vec<GameDto>,
+    /// 已在库中而跳过的路径
+    pub skipped: Vec<String>,
+    /// 导入失败的路径及原因
+    pub failed: Vec<(String, String)>,
+}
+
 /// 更新游戏输入
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateGameInput {
     /// 游戏标题
@@ -98,6 +144,10 @@ pub struct EngineDto {
     pub path: String,
     /// 安装时间
     pub installed_at: i64,
+    /// 运行时构建的目标平台（如 `linux-x64`）
+    pub target: Option<String>,
+    /// 安装目录占用空间（字节）
+    pub size_bytes: Option<u64>,
 }
 
 /// 引擎更新检测信息
@@ -127,6 +177,11 @@ pub struct EngineUpdateResult {
 pub struct LaunchResult {
     /// 进程ID
     pub pid: u32,
+    /// NW.js 远程调试端口（开启 devtools 且使用 SDK 版时），可访问 `http://localhost:<port>`
+    pub devtools_port: Option<u16>,
+    /// 能否跟踪游戏进程；在终端窗口中启动时为 false，`pid` 为终端进程，
+    /// 其退出不代表游戏结束，也不记录游玩时长
+    pub tracked: bool,
 }
 
 /// 设置容器根目录输入
@@ -145,6 +200,29 @@ pub struct ScanGamesInput {
     pub root: String,
     /// 最大扫描深度
     pub max_depth: u32,
+    /// 仅检测并返回候选列表，不导入
+    #[serde(default)]
+    pub dry_run: bool,
+    /// 不跟随符号链接进入目录
+    #[serde(default)]
+    pub skip_symlinks: bool,
+    /// 增量扫描：跳过修改时间自上次扫描以来未变化的顶层目录
+    #[serde(default)]
+    pub incremental: bool,
+}
+
+/// 扫描识别出的候选游戏
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanCandidate {
+    /// 游戏目录
+    pub path: String,
+    /// 识别出的引擎类型
+    pub engine_type: String,
+    /// 检测置信度
+    pub confidence: i32,
+    /// 是否已在库中
+    pub existing: bool,
 }
 
 /// 扫描结果
@@ -159,6 +237,77 @@ pub struct ScanGamesResult {
     pub imported: u32,
     /// 已存在数量
     pub skipped_existing: u32,
+    /// 增量扫描中因未变化而跳过的顶层目录数
+    pub skipped_unchanged: u32,
+    /// 按引擎类型统计的发现游戏数
+    pub by_engine: HashMap<String, u32>,
+    /// 识别出的全部候选游戏（按扫描顺序）
+    pub candidates: Vec<ScanCandidate>,
+}
+
+/// 游戏存档备份信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveBackupInfo {
+    /// 压缩包绝对路径
+    pub path: String,
+    pub file_name: String,
+    /// 文件大小（字节）
+    pub size: u64,
+    /// 备份时间（Unix 毫秒）
+    pub created_at: i64,
+}
+
+/// 游戏磁盘占用（字节）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameDiskUsage {
+    /// 游戏目录
+    pub game_bytes: u64,
+    /// profile 目录（存档、配置、Wine 前缀等）
+    pub profile_bytes: u64,
+}
+
+/// 游戏设置中校验失败的字段
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsFieldError {
+    /// 字段名（与前端 GameConfig 字段一致，如 `entryPath`、`args[2]`）
+    pub field: String,
+    pub message: String,
+}
+
+/// 保存游戏设置结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveGameSettingsResult {
+    /// 已保存但需要提醒用户的设置（如关闭了主目录沙盒）
+    pub warnings: Vec<SettingsFieldError>,
+}
+
+/// 重新检测后引擎类型发生变化的游戏
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineChange {
+    pub game_id: String,
+    pub title: String,
+    pub old_engine_type: String,
+    pub new_engine_type: String,
+    /// 新检测结果的置信度（0-100）
+    pub confidence: i32,
+}
+
+/// 批量重新检测引擎结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RedetectEnginesResult {
+    /// 参与检测的游戏数
+    pub checked: u32,
+    /// 路径已不存在而跳过的游戏数
+    pub skipped_missing: u32,
+    /// 引擎类型由用户手动指定而跳过的游戏数
+    pub skipped_user_set: u32,
+    pub changed: Vec<EngineChange>,
 }
 
 /// 清理容器结果
@@ -168,3 +317,19 @@ pub struct CleanupResult {
     /// 删除数量
     pub deleted: u32,
 }
+
+/// 运行环境诊断信息（用于“关于/诊断”页面与问题反馈）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticsInfo {
+    pub os: String,
+    pub arch: String,
+    pub app_version: String,
+    pub container_root: String,
+    pub db_path: String,
+    /// engines 表中登记的运行时
+    pub installed_runtimes: Vec<EngineDto>,
+    pub bottles_installed: bool,
+    /// `wine --version` 的输出，未安装或执行失败时为空
+    pub wine_version: Option<String>,
+}
@@ -1,9 +1,14 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
 
 /// 游戏引擎类型
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum EngineType {
+    /// RPG Maker 2000 / 2003
+    RpgMaker2000,
+    /// RPG Maker XP（RGSS1）
+    RpgMakerXP,
     /// RPG Maker VX
     RpgMakerVX,
     /// RPG Maker VX Ace
@@ -12,8 +17,14 @@ pub enum EngineType {
     RpgMakerMV,
     /// RPG Maker MZ
     RpgMakerMZ,
+    /// WOLF RPG エディター
+    WolfRpg,
     /// RenPy视觉小说
     RenPy,
+    /// TyranoScript / TyranoBuilder 视觉小说（NW.js）
+    Tyrano,
+    /// 吉里吉里（KiriKiri 2 / Z）视觉小说，数据打包为 .xp3
+    KiriKiri,
     /// Unity 游戏引擎
     Unity,
     /// Godot 游戏引擎
@@ -33,10 +44,17 @@ impl EngineType {
     // one alias. The manual match gives full control over alternate names.
     pub fn from_str(s: &str) -> Self {
         match s.to_lowercase().as_str() {
+            "rpgmaker2000" | "rpg_maker_2000" | "rpgmaker2003" | "rpg_maker_2003" => {
+                Self::RpgMaker2000
+            }
+            "rpgmakerxp" | "rpg_maker_xp" => Self::RpgMakerXP,
             "rpgmakervx" | "rpg_maker_vx" => Self::RpgMakerVX,
             "rpgmakervxace" | "rpg_maker_vx_ace" => Self::RpgMakerVXAce,
             "rpgmakermv" | "rpg_maker_mv" | "rpgmakermz" | "rpg_maker_mz" => Self::RpgMakerMV,
+            "wolfrpg" | "wolf_rpg" => Self::WolfRpg,
             "renpy" => Self::RenPy,
+            "tyrano" | "tyranoscript" | "tyranobuilder" => Self::Tyrano,
+            "kirikiri" | "krkr" | "krkrz" => Self::KiriKiri,
             "unity" => Self::Unity,
             "godot" => Self::Godot,
             "html" => Self::Html,
@@ -47,11 +65,16 @@ impl EngineType {
     /// 转换为字符串
     pub fn as_str(&self) -> &'static str {
         match self {
+            Self::RpgMaker2000 => "rpgmaker2000",
+            Self::RpgMakerXP => "rpgmakerxp",
             Self::RpgMakerVX => "rpgmakervx",
             Self::RpgMakerVXAce => "rpgmakervxace",
             Self::RpgMakerMV => "rpgmakermv",
             Self::RpgMakerMZ => "rpgmakermz",
+            Self::WolfRpg => "wolfrpg",
             Self::RenPy => "renpy",
+            Self::Tyrano => "tyrano",
+            Self::KiriKiri => "kirikiri",
             Self::Unity => "unity",
             Self::Godot => "godot",
             Self::Html => "html",
@@ -60,6 +83,129 @@ impl EngineType {
     }
 }
 
+/// 游戏完成状态（数据库中按中文文本保存）
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum GameStatus {
+    #[serde(rename = "未开始")]
+    NotStarted,
+    #[serde(rename = "进行中")]
+    InProgress,
+    #[serde(rename = "已通关")]
+    Completed,
+    #[serde(rename = "弃坑")]
+    Dropped,
+}
+
+impl GameStatus {
+    /// 从字符串解析状态，未知值返回错误
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.trim() {
+            "未开始" => Ok(Self::NotStarted),
+            "进行中" => Ok(Self::InProgress),
+            "已通关" => Ok(Self::Completed),
+            "弃坑" => Ok(Self::Dropped),
+            other => Err(format!(
+                "未知的游戏状态: {}（可选：未开始、进行中、已通关、弃坑）",
+                other
+            )),
+        }
+    }
+
+    /// 转换为字符串
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::NotStarted => "未开始",
+            Self::InProgress => "进行中",
+            Self::Completed => "已通关",
+            Self::Dropped => "弃坑",
+        }
+    }
+}
+
+/// 主目录沙盒模式
+///
+/// 配置中以 `true` / `false` 表示完全隔离 / 不隔离，中间模式写作 `"xdg-only"`。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SandboxHome {
+    /// 重定向 HOME 与 XDG 目录
+    #[default]
+    Full,
+    /// 只重定向 XDG 目录，保留真实 HOME（适用于写死 `$HOME/.renpy` 等路径的游戏）
+    XdgOnly,
+    /// 不隔离，游戏直接读写真实的用户目录
+    Off,
+}
+
+/// XDG 基础目录变量及其相对沙盒目录的位置
+pub const XDG_DIR_VARS: [(&str, &str); 4] = [
+    ("XDG_CONFIG_HOME", ".config"),
+    ("XDG_DATA_HOME", ".local/share"),
+    ("XDG_CACHE_HOME", ".cache"),
+    ("XDG_STATE_HOME", ".local/state"),
+];
+
+impl SandboxHome {
+    pub fn redirects_home(self) -> bool {
+        self == Self::Full
+    }
+
+    pub fn redirects_xdg(self) -> bool {
+        self != Self::Off
+    }
+
+    /// 该模式下不会重定向的环境变量
+    pub fn unredirected_vars(self) -> Vec<&'static str> {
+        let mut vars = Vec::new();
+        if !self.redirects_home() {
+            vars.push("HOME");
+        }
+        if !self.redirects_xdg() {
+            vars.extend(XDG_DIR_VARS.iter().map(|(var, _)| *var));
+        }
+        vars
+    }
+}
+
+impl From<bool> for SandboxHome {
+    fn from(enabled: bool) -> Self {
+        if enabled { Self::Full } else { Self::Off }
+    }
+}
+
+impl Serialize for SandboxHome {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Full => serializer.serialize_bool(true),
+            Self::Off => serializer.serialize_bool(false),
+            Self::XdgOnly => serializer.serialize_str("xdg-only"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for SandboxHome {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Bool(bool),
+            Mode(String),
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Bool(enabled) => Ok(enabled.into()),
+            Repr::Mode(mode) => match mode.as_str() {
+                "full" => Ok(Self::Full),
+                "xdg-only" => Ok(Self::XdgOnly),
+                "off" => Ok(Self::Off),
+                other => Err(serde::de::Error::custom(format!(
+                    "未知的沙盒模式: {}",
+                    other
+                ))),
+            },
+        }
+    }
+}
+
 /// 游戏配置文件（TOML格式）
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -70,28 +216,69 @@ pub struct GameConfig {
     pub entry_path: String,
     /// 运行时版本
     pub runtime_version: Option<String>,
-    /// 启动运行器：auto | native | nwjs | mkxpz | bottles。
+    /// 启动运行器：auto | native | nwjs | mkxpz | bottles | proton。
     #[serde(default = "default_runner")]
     pub runner: String,
     /// 启动参数
     #[serde(default)]
     pub args: Vec<String>,
-    /// 是否启用沙盒主目录
-    #[serde(default = "default_true")]
-    pub sandbox_home: bool,
+    /// 额外环境变量，在沙盒环境之后设置（可覆盖 HOME）；值按字面传递，不做 shell 展开
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// 主目录沙盒模式
+    #[serde(default)]
+    pub sandbox_home: SandboxHome,
     /// 是否使用 Bottles
     #[serde(default)]
     pub use_bottles: bool,
-    /// Bottles bottle 名称
+    /// Bottles bottle 名称，为空时使用全局默认 bottle
     #[serde(default)]
     pub bottle_name: Option<String>,
+    /// 通过 Proton 启动时使用的版本（目录名），为空时使用检测到的最新版本
+    #[serde(default)]
+    pub proton_version: Option<String>,
     /// 封面图片文件名
     #[serde(default)]
     pub cover_file: Option<String>,
-}
-
-fn default_true() -> bool {
-    true
+    /// Wine 前缀目录（为空时使用 profile 下的 `Wine Prefix`）
+    #[serde(default)]
+    pub wine_prefix: Option<String>,
+    /// 允许同时运行多个实例（默认禁止，避免多个进程争用同一存档目录）
+    #[serde(default)]
+    pub allow_multiple: bool,
+    /// 强制区域设置（如 `ja_JP.UTF-8`），启动时写入 `LANG`/`LC_ALL`/`LC_CTYPE`；
+    /// 该 locale 需已在宿主系统中安装
+    #[serde(default)]
+    pub locale: Option<String>,
+    /// Linux 上通过 `gamemoderun` 启动（需已安装 Feral GameMode）
+    #[serde(default)]
+    pub use_gamemode: bool,
+    /// Linux 上启用 MangoHud 性能叠加层（需已安装 MangoHud）
+    #[serde(default)]
+    pub use_mangohud: bool,
+    /// 启动前执行的 shell 命令（工作目录为游戏目录），非零退出码时中止启动
+    #[serde(default)]
+    pub pre_launch: Option<String>,
+    /// 游戏退出后执行的 shell 命令
+    #[serde(default)]
+    pub post_exit: Option<String>,
+    /// NW.js flavor（`normal` / `sdk`），为空时使用全局默认；所选 flavor 未安装时回退到普通版
+    #[serde(default)]
+    pub flavor: Option<String>,
+    /// 启动时开启 NW.js 远程调试（devtools），需要 SDK 版运行时
+    #[serde(default)]
+    pub devtools: bool,
+    /// 游戏目录自带 NW.js 运行时（导入时检测）；未固定运行时版本时优先使用自带运行时，
+    /// 未下载 NW.js 也可启动
+    #[serde(default)]
+    pub self_contained: bool,
+    /// 启动后最小化主窗口、退出后恢复；为空时使用全局设置
+    #[serde(default)]
+    pub minimize_on_launch: Option<bool>,
+    /// 在终端窗口中启动游戏以实时查看输出；为空时使用全局设置。
+    /// 此时只能跟踪终端进程，不记录游玩时长，也不执行退出后钩子
+    #[serde(default)]
+    pub launch_in_terminal: Option<bool>,
 }
 
 fn default_runner() -> String {
@@ -106,10 +293,24 @@ impl Default for GameConfig {
             runtime_version: None,
             runner: default_runner(),
             args: Vec::new(),
-            sandbox_home: true,
+            env: HashMap::new(),
+            sandbox_home: SandboxHome::Full,
             use_bottles: false,
             bottle_name: None,
+            proton_version: None,
             cover_file: None,
+            wine_prefix: None,
+            allow_multiple: false,
+            locale: None,
+            use_gamemode: false,
+            use_mangohud: false,
+            pre_launch: None,
+            post_exit: None,
+            flavor: None,
+            devtools: false,
+            self_contained: false,
+            minimize_on_launch: None,
+            launch_in_terminal: None,
         }
     }
 }
@@ -0,0 +1,75 @@
+use super::GameConfig;
+use serde::{Deserialize, Serialize};
+
+/// 游戏库导出清单版本，结构不兼容时递增
+pub const LIBRARY_MANIFEST_VERSION: u32 = 1;
+
+/// 游戏库导出清单（JSON）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryManifest {
+    /// 清单版本
+    pub version: u32,
+    /// 导出时间（Unix 毫秒）
+    pub exported_at: i64,
+    /// 游戏条目
+    pub games: Vec<LibraryEntry>,
+}
+
+/// 游戏库导入结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportLibraryResult {
+    /// 新增的游戏数
+    pub added: u32,
+    /// 路径已存在而跳过的游戏数
+    pub skipped: u32,
+    /// 导入失败的游戏数
+    pub failed: u32,
+}
+
+/// 重复游戏合并结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeduplicateResult {
+    /// 存在重复的游戏组数
+    pub groups: u32,
+    /// 被合并删除的重复记录数
+    pub merged: u32,
+    /// 清理的重复 profile 目录数
+    pub profiles_removed: u32,
+    /// 删除重复 profile 前创建的存档备份（位于保留记录的备份目录）
+    pub save_backups: Vec<String>,
+}
+
+/// 清单中的单个游戏
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryEntry {
+    pub title: String,
+    pub engine_type: String,
+    /// 游戏目录
+    pub path: String,
+    pub game_type: String,
+    pub detection_confidence: i32,
+    pub runtime_version: Option<String>,
+    /// 封面文件名（位于 profile 目录）
+    pub cover_file: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub favorite: bool,
+    #[serde(default)]
+    pub rating: Option<i32>,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub play_count: i64,
+    #[serde(default)]
+    pub total_play_ms: i64,
+    pub last_played_at: Option<i64>,
+    pub created_at: i64,
+    pub metadata_json: Option<String>,
+    /// 游戏设置（settings.toml）
+    pub config: Option<GameConfig>,
+}
@@ -3,8 +3,10 @@
 
 pub mod dto;
 pub mod game;
+pub mod library;
 pub mod settings;
 
 pub use dto::*;
-pub use game::{EngineType, GameConfig};
+pub use game::{EngineType, GameConfig, GameStatus, SandboxHome};
+pub use library::*;
 pub use settings::*;
@@ -4,6 +4,36 @@ use serde::{Deserialize, Serialize};
 pub const SETTING_CONTAINER_ROOT: &str = "container_root";
 pub const SETTING_BOTTLES_DEFAULT: &str = "bottles_default";
 pub const SETTING_BOTTLES_ENABLED: &str = "bottles_enabled";
+pub const SETTING_WINE_BINARY: &str = "wine_binary";
+pub const SETTING_NWJS_MIRROR: &str = "nwjs_mirror";
+/// 启动游戏时默认使用的 NW.js flavor（`normal` / `sdk`）
+pub const SETTING_NWJS_DEFAULT_FLAVOR: &str = "nwjs_default_flavor";
+pub const SETTING_EASYRPG_BINARY: &str = "easyrpg_binary";
+/// 是否允许执行游戏配置中的启动前/退出后钩子命令（"1" 为允许）
+pub const SETTING_ALLOW_HOOKS: &str = "allow_launch_hooks";
+/// 用户自定义的扫描忽略规则（JSON 字符串数组，支持 `*`/`?` 通配符）
+pub const SETTING_SCAN_IGNORES: &str = "scan_ignores";
+/// 按引擎类型的默认启动参数，键为前缀 + 引擎类型（JSON 字符串数组）
+pub const SETTING_ENGINE_DEFAULTS_PREFIX: &str = "engine_defaults_";
+/// 关闭主窗口时隐藏到托盘而不是退出（"1" 为启用，默认退出）
+pub const SETTING_CLOSE_TO_TRAY: &str = "close_to_tray";
+/// 启动游戏后最小化主窗口、游戏退出后恢复（"1" 为启用，可被游戏配置覆盖）
+pub const SETTING_MINIMIZE_ON_LAUNCH: &str = "minimize_on_launch";
+/// 在终端窗口中启动游戏以实时查看输出（"1" 为启用，可被游戏配置覆盖）
+pub const SETTING_LAUNCH_IN_TERMINAL: &str = "launch_in_terminal";
+/// 同时运行的游戏数上限（"0" 或未设置为不限制）
+pub const SETTING_LAUNCH_LIMIT: &str = "launch_limit";
+/// 达到上限时排队等待空闲名额而不是拒绝启动（"1" 为排队）
+pub const SETTING_LAUNCH_QUEUE: &str = "launch_queue";
+/// 显示/隐藏主窗口的全局快捷键（如 `CommandOrControl+Shift+G`，为空表示未启用）
+pub const SETTING_GLOBAL_SHORTCUT: &str = "global_shortcut";
+/// 已在启动时把 UUID 形式的 profile 目录迁移为可读名称（"1"），之后启动不再迁移
+pub const SETTING_PROFILE_KEYS_MIGRATED: &str = "profile_keys_migrated";
+/// 数据库迁移 7 为区分重复 profile_key 而重命名的记录（JSON `[[原 key, 新 key], ...]`），
+/// 启动时为新 key 复制原 profile 目录后清空
+pub const SETTING_RENAMED_PROFILE_KEYS: &str = "renamed_profile_keys";
+/// 主窗口状态（JSON，见 [`WindowState`]）
+pub const SETTING_WINDOW_STATE: &str = "window_state";
 
 /// 应用全局设置
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,6 +43,37 @@ pub struct AppSettings {
     pub container_root: String,
 }
 
+/// 主窗口状态（物理像素），退出时保存、启动时恢复
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowState {
+    pub width: u32,
+    pub height: u32,
+    pub x: i32,
+    pub y: i32,
+    pub maximized: bool,
+}
+
+/// 外部工具路径（可以是绝对路径或 PATH 中的程序名，为空表示未配置）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalToolPaths {
+    /// wine 路径，为空时从 PATH 查找 `wine`
+    pub wine_binary: Option<String>,
+    /// EasyRPG Player 路径，配置后 RPG Maker 2000/2003 游戏优先用它运行
+    pub easyrpg_binary: Option<String>,
+}
+
+/// 同时运行游戏数的限制（默认不限制）
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchLimit {
+    /// 同时运行的游戏数上限，0 为不限制
+    pub max_concurrent: u32,
+    /// 达到上限时排队，等其它游戏退出后自动启动；否则直接拒绝
+    pub queue: bool,
+}
+
 /// 集成选项（可扩展）
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
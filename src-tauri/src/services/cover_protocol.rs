@@ -0,0 +1,116 @@
+//! `gmcover://` 自定义协议：按 profile_key 提供封面与缩略图，附带缓存头。
+//!
+//! URL 形如 `gmcover://localhost/<profile_key>[/thumb]?v=<updated_at>`，
+//! `v` 随封面更新而变化，因此响应可以被长期缓存。
+
+use crate::services::FileService;
+use percent_encoding::{NON_ALPHANUMERIC, percent_decode_str, utf8_percent_encode};
+use std::path::{Path, PathBuf};
+use tauri::http::{Response, StatusCode, header};
+
+/// 协议名
+pub const COVER_SCHEME: &str = "gmcover";
+
+/// 生成封面 URL；`thumb` 为 true 时返回缩略图（不存在时服务端回退到完整封面）
+pub fn cover_url(profile_key: &str, thumb: bool, version: i64) -> String {
+    // Windows / Android 上 WebView 通过 http://<scheme>.localhost 访问自定义协议
+    let base = if cfg!(any(target_os = "windows", target_os = "android")) {
+        format!("http://{}.localhost", COVER_SCHEME)
+    } else {
+        format!("{}://localhost", COVER_SCHEME)
+    };
+    let key = utf8_percent_encode(profile_key, NON_ALPHANUMERIC);
+    let suffix = if thumb { "/thumb" } else { "" };
+    format!("{}/{}{}?v={}", base, key, suffix, version)
+}
+
+/// 处理协议请求：`path` 为请求 URI 的路径部分
+pub fn respond(container_root: &Path, path: &str) -> Response<Vec<u8>> {
+    let path = path.trim_start_matches('/');
+    let (key, thumb) = match path.strip_suffix("/thumb") {
+        Some(key) => (key, true),
+        None => (path, false),
+    };
+    let Ok(key) = percent_decode_str(key).decode_utf8() else {
+        return error_response(StatusCode::BAD_REQUEST);
+    };
+    if key.is_empty() || key == "." || key == ".." || key.contains(['/', '\\']) {
+        return error_response(StatusCode::BAD_REQUEST);
+    }
+
+    let file_service = FileService::new();
+    let Some(cover) = resolve_cover_file(&file_service, container_root, &key) else {
+        return error_response(StatusCode::NOT_FOUND);
+    };
+    let file = if thumb {
+        file_service.cover_thumbnail(&cover).unwrap_or(cover)
+    } else {
+        cover
+    };
+
+    match std::fs::read(&file) {
+        Ok(bytes) => Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, content_type(&file))
+            .header(header::CACHE_CONTROL, "public, max-age=31536000, immutable")
+            .header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")
+            .body(bytes)
+            .unwrap_or_else(|_| error_response(StatusCode::INTERNAL_SERVER_ERROR)),
+        Err(e) => {
+            tracing::debug!(path = %file.display(), error = %e, "读取封面失败");
+            error_response(StatusCode::NOT_FOUND)
+        }
+    }
+}
+
+/// 查找 profile 的封面：先读取配置中的 cover_file，再查找 profile 目录中的 `cover.*`
+fn resolve_cover_file(file_service: &FileService, root: &Path, key: &str) -> Option<PathBuf> {
+    let profile_dir = file_service.game_profile_dir(root, key);
+    let config_path = file_service.game_config_path(root, key);
+    if config_path.is_file()
+        && let Ok(config) = file_service.read_game_config(&config_path)
+        && let Some(cover_file) = config.cover_file.filter(|f| !f.trim().is_empty())
+    {
+        let path = if Path::new(&cover_file).is_absolute() {
+            PathBuf::from(&cover_file)
+        } else {
+            profile_dir.join(&cover_file)
+        };
+        if path.is_file() {
+            return Some(path);
+        }
+    }
+
+    let mut covers: Vec<PathBuf> = std::fs::read_dir(&profile_dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.file_stem().is_some_and(|stem| stem == "cover"))
+        .collect();
+    covers.sort();
+    covers.into_iter().next()
+}
+
+fn content_type(path: &Path) -> &'static str {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    match ext.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "webp" => "image/webp",
+        "bmp" => "image/bmp",
+        "gif" => "image/gif",
+        "ico" => "image/x-icon",
+        _ => "application/octet-stream",
+    }
+}
+
+/// 空响应体的错误响应
+pub fn error_response(status: StatusCode) -> Response<Vec<u8>> {
+    let mut response = Response::new(Vec::new());
+    *response.status_mut() = status;
+    response
+}
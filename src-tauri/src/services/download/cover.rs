@@ -0,0 +1,70 @@
+//! 从远程 URL 下载封面图片（限制协议、类型与大小）。
+
+use futures_util::StreamExt;
+use std::time::Duration;
+
+/// 封面下载大小上限
+const MAX_COVER_BYTES: u64 = 20 * 1024 * 1024;
+/// 封面下载超时
+const COVER_DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 下载封面图片，返回图片数据与按内容识别出的扩展名
+///
+/// 仅接受 http(s) 地址；响应必须声明为 `image/*`，且实际内容可被识别为图片。
+pub async fn fetch_cover_image(url: &str) -> Result<(Vec<u8>, &'static str), String> {
+    let url = reqwest::Url::parse(url.trim()).map_err(|e| format!("封面地址无效: {}", e))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err("封面地址必须以 http:// 或 https:// 开头".to_string());
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(COVER_DOWNLOAD_TIMEOUT)
+        .build()
+        .map_err(|e| format!("创建下载客户端失败: {}", e))?;
+    let resp = client
+        .get(url)
+        .send()
+        .await
+        .and_then(|resp| resp.error_for_status())
+        .map_err(|e| format!("下载封面失败: {}", e))?;
+
+    let content_type = resp
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    if !content_type.starts_with("image/") {
+        return Err(format!("链接内容不是图片: {}", content_type));
+    }
+    if resp
+        .content_length()
+        .is_some_and(|len| len > MAX_COVER_BYTES)
+    {
+        return Err(too_large());
+    }
+
+    let mut data = Vec::new();
+    let mut stream = resp.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| format!("下载封面失败: {}", e))?;
+        if data.len() as u64 + chunk.len() as u64 > MAX_COVER_BYTES {
+            return Err(too_large());
+        }
+        data.extend_from_slice(&chunk);
+    }
+
+    let ext = match image::guess_format(&data) {
+        Ok(image::ImageFormat::Png) => "png",
+        Ok(image::ImageFormat::Jpeg) => "jpg",
+        Ok(image::ImageFormat::WebP) => "webp",
+        Ok(image::ImageFormat::Bmp) => "bmp",
+        Ok(image::ImageFormat::Ico) => "ico",
+        _ => return Err("不支持的图片格式".to_string()),
+    };
+    Ok((data, ext))
+}
+
+fn too_large() -> String {
+    format!("封面图片超过 {} MB", MAX_COVER_BYTES / 1024 / 1024)
+}
@@ -0,0 +1,279 @@
+//! 为 NW.js 运行时替换支持专有编解码器（H.264/AAC）的 ffmpeg 库。
+//!
+//! 官方构建的 ffmpeg 不含专有编解码器，MV 游戏中的 MP4 视频会黑屏或无声。
+//! 社区项目 nwjs-ffmpeg-prebuilt 按 NW.js 版本发布替换用的库文件。
+
+use crate::services::fs::ArchiveService;
+use futures_util::StreamExt;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tempfile::TempDir;
+
+/// 预编译 ffmpeg 的发布地址
+const FFMPEG_PREBUILT_BASE: &str =
+    "https://github.com/nwjs-ffmpeg-prebuilt/nwjs-ffmpeg-prebuilt/releases/download";
+/// 发布清单（GitHub Releases API，按 tag 查询）
+const FFMPEG_RELEASE_API: &str =
+    "https://api.github.com/repos/nwjs-ffmpeg-prebuilt/nwjs-ffmpeg-prebuilt/releases/tags";
+/// 下载大小上限
+const MAX_FFMPEG_BYTES: u64 = 64 * 1024 * 1024;
+/// 下载超时
+const FFMPEG_DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(120);
+/// 已替换标记文件（内容为替换时的 NW.js 版本），位于运行时安装目录
+const CODEC_MARKER: &str = ".ffmpeg-codecs";
+/// 被替换的原始库文件后缀
+const STOCK_SUFFIX: &str = ".stock";
+/// 校验通过、待换入的新库文件后缀
+const STAGED_SUFFIX: &str = ".new";
+
+#[derive(Debug, Deserialize)]
+struct ReleaseManifest {
+    #[serde(default)]
+    assets: Vec<ReleaseAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReleaseAsset {
+    name: String,
+    /// 形如 `sha256:<hex>`
+    digest: Option<String>,
+}
+
+/// 目标平台上的 ffmpeg 库文件名
+fn ffmpeg_lib_name(target: &str) -> &'static str {
+    if target.starts_with("win-") {
+        "ffmpeg.dll"
+    } else if target.starts_with("osx-") {
+        "libffmpeg.dylib"
+    } else {
+        "libffmpeg.so"
+    }
+}
+
+/// 运行时是否已替换为带专有编解码器的 ffmpeg
+pub fn has_codec_ffmpeg(install_dir: &Path) -> bool {
+    install_dir.join(CODEC_MARKER).is_file()
+}
+
+/// 下载与 `version` 匹配的 ffmpeg 并替换 `install_dir` 中的原始库，返回替换后的库路径
+///
+/// 原始库保留为 `<文件名>.stock`，重新安装运行时即可恢复。
+/// 压缩包须与发布清单中的 SHA-256 一致；校验通过前不会改动运行时中的库。
+pub async fn install_codec_ffmpeg(
+    install_dir: &Path,
+    version: &str,
+    target: &str,
+) -> Result<PathBuf, String> {
+    let lib_name = ffmpeg_lib_name(target);
+    let stock = find_file(install_dir, lib_name)
+        .ok_or_else(|| format!("运行时中未找到 {}: {}", lib_name, install_dir.display()))?;
+
+    let client = reqwest::Client::builder()
+        .timeout(FFMPEG_DOWNLOAD_TIMEOUT)
+        .user_agent(concat!("GameManager/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| format!("创建下载客户端失败: {}", e))?;
+    let asset_name = format!("{}-{}.zip", version, target);
+    let expected = fetch_expected_sha256(&client, version, &asset_name).await?;
+    let url = format!("{}/{}/{}", FFMPEG_PREBUILT_BASE, version, asset_name);
+    let data = fetch(&client, &url).await?;
+
+    let actual = format!("{:x}", Sha256::digest(&data));
+    if actual != expected {
+        return Err(format!(
+            "ffmpeg 校验失败: 期望 {}，实际 {}",
+            expected, actual
+        ));
+    }
+
+    let install_dir = install_dir.to_path_buf();
+    let version = version.to_string();
+    tokio::task::spawn_blocking(move || {
+        replace_library(&stock, lib_name, &data)?;
+        std::fs::write(install_dir.join(CODEC_MARKER), version)
+            .map_err(|e| format!("写入标记文件失败: {}", e))?;
+        Ok(stock)
+    })
+    .await
+    .map_err(|e| format!("替换 ffmpeg 失败: {}", e))?
+}
+
+/// 从发布清单中查找压缩包的 SHA-256（小写十六进制）
+async fn fetch_expected_sha256(
+    client: &reqwest::Client,
+    version: &str,
+    asset_name: &str,
+) -> Result<String, String> {
+    let url = format!("{}/{}", FFMPEG_RELEASE_API, version);
+    let resp = client
+        .get(&url)
+        .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+        .send()
+        .await
+        .map_err(|e| format!("获取 ffmpeg 发布清单失败: {}", e))?;
+    if resp.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err("该 NW.js 版本暂无可用的 ffmpeg 替换包".to_string());
+    }
+    let manifest: ReleaseManifest = resp
+        .error_for_status()
+        .map_err(|e| format!("获取 ffmpeg 发布清单失败: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("解析 ffmpeg 发布清单失败: {}", e))?;
+    asset_sha256(&manifest, asset_name)
+}
+
+fn asset_sha256(manifest: &ReleaseManifest, asset_name: &str) -> Result<String, String> {
+    let asset = manifest
+        .assets
+        .iter()
+        .find(|a| a.name == asset_name)
+        .ok_or_else(|| format!("发布清单中没有 {}", asset_name))?;
+    asset
+        .digest
+        .as_deref()
+        .and_then(|d| d.strip_prefix("sha256:"))
+        .map(|d| d.trim().to_lowercase())
+        .ok_or_else(|| format!("发布清单中没有 {} 的 SHA-256", asset_name))
+}
+
+/// 从已校验的压缩包中取出库文件并换入 `stock` 的位置
+///
+/// 新库先解压、复制为 `<文件名>.new`，再整体重命名覆盖；任一步失败时原始库保持不变。
+fn replace_library(stock: &Path, lib_name: &str, archive: &[u8]) -> Result<(), String> {
+    let tmp = TempDir::new().map_err(|e| format!("创建临时目录失败: {}", e))?;
+    let archive_path = tmp.path().join("ffmpeg.zip");
+    std::fs::write(&archive_path, archive).map_err(|e| format!("保存 ffmpeg 失败: {}", e))?;
+    let extract_dir = tmp.path().join("extract");
+    crate::utils::path::ensure_dir(&extract_dir)?;
+    ArchiveService::new().extract_auto(&archive_path, &extract_dir)?;
+    let replacement = find_file(&extract_dir, lib_name)
+        .ok_or_else(|| format!("下载的压缩包中没有 {}", lib_name))?;
+
+    let staged = stock.with_file_name(format!("{}{}", lib_name, STAGED_SUFFIX));
+    std::fs::copy(&replacement, &staged).map_err(|e| format!("复制 ffmpeg 失败: {}", e))?;
+    let backup = stock.with_file_name(format!("{}{}", lib_name, STOCK_SUFFIX));
+    if !backup.exists()
+        && let Err(e) = std::fs::copy(stock, &backup)
+    {
+        let _ = std::fs::remove_file(&staged);
+        return Err(format!("备份原始 ffmpeg 失败: {}", e));
+    }
+    if let Err(e) = std::fs::rename(&staged, stock) {
+        let _ = std::fs::remove_file(&staged);
+        return Err(format!("替换 ffmpeg 失败 {}: {}", stock.display(), e));
+    }
+    Ok(())
+}
+
+async fn fetch(client: &reqwest::Client, url: &str) -> Result<Vec<u8>, String> {
+    let resp = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("下载 ffmpeg 失败: {}", e))?;
+    if resp.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err("该 NW.js 版本暂无可用的 ffmpeg 替换包".to_string());
+    }
+    let resp = resp
+        .error_for_status()
+        .map_err(|e| format!("下载 ffmpeg 失败: {}", e))?;
+
+    let mut data = Vec::new();
+    let mut stream = resp.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| format!("下载 ffmpeg 失败: {}", e))?;
+        if data.len() as u64 + chunk.len() as u64 > MAX_FFMPEG_BYTES {
+            return Err("ffmpeg 文件过大".to_string());
+        }
+        data.extend_from_slice(&chunk);
+    }
+    Ok(data)
+}
+
+/// 在目录树中查找指定文件名（macOS 的库位于 framework 深处）
+fn find_file(dir: &Path, name: &str) -> Option<PathBuf> {
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                pending.push(path);
+            } else if file_type.is_file() && entry.file_name() == name {
+                return Some(path);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zip_with(dir: &Path, name: &str, content: &[u8]) -> Vec<u8> {
+        let src = dir.join("src").join(name);
+        std::fs::create_dir_all(src.parent().unwrap()).unwrap();
+        std::fs::write(&src, content).unwrap();
+        let zip = dir.join("pkg.zip");
+        ArchiveService::new()
+            .create_zip(&zip, &[(src, format!("lib/{}", name))])
+            .unwrap();
+        std::fs::read(zip).unwrap()
+    }
+
+    #[test]
+    fn asset_sha256_reads_digest_from_manifest() {
+        let manifest: ReleaseManifest = serde_json::from_str(
+            r#"{"assets":[
+                {"name":"0.90.0-win-x64.zip","digest":"sha256:ABCDEF"},
+                {"name":"0.90.0-linux-x64.zip","digest":null}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            asset_sha256(&manifest, "0.90.0-win-x64.zip").unwrap(),
+            "abcdef"
+        );
+        assert!(asset_sha256(&manifest, "0.90.0-linux-x64.zip").is_err());
+        assert!(asset_sha256(&manifest, "0.90.0-osx-x64.zip").is_err());
+    }
+
+    #[test]
+    fn replace_library_swaps_in_new_lib_and_keeps_stock() {
+        let tmp = tempfile::tempdir().unwrap();
+        let stock = tmp.path().join("runtime").join("libffmpeg.so");
+        std::fs::create_dir_all(stock.parent().unwrap()).unwrap();
+        std::fs::write(&stock, b"stock").unwrap();
+        let archive = zip_with(tmp.path(), "libffmpeg.so", b"codecs");
+
+        replace_library(&stock, "libffmpeg.so", &archive).unwrap();
+        assert_eq!(std::fs::read(&stock).unwrap(), b"codecs");
+        assert_eq!(
+            std::fs::read(stock.with_file_name("libffmpeg.so.stock")).unwrap(),
+            b"stock"
+        );
+        assert!(!stock.with_file_name("libffmpeg.so.new").exists());
+    }
+
+    #[test]
+    fn replace_library_leaves_stock_untouched_on_bad_archive() {
+        let tmp = tempfile::tempdir().unwrap();
+        let stock = tmp.path().join("runtime").join("ffmpeg.dll");
+        std::fs::create_dir_all(stock.parent().unwrap()).unwrap();
+        std::fs::write(&stock, b"stock").unwrap();
+        let archive = zip_with(tmp.path(), "readme.txt", b"nothing here");
+
+        assert!(replace_library(&stock, "ffmpeg.dll", &archive).is_err());
+        assert_eq!(std::fs::read(&stock).unwrap(), b"stock");
+        assert!(!stock.with_file_name("ffmpeg.dll.stock").exists());
+    }
+}
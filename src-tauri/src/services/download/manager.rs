@@ -0,0 +1,189 @@
+//! 下载任务登记：记录进行中的运行时下载，限制同时下载数并支持取消。
+
+use super::nwjs::NwjsFlavor;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex as StdMutex};
+use tokio::sync::{Notify, OwnedSemaphorePermit, Semaphore};
+
+/// 同时进行的下载数上限，超出的任务排队等待
+pub const MAX_CONCURRENT_DOWNLOADS: usize = 2;
+
+/// 下载任务所处阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DownloadStage {
+    /// 等待空闲的下载名额
+    Queued,
+    Downloading,
+    Extracting,
+}
+
+/// 下载任务状态（`list_download_tasks` 返回）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadTaskInfo {
+    pub task_id: String,
+    pub version: String,
+    pub flavor: NwjsFlavor,
+    pub target: String,
+    pub stage: DownloadStage,
+    pub downloaded: u64,
+    pub total: Option<u64>,
+    /// 已请求取消，任务即将结束
+    pub cancelled: bool,
+    /// 开始时间（Unix 毫秒）
+    pub started_at: i64,
+}
+
+struct DownloadHandle {
+    info: DownloadTaskInfo,
+    cancel: Arc<AtomicBool>,
+    cancel_notify: Arc<Notify>,
+}
+
+type TaskMap = Arc<StdMutex<HashMap<String, DownloadHandle>>>;
+
+/// 进行中的下载任务（作为 Tauri 状态托管）
+pub struct DownloadManager {
+    tasks: TaskMap,
+    permits: Arc<Semaphore>,
+}
+
+impl DownloadManager {
+    pub fn new() -> Self {
+        Self {
+            tasks: Arc::new(StdMutex::new(HashMap::new())),
+            permits: Arc::new(Semaphore::new(MAX_CONCURRENT_DOWNLOADS)),
+        }
+    }
+
+    /// 登记新任务（初始为排队状态），返回的句柄在任务结束时自动注销
+    pub fn register(
+        &self,
+        task_id: &str,
+        version: &str,
+        flavor: NwjsFlavor,
+        target: &str,
+    ) -> DownloadTask {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let cancel_notify = Arc::new(Notify::new());
+        let info = DownloadTaskInfo {
+            task_id: task_id.to_string(),
+            version: version.to_string(),
+            flavor,
+            target: target.to_string(),
+            stage: DownloadStage::Queued,
+            downloaded: 0,
+            total: None,
+            cancelled: false,
+            started_at: crate::utils::now_unix_ms(),
+        };
+        self.tasks.lock().unwrap().insert(
+            task_id.to_string(),
+            DownloadHandle {
+                info,
+                cancel: cancel.clone(),
+                cancel_notify: cancel_notify.clone(),
+            },
+        );
+        DownloadTask {
+            task_id: task_id.to_string(),
+            cancel,
+            cancel_notify,
+            tasks: self.tasks.clone(),
+            permits: self.permits.clone(),
+        }
+    }
+
+    /// 全部进行中的任务（按开始时间排序）
+    pub fn list(&self) -> Vec<DownloadTaskInfo> {
+        let mut tasks: Vec<DownloadTaskInfo> = self
+            .tasks
+            .lock()
+            .unwrap()
+            .values()
+            .map(|handle| handle.info.clone())
+            .collect();
+        tasks.sort_by_key(|task| task.started_at);
+        tasks
+    }
+
+    /// 请求取消任务；任务不存在（已结束）时返回 false
+    pub fn cancel(&self, task_id: &str) -> bool {
+        let mut tasks = self.tasks.lock().unwrap();
+        let Some(handle) = tasks.get_mut(task_id) else {
+            return false;
+        };
+        handle.cancel.store(true, Ordering::Relaxed);
+        handle.info.cancelled = true;
+        handle.cancel_notify.notify_one();
+        true
+    }
+}
+
+impl Default for DownloadManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 单个下载任务的句柄，drop 时从登记表中移除
+pub struct DownloadTask {
+    task_id: String,
+    cancel: Arc<AtomicBool>,
+    cancel_notify: Arc<Notify>,
+    tasks: TaskMap,
+    permits: Arc<Semaphore>,
+}
+
+impl DownloadTask {
+    /// 等待下载名额；排队期间被取消时返回 `None`
+    pub async fn acquire_slot(&self) -> Option<OwnedSemaphorePermit> {
+        if self.is_cancelled() {
+            return None;
+        }
+        let permit = tokio::select! {
+            permit = self.permits.clone().acquire_owned() => permit.ok()?,
+            _ = self.cancel_notify.notified() => return None,
+        };
+        self.set_stage(DownloadStage::Downloading);
+        Some(permit)
+    }
+
+    /// 等待任务被取消（已取消时立即返回），用于在 `select!` 中中断等待
+    pub async fn cancelled(&self) {
+        if self.is_cancelled() {
+            return;
+        }
+        self.cancel_notify.notified().await;
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+
+    pub fn set_stage(&self, stage: DownloadStage) {
+        self.update(|info| info.stage = stage);
+    }
+
+    pub fn set_progress(&self, downloaded: u64, total: Option<u64>) {
+        self.update(|info| {
+            info.downloaded = downloaded;
+            info.total = total;
+        });
+    }
+
+    fn update(&self, f: impl FnOnce(&mut DownloadTaskInfo)) {
+        if let Some(handle) = self.tasks.lock().unwrap().get_mut(&self.task_id) {
+            f(&mut handle.info);
+        }
+    }
+}
+
+impl Drop for DownloadTask {
+    fn drop(&mut self) {
+        self.tasks.lock().unwrap().remove(&self.task_id);
+    }
+}
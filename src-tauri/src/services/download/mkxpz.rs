@@ -87,7 +87,7 @@ pub fn import_from_archive(
     let version_dir = runtime_root.join(&version);
     let result = (|| -> Result<MkxpzImportResult, String> {
         // 先解压到临时目录，再规范化到最终 runtimes 目录，避免半成品污染。
-        archive_service.extract_zip(archive_path, &staging_dir)?;
+        archive_service.extract_zip(archive_path, &staging_dir, None)?;
 
         let content_root = archive_service
             .find_single_root_dir(&staging_dir)
@@ -1,2 +1,5 @@
+pub mod cover;
+pub mod ffmpeg;
+pub mod manager;
 pub mod mkxpz;
 pub mod nwjs;
@@ -1,9 +1,12 @@
+use crate::services::download::manager::{DownloadManager, DownloadStage, DownloadTask};
 use crate::services::fs::ArchiveService;
 use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::fs::File;
-use std::io::Write;
-use std::path::PathBuf;
+use std::io::{Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tauri::{AppHandle, Emitter, Manager};
 use tempfile::TempDir;
 use uuid::Uuid;
@@ -15,6 +18,33 @@ pub enum NwjsFlavor {
     Sdk,
 }
 
+impl NwjsFlavor {
+    /// 解析 flavor 名称（`normal` / `sdk`，不区分大小写）
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "normal" => Some(Self::Normal),
+            "sdk" => Some(Self::Sdk),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Sdk => "sdk",
+        }
+    }
+
+    /// 已登记的 NW.js 引擎是否属于该 flavor（按引擎名称区分，SDK 版名称含 "sdk"）
+    pub fn matches_engine_name(self, name: &str) -> bool {
+        let is_sdk = name.to_lowercase().contains("sdk");
+        match self {
+            Self::Sdk => is_sdk,
+            Self::Normal => !is_sdk,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NwjsStableInfo {
@@ -24,6 +54,40 @@ pub struct NwjsStableInfo {
     pub sdk_url: String,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NwjsVersionInfo {
+    pub version: String,
+    /// 本地 runtimes/nwjs/<version> 是否已存在
+    pub installed: bool,
+}
+
+/// 本地已安装的 NW.js 运行时
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NwjsInstalledRuntime {
+    pub version: String,
+    pub flavor: NwjsFlavor,
+    pub target: String,
+    pub install_dir: String,
+    /// 占用磁盘空间（字节）
+    pub size_bytes: u64,
+    /// engines 表中是否有对应记录（游戏启动只会使用已登记的运行时）
+    pub registered: bool,
+    /// 已替换为支持专有编解码器（H.264/AAC）的 ffmpeg
+    pub codec_ffmpeg: bool,
+}
+
+/// 本地运行时列表及磁盘占用
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NwjsInstalledRuntimes {
+    pub runtimes: Vec<NwjsInstalledRuntime>,
+    pub total_bytes: u64,
+    /// 已登记但安装目录不存在的引擎 ID
+    pub missing_engine_ids: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NwjsInstallResult {
@@ -44,6 +108,42 @@ pub struct NwjsDownloadProgress {
     pub downloaded: u64,
     pub total: Option<u64>,
     pub percent: Option<u8>,
+    /// 当前下载尝试次数（从 1 开始，大于 1 表示正在重试）
+    pub attempt: u32,
+    pub max_attempts: u32,
+    /// 下载被用户取消（最后一条进度事件）
+    pub cancelled: bool,
+}
+
+/// 网络错误时的最大下载尝试次数
+const MAX_DOWNLOAD_ATTEMPTS: u32 = 3;
+/// 首次重试前的等待时间，之后每次翻倍
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+
+/// 单次下载尝试的失败原因
+enum AttemptError {
+    Cancelled,
+    /// 网络或 IO 错误，可重试
+    Retryable(String),
+    Fatal(String),
+}
+
+impl AttemptError {
+    fn into_message(self) -> String {
+        match self {
+            Self::Cancelled => "下载已取消".to_string(),
+            Self::Retryable(e) | Self::Fatal(e) => e,
+        }
+    }
+}
+
+/// 下载过程中跨重试保留的进度
+struct DownloadState {
+    downloaded: u64,
+    total: Option<u64>,
+    /// 当前尝试次数（从 1 开始）
+    attempt: u32,
+    hasher: Sha256,
 }
 
 pub fn current_target() -> Result<String, String> {
@@ -77,23 +177,100 @@ fn nwjs_archive_ext(target: &str) -> &'static str {
     }
 }
 
-pub fn build_download_url(version: &str, flavor: NwjsFlavor, target: &str) -> String {
+fn archive_file_name(version: &str, flavor: NwjsFlavor, target: &str) -> String {
     let ext = nwjs_archive_ext(target);
     let prefix = match flavor {
         NwjsFlavor::Normal => "nwjs",
         NwjsFlavor::Sdk => "nwjs-sdk",
     };
 
-    format!("https://dl.nwjs.io/v{version}/{prefix}-v{version}-{target}.{ext}")
+    format!("{prefix}-v{version}-{target}.{ext}")
+}
+
+/// 官方下载地址
+pub const DEFAULT_MIRROR: &str = "https://dl.nwjs.io";
+
+/// 校验下载镜像地址：必须是 http(s) URL，并去掉结尾的 `/`
+pub fn normalize_mirror(raw: &str) -> Result<String, String> {
+    let trimmed = raw.trim().trim_end_matches('/');
+    let url = reqwest::Url::parse(trimmed).map_err(|e| format!("镜像地址无效: {e}"))?;
+    if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+        return Err("镜像地址必须是 http(s) URL".to_string());
+    }
+    Ok(trimmed.to_string())
+}
+
+/// 读取设置中的下载镜像，未设置时使用官方地址
+pub async fn resolve_mirror(db: &mut toasty::Db) -> Result<String, String> {
+    let mirror = crate::db::get_setting(db, crate::models::SETTING_NWJS_MIRROR)
+        .await?
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_MIRROR.to_string());
+    Ok(mirror)
+}
+
+/// 读取设置中的默认 flavor，未设置时为普通版
+pub async fn resolve_default_flavor(db: &mut toasty::Db) -> Result<NwjsFlavor, String> {
+    Ok(
+        crate::db::get_setting(db, crate::models::SETTING_NWJS_DEFAULT_FLAVOR)
+            .await?
+            .and_then(|value| NwjsFlavor::parse(&value))
+            .unwrap_or(NwjsFlavor::Normal),
+    )
+}
+
+pub fn build_download_url(mirror: &str, version: &str, flavor: NwjsFlavor, target: &str) -> String {
+    let file_name = archive_file_name(version, flavor, target);
+    format!("{mirror}/v{version}/{file_name}")
+}
+
+/// 从 SHASUMS256.txt 中查找归档文件的 SHA256（小写十六进制）
+async fn fetch_expected_sha256(
+    mirror: &str,
+    version: &str,
+    file_name: &str,
+) -> Result<String, String> {
+    let url = format!("{mirror}/v{version}/SHASUMS256.txt");
+    let text = reqwest::Client::new()
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("failed to fetch SHASUMS256.txt: {e}"))?
+        .error_for_status()
+        .map_err(|e| format!("failed to fetch SHASUMS256.txt: {e}"))?
+        .text()
+        .await
+        .map_err(|e| format!("failed to read SHASUMS256.txt: {e}"))?;
+
+    // 每行格式：<sha256>  <file name>
+    text.lines()
+        .filter_map(|line| line.split_once(char::is_whitespace))
+        .find(|(_, name)| name.trim().trim_start_matches('*') == file_name)
+        .map(|(digest, _)| digest.trim().to_lowercase())
+        .ok_or_else(|| format!("SHASUMS256.txt has no entry for {file_name}"))
 }
 
 #[derive(Debug, Deserialize)]
 struct VersionsJson {
     stable: Option<String>,
     latest: Option<String>,
+    #[serde(default)]
+    versions: Vec<VersionEntry>,
 }
 
-pub async fn fetch_stable_version() -> Result<String, String> {
+#[derive(Debug, Deserialize)]
+struct VersionEntry {
+    version: String,
+}
+
+/// 去掉版本号前的 `v`
+pub fn normalize_version(raw: &str) -> String {
+    raw.trim()
+        .trim_start_matches(|c: char| c == 'v' || c == 'V')
+        .to_string()
+}
+
+async fn fetch_versions_json() -> Result<VersionsJson, String> {
     // The downloads page is rendered dynamically; the stable/latest versions are published in versions.json.
     let json_text = reqwest::Client::new()
         .get("https://nwjs.io/versions.json")
@@ -104,18 +281,18 @@ pub async fn fetch_stable_version() -> Result<String, String> {
         .await
         .map_err(|e| format!("failed to read versions.json: {e}"))?;
 
-    let versions: VersionsJson = serde_json::from_str(&json_text)
-        .map_err(|e| format!("failed to parse versions.json: {e}"))?;
+    serde_json::from_str(&json_text).map_err(|e| format!("failed to parse versions.json: {e}"))
+}
+
+pub async fn fetch_stable_version() -> Result<String, String> {
+    let versions = fetch_versions_json().await?;
 
     let raw = versions
         .stable
         .or(versions.latest)
         .ok_or_else(|| "versions.json missing stable/latest".to_string())?;
 
-    let ver = raw
-        .trim()
-        .trim_start_matches(|c: char| c == 'v' || c == 'V')
-        .to_string();
+    let ver = normalize_version(&raw);
 
     if ver.is_empty() {
         return Err("failed to parse stable version from versions.json".to_string());
@@ -124,18 +301,95 @@ pub async fn fetch_stable_version() -> Result<String, String> {
     Ok(ver)
 }
 
-pub async fn get_stable_info() -> Result<NwjsStableInfo, String> {
+pub async fn get_stable_info(mirror: &str) -> Result<NwjsStableInfo, String> {
     let version = fetch_stable_version().await?;
     let target = current_target()?;
 
     Ok(NwjsStableInfo {
-        normal_url: build_download_url(&version, NwjsFlavor::Normal, &target),
-        sdk_url: build_download_url(&version, NwjsFlavor::Sdk, &target),
+        normal_url: build_download_url(mirror, &version, NwjsFlavor::Normal, &target),
+        sdk_url: build_download_url(mirror, &version, NwjsFlavor::Sdk, &target),
         version,
         target,
     })
 }
 
+/// 列出 versions.json 中的全部版本（按版本号降序），并标记本地是否已安装
+pub async fn list_versions(app: &AppHandle) -> Result<Vec<NwjsVersionInfo>, String> {
+    let versions = fetch_versions_json().await?;
+    let runtime_root = app_runtime_root(app)?;
+
+    let mut list: Vec<NwjsVersionInfo> = versions
+        .versions
+        .iter()
+        .map(|entry| normalize_version(&entry.version))
+        .filter(|version| !version.is_empty())
+        .map(|version| NwjsVersionInfo {
+            installed: runtime_root.join(&version).is_dir(),
+            version,
+        })
+        .collect();
+
+    list.sort_by(|a, b| parse_version(&b.version).cmp(&parse_version(&a.version)));
+    list.dedup_by(|a, b| a.version == b.version);
+
+    Ok(list)
+}
+
+/// 扫描 `runtimes/nwjs/<version>/<flavor>/<target>` 下已安装的运行时（版本号降序）
+///
+/// 返回的 `registered` 均为 false，由调用方对照 engines 表填充。
+pub fn list_installed(app: &AppHandle) -> Result<Vec<NwjsInstalledRuntime>, String> {
+    let runtime_root = app_runtime_root(app)?;
+    let Ok(versions) = std::fs::read_dir(&runtime_root) else {
+        return Ok(Vec::new());
+    };
+
+    let mut list = Vec::new();
+    for version_dir in versions.flatten().map(|e| e.path()).filter(|p| p.is_dir()) {
+        let version = version_dir
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        // `_downloads` 等内部目录不是版本号
+        if version.starts_with(['_', '.']) {
+            continue;
+        }
+        for flavor in [NwjsFlavor::Normal, NwjsFlavor::Sdk] {
+            let Ok(targets) = std::fs::read_dir(version_dir.join(flavor.as_str())) else {
+                continue;
+            };
+            for install_dir in targets.flatten().map(|e| e.path()).filter(|p| p.is_dir()) {
+                list.push(NwjsInstalledRuntime {
+                    version: version.clone(),
+                    flavor,
+                    target: install_dir
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                    size_bytes: crate::utils::path::dir_size(&install_dir),
+                    codec_ffmpeg: super::ffmpeg::has_codec_ffmpeg(&install_dir),
+                    install_dir: install_dir.to_string_lossy().to_string(),
+                    registered: false,
+                });
+            }
+        }
+    }
+
+    list.sort_by(|a, b| {
+        parse_version(&b.version)
+            .cmp(&parse_version(&a.version))
+            .then(a.flavor.as_str().cmp(b.flavor.as_str()))
+            .then(a.target.cmp(&b.target))
+    });
+    Ok(list)
+}
+
+fn parse_version(version: &str) -> semver::Version {
+    version
+        .parse::<semver::Version>()
+        .unwrap_or_else(|_| semver::Version::new(0, 0, 0))
+}
+
 fn app_runtime_root(app: &AppHandle) -> Result<PathBuf, String> {
     let app_data_dir = app
         .path()
@@ -144,46 +398,223 @@ fn app_runtime_root(app: &AppHandle) -> Result<PathBuf, String> {
     Ok(app_data_dir.join("runtimes").join("nwjs"))
 }
 
-pub async fn download_and_install(
+/// 运行时安装目录：runtimes/nwjs/<version>/<flavor>/<target>
+pub fn runtime_install_dir(
     app: &AppHandle,
-    version: String,
+    version: &str,
     flavor: NwjsFlavor,
-    target: String,
-) -> Result<NwjsInstallResult, String> {
-    let url = build_download_url(&version, flavor, &target);
-    let task_id = Uuid::new_v4().to_string();
+    target: &str,
+) -> Result<PathBuf, String> {
+    Ok(app_runtime_root(app)?
+        .join(version)
+        .join(flavor.as_str())
+        .join(target))
+}
 
-    let runtime_root = app_runtime_root(app)?;
-    crate::utils::path::ensure_dir(&runtime_root)?;
+/// 运行时完整性检查结果（`verify_runtime` 返回）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NwjsRuntimeCheck {
+    pub ok: bool,
+    /// 缺失的文件或目录（相对安装目录；安装目录本身不存在时为其完整路径）
+    pub missing: Vec<String>,
+}
 
-    let download_dir = runtime_root.join("_downloads");
-    crate::utils::path::ensure_dir(&download_dir)?;
+/// 检查安装目录中是否有目标平台的运行时可执行文件与关键资源文件
+///
+/// macOS 的资源位于 app bundle 的 framework 内，只检查可执行文件与 framework 目录。
+pub fn verify_install(install_dir: &Path, target: &str) -> NwjsRuntimeCheck {
+    if !install_dir.is_dir() {
+        return NwjsRuntimeCheck {
+            ok: false,
+            missing: vec![install_dir.to_string_lossy().to_string()],
+        };
+    }
 
-    let ext = nwjs_archive_ext(&target);
-    let archive_path = download_dir.join(format!("{task_id}-{version}-{target}.{ext}"));
+    let required: &[&str] = if target.starts_with("osx-") {
+        &[
+            "nwjs.app/Contents/MacOS/nwjs",
+            "nwjs.app/Contents/Frameworks",
+        ]
+    } else if target.starts_with("win-") {
+        &["nw.exe", "nw.pak", "icudtl.dat", "locales"]
+    } else {
+        &["nw", "nw.pak", "icudtl.dat", "locales"]
+    };
+    let missing: Vec<String> = required
+        .iter()
+        .filter(|name| {
+            let path = install_dir.join(name);
+            match *name {
+                "locales" | "nwjs.app/Contents/Frameworks" => !path.is_dir(),
+                // 部分版本只有按缩放比例拆分的 pak
+                "nw.pak" => !path.is_file() && !install_dir.join("nw_100_percent.pak").is_file(),
+                _ => !path.is_file(),
+            }
+        })
+        .map(|name| name.to_string())
+        .collect();
+    NwjsRuntimeCheck {
+        ok: missing.is_empty(),
+        missing,
+    }
+}
+
+/// 探测下载文件大小：先发 HEAD，不支持时再请求首字节并解析 `Content-Range`
+async fn probe_content_length(client: &reqwest::Client, url: &str) -> Option<u64> {
+    fn header_str(resp: &reqwest::Response, name: reqwest::header::HeaderName) -> Option<&str> {
+        resp.headers().get(name).and_then(|v| v.to_str().ok())
+    }
+
+    if let Ok(resp) = client.head(url).send().await
+        && resp.status().is_success()
+        && let Some(len) = header_str(&resp, reqwest::header::CONTENT_LENGTH)
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .filter(|len| *len > 0)
+    {
+        return Some(len);
+    }
 
-    let client = reqwest::Client::new();
     let resp = client
-        .get(&url)
+        .get(url)
+        .header(reqwest::header::RANGE, "bytes=0-0")
         .send()
         .await
-        .map_err(|e| format!("download request failed: {e}"))?
-        .error_for_status()
-        .map_err(|e| format!("download failed: {e}"))?;
+        .ok()?;
+    if resp.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+        return None;
+    }
+    // 形如 `bytes 0-0/123456`
+    header_str(&resp, reqwest::header::CONTENT_RANGE)?
+        .rsplit('/')
+        .next()?
+        .trim()
+        .parse()
+        .ok()
+}
 
-    let total = resp.content_length();
-    let mut downloaded: u64 = 0;
+/// 下载到 `file`；可重试的错误按指数退避重试，等待响应、数据与退避期间都可以取消
+async fn download_with_retries(
+    client: &reqwest::Client,
+    url: &str,
+    file: &mut File,
+    task: &DownloadTask,
+    state: &mut DownloadState,
+    probed_total: Option<u64>,
+    on_progress: impl Fn(&DownloadState),
+) -> Result<(), AttemptError> {
+    loop {
+        let result =
+            download_attempt(client, url, file, task, state, probed_total, &on_progress).await;
+        match result {
+            Ok(()) => return Ok(()),
+            Err(AttemptError::Retryable(e)) if state.attempt < MAX_DOWNLOAD_ATTEMPTS => {
+                tracing::warn!(attempt = state.attempt, error = %e, "NW.js 下载失败，准备重试");
+                // 退避前后都检查取消，取消后不再发起新请求，也不发送新的重试进度
+                if task.is_cancelled() {
+                    return Err(AttemptError::Cancelled);
+                }
+                tokio::select! {
+                    _ = tokio::time::sleep(RETRY_BASE_DELAY * 2u32.pow(state.attempt - 1)) => {}
+                    _ = task.cancelled() => return Err(AttemptError::Cancelled),
+                }
+                if task.is_cancelled() {
+                    return Err(AttemptError::Cancelled);
+                }
+                state.attempt += 1;
+                on_progress(state);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
 
-    let mut file = File::create(&archive_path)
-        .map_err(|e| format!("failed to create {}: {e}", archive_path.display()))?;
+/// 单次下载尝试，已下载部分通过 Range 请求续传
+async fn download_attempt(
+    client: &reqwest::Client,
+    url: &str,
+    file: &mut File,
+    task: &DownloadTask,
+    state: &mut DownloadState,
+    probed_total: Option<u64>,
+    on_progress: &impl Fn(&DownloadState),
+) -> Result<(), AttemptError> {
+    let mut request = client.get(url);
+    if state.downloaded > 0 {
+        request = request.header(
+            reqwest::header::RANGE,
+            format!("bytes={}-", state.downloaded),
+        );
+    }
+    let resp = tokio::select! {
+        resp = request.send() => resp
+            .map_err(|e| AttemptError::Retryable(format!("download request failed: {e}")))?,
+        _ = task.cancelled() => return Err(AttemptError::Cancelled),
+    };
+
+    let status = resp.status();
+    if status.is_client_error() {
+        // 4xx 说明 URL 或版本本身有误，重试也没有意义
+        return Err(AttemptError::Fatal(format!(
+            "download failed: HTTP {status}"
+        )));
+    }
+    let resp = resp
+        .error_for_status()
+        .map_err(|e| AttemptError::Retryable(format!("download failed: {e}")))?;
+
+    if state.downloaded > 0 && resp.status() == reqwest::StatusCode::PARTIAL_CONTENT {
+        state.total = resp
+            .content_length()
+            .map(|len| state.downloaded + len)
+            .or(probed_total);
+    } else {
+        // 服务器不支持断点续传，从头开始
+        if state.downloaded > 0 {
+            file.set_len(0)
+                .and_then(|_| file.seek(SeekFrom::Start(0)))
+                .map(|_| ())
+                .map_err(|e| AttemptError::Fatal(format!("write error: {e}")))?;
+            state.downloaded = 0;
+            state.hasher = Sha256::new();
+        }
+        state.total = resp.content_length().or(probed_total);
+    }
 
     let mut stream = resp.bytes_stream();
-    while let Some(chunk) = stream.next().await {
-        let chunk = chunk.map_err(|e| format!("download stream error: {e}"))?;
+    loop {
+        // 连接停滞时也要能取消，不能只在收到数据后检查
+        let chunk = tokio::select! {
+            chunk = stream.next() => chunk,
+            _ = task.cancelled() => return Err(AttemptError::Cancelled),
+        };
+        let Some(chunk) = chunk else {
+            return Ok(());
+        };
+        let chunk =
+            chunk.map_err(|e| AttemptError::Retryable(format!("download stream error: {e}")))?;
         file.write_all(&chunk)
-            .map_err(|e| format!("write error: {e}"))?;
-        downloaded += chunk.len() as u64;
+            .map_err(|e| AttemptError::Retryable(format!("write error: {e}")))?;
+        state.hasher.update(&chunk);
+        state.downloaded += chunk.len() as u64;
+        task.set_progress(state.downloaded, state.total);
+        on_progress(state);
+    }
+}
 
+pub async fn download_and_install(
+    app: &AppHandle,
+    mirror: &str,
+    version: String,
+    flavor: NwjsFlavor,
+    target: String,
+    verify_checksum: bool,
+) -> Result<NwjsInstallResult, String> {
+    let url = build_download_url(mirror, &version, flavor, &target);
+    let task_id = Uuid::new_v4().to_string();
+    let downloads = app.state::<DownloadManager>();
+    let task = downloads.register(&task_id, &version, flavor, &target);
+    let progress = |downloaded: u64, total: Option<u64>, attempt: u32, cancelled: bool| {
         let percent = total.and_then(|t| {
             if t == 0 {
                 None
@@ -192,22 +623,102 @@ pub async fn download_and_install(
                 Some(p.min(100))
             }
         });
+        NwjsDownloadProgress {
+            task_id: task_id.clone(),
+            version: version.clone(),
+            flavor,
+            target: target.clone(),
+            downloaded,
+            total,
+            percent,
+            attempt,
+            max_attempts: MAX_DOWNLOAD_ATTEMPTS,
+            cancelled,
+        }
+    };
 
-        let _ = app.emit(
-            "nwjs_download_progress",
-            NwjsDownloadProgress {
-                task_id: task_id.clone(),
-                version: version.clone(),
-                flavor,
-                target: target.clone(),
-                downloaded,
-                total,
-                percent,
-            },
-        );
+    // 先发一条空进度，前端据此拿到 task_id 以便取消
+    let _ = app.emit("nwjs_download_progress", progress(0, None, 1, false));
+
+    // 超出并发上限时排队，排队期间也可以取消
+    let Some(slot) = task.acquire_slot().await else {
+        let _ = app.emit("nwjs_download_progress", progress(0, None, 1, true));
+        return Err("下载已取消".to_string());
+    };
+
+    // 先获取期望摘要，避免下载完成后才发现校验文件不可达
+    let expected_sha256 = if verify_checksum {
+        let file_name = archive_file_name(&version, flavor, &target);
+        Some(fetch_expected_sha256(mirror, &version, &file_name).await?)
+    } else {
+        None
+    };
+
+    let runtime_root = app_runtime_root(app)?;
+    crate::utils::path::ensure_dir(&runtime_root)?;
+
+    let download_dir = runtime_root.join("_downloads");
+    crate::utils::path::ensure_dir(&download_dir)?;
+
+    let ext = nwjs_archive_ext(&target);
+    let archive_path = download_dir.join(format!("{task_id}-{version}-{target}.{ext}"));
+
+    let client = reqwest::Client::new();
+    let mut file = File::create(&archive_path)
+        .map_err(|e| format!("failed to create {}: {e}", archive_path.display()))?;
+
+    // 部分 CDN 的 GET 响应不带 Content-Length，预先探测文件大小以显示百分比
+    let probed_total = probe_content_length(&client, &url).await;
+    let mut state = DownloadState {
+        downloaded: 0,
+        total: probed_total,
+        attempt: 1,
+        hasher: Sha256::new(),
+    };
+    let result = download_with_retries(
+        &client,
+        &url,
+        &mut file,
+        &task,
+        &mut state,
+        probed_total,
+        |state| {
+            let _ = app.emit(
+                "nwjs_download_progress",
+                progress(state.downloaded, state.total, state.attempt, false),
+            );
+        },
+    )
+    .await;
+    if let Err(err) = result {
+        drop(file);
+        let _ = std::fs::remove_file(&archive_path);
+        if matches!(err, AttemptError::Cancelled) {
+            let _ = app.emit(
+                "nwjs_download_progress",
+                progress(state.downloaded, state.total, state.attempt, true),
+            );
+        }
+        return Err(err.into_message());
     }
+    let hasher = state.hasher;
 
     file.flush().ok();
+    drop(file);
+    // 下载完成即释放名额，解压不占用
+    drop(slot);
+    task.set_stage(DownloadStage::Extracting);
+
+    if let Some(expected) = expected_sha256 {
+        let actual = format!("{:x}", hasher.finalize());
+        if actual != expected {
+            let _ = std::fs::remove_file(&archive_path);
+            return Err(format!(
+                "checksum mismatch for {}: expected {expected}, got {actual}",
+                archive_path.display()
+            ));
+        }
+    }
 
     let _ = app.emit(
         "nwjs_install_stage",
@@ -228,21 +739,37 @@ pub async fn download_and_install(
     let tmp_extract = tmp.path().join("extract");
     crate::utils::path::ensure_dir(&tmp_extract)?;
 
-    // 根据扩展名自动选择解压方法
-    archive_service.extract_auto(&archive_path, &tmp_extract)?;
+    // 根据扩展名自动选择解压方法，按百分比变化转发解压进度
+    let last_percent = std::cell::Cell::new(None::<u8>);
+    let on_progress = |done: u64, total: u64| {
+        if total == 0 {
+            return;
+        }
+        let percent = ((done as f64 / total as f64) * 100.0).floor().min(100.0) as u8;
+        if last_percent.replace(Some(percent)) == Some(percent) {
+            return;
+        }
+        let _ = app.emit(
+            "nwjs_install_stage",
+            serde_json::json!({
+                "taskId": task_id,
+                "version": version,
+                "flavor": flavor,
+                "target": target,
+                "stage": "extracting",
+                "label": format!("正在解压… {percent}%"),
+                "percent": percent
+            }),
+        );
+    };
+    archive_service.extract_auto_with_progress(&archive_path, &tmp_extract, Some(&on_progress))?;
 
     // 查找单一根目录
     let extracted_root = archive_service
         .find_single_root_dir(&tmp_extract)
         .unwrap_or(tmp_extract);
 
-    let install_dir = runtime_root
-        .join(&version)
-        .join(match flavor {
-            NwjsFlavor::Normal => "normal",
-            NwjsFlavor::Sdk => "sdk",
-        })
-        .join(&target);
+    let install_dir = runtime_install_dir(app, &version, flavor, &target)?;
 
     // 删除已存在的安装目录
     archive_service.remove_dir_if_exists(&install_dir)?;
@@ -272,3 +799,117 @@ pub async fn download_and_install(
         install_dir: install_dir.to_string_lossy().to_string(),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use std::net::{TcpListener, TcpStream};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// 本地 HTTP 服务：记录请求数，每个连接在单独线程中由 `respond` 写入响应
+    fn serve(respond: fn(TcpStream)) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/nwjs.zip", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else {
+                    return;
+                };
+                let _ = stream.read(&mut [0_u8; 4096]);
+                counter.fetch_add(1, Ordering::SeqCst);
+                std::thread::spawn(move || respond(stream));
+            }
+        });
+        (url, requests)
+    }
+
+    fn client() -> reqwest::Client {
+        reqwest::Client::builder().no_proxy().build().unwrap()
+    }
+
+    fn new_state() -> DownloadState {
+        DownloadState {
+            downloaded: 0,
+            total: None,
+            attempt: 1,
+            hasher: Sha256::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn cancel_interrupts_stalled_stream() {
+        let (url, _requests) = serve(|mut stream| {
+            let _ = stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 1000\r\n\r\n0123456789");
+            let _ = stream.flush();
+            // 之后不再发送数据，模拟停滞的连接
+            std::thread::sleep(Duration::from_secs(30));
+        });
+        let manager = DownloadManager::new();
+        let task = manager.register("stalled", "0.1.0", NwjsFlavor::Normal, "linux-x64");
+        let tmp = tempfile::tempdir().unwrap();
+        let mut file = File::create(tmp.path().join("nwjs.zip")).unwrap();
+        let mut state = new_state();
+
+        let result = tokio::time::timeout(
+            Duration::from_secs(5),
+            download_with_retries(&client(), &url, &mut file, &task, &mut state, None, |_| {
+                manager.cancel("stalled");
+            }),
+        )
+        .await
+        .expect("停滞的下载应能被取消");
+
+        assert_eq!(
+            result.err().map(AttemptError::into_message).as_deref(),
+            Some("下载已取消")
+        );
+        assert_eq!(state.downloaded, 10);
+    }
+
+    #[tokio::test]
+    async fn cancel_during_backoff_skips_next_attempt() {
+        let (url, requests) = serve(|mut stream| {
+            let _ = stream.write_all(
+                b"HTTP/1.1 500 Internal Server Error\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            );
+        });
+        let manager = DownloadManager::new();
+        let task = manager.register("backoff", "0.1.0", NwjsFlavor::Normal, "linux-x64");
+        let tmp = tempfile::tempdir().unwrap();
+        let mut file = File::create(tmp.path().join("nwjs.zip")).unwrap();
+        let mut state = new_state();
+        let retries = AtomicUsize::new(0);
+        let client = client();
+
+        let download =
+            download_with_retries(&client, &url, &mut file, &task, &mut state, None, |_| {
+                retries.fetch_add(1, Ordering::SeqCst);
+            });
+        let cancel = async {
+            while requests.load(Ordering::SeqCst) == 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            manager.cancel("backoff");
+        };
+        // 首次退避为 1 秒，取消必须在退避结束前生效
+        let (result, ()) =
+            tokio::time::timeout(RETRY_BASE_DELAY / 2 + Duration::from_millis(300), async {
+                tokio::join!(download, cancel)
+            })
+            .await
+            .expect("退避期间的取消应立即生效");
+
+        assert_eq!(
+            result.err().map(AttemptError::into_message).as_deref(),
+            Some("下载已取消")
+        );
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+        assert_eq!(retries.load(Ordering::SeqCst), 0);
+        assert_eq!(state.attempt, 1);
+    }
+}
@@ -57,6 +57,18 @@ impl EngineService {
         }
     }
 
+    /// 获取指定类型的全部引擎（最新安装的在前）
+    pub async fn list_engines_by_type(&self, engine_type: &str) -> Result<Vec<Engine>, String> {
+        let mut db = self.db.lock().await;
+        let mut engines = Engine::filter(Engine::fields().engine_type().eq(engine_type))
+            .exec(&mut *db)
+            .await
+            .map_err(|e| format!("查询引擎失败: {}", e))?;
+
+        engines.sort_by(|a, b| b.installed_at.cmp(&a.installed_at));
+        Ok(engines)
+    }
+
     /// 获取指定类型的最新引擎
     pub async fn find_latest_engine_by_type(
         &self,
@@ -137,6 +149,34 @@ impl EngineService {
         Ok(())
     }
 
+    /// 记录运行时的目标平台与安装目录占用空间
+    pub async fn update_engine_build(
+        &self,
+        id: &str,
+        target: &str,
+        install_dir: &str,
+    ) -> Result<(), String> {
+        let dir = std::path::PathBuf::from(install_dir);
+        let size_bytes = tokio::task::spawn_blocking(move || crate::utils::path::dir_size(&dir))
+            .await
+            .map_err(|e| format!("统计运行时大小失败: {}", e))?;
+
+        let mut db = self.db.lock().await;
+        let mut engine = Engine::get_by_id(&mut *db, id)
+            .await
+            .map_err(|e| format!("查询引擎失败: {}", e))?;
+
+        engine
+            .update()
+            .target(Some(target.to_string()))
+            .size_bytes(Some(size_bytes.min(i64::MAX as u64) as i64))
+            .exec(&mut *db)
+            .await
+            .map_err(|e| format!("更新引擎失败: {}", e))?;
+
+        Ok(())
+    }
+
     /// 转换为DTO
     pub fn to_dto(&self, engine: Engine) -> EngineDto {
         EngineDto {
@@ -146,6 +186,8 @@ impl EngineService {
             engine_type: engine.engine_type,
             path: engine.engine_path,
             installed_at: engine.installed_at,
+            target: engine.target,
+            size_bytes: engine.size_bytes.map(|size| size.max(0) as u64),
         }
     }
 }
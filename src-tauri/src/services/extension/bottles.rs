@@ -104,12 +104,47 @@ impl BottlesService {
         Ok(Self::parse_bottles_text(&text))
     }
 
+    /// 通过 `bottles-cli new` 新建 bottle；`environment` 为 gaming / application / custom
+    pub async fn create_bottle(
+        cli: &BottlesCli,
+        name: &str,
+        environment: &str,
+    ) -> Result<(), String> {
+        let cli = cli.clone();
+        let (name, environment) = (name.to_string(), environment.to_string());
+        let output = tokio::task::spawn_blocking(move || {
+            cli.with_args_sync(&["new", "--bottle-name", &name, "--environment", &environment])
+                .output()
+        })
+        .await
+        .map_err(|e| format!("无法执行 bottles-cli: {e}"))?
+        .map_err(|e| format!("无法执行 bottles-cli: {e}"))?;
+
+        if !output.status.success() {
+            let err = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("创建 bottle 失败: {}", err.trim()));
+        }
+        Ok(())
+    }
+
     pub fn run_executable(
         cli: &BottlesCli,
         bottle: &str,
         exe_path: &str,
         args: &[String],
     ) -> Result<std::process::Child, String> {
+        Self::executable_command(cli, bottle, exe_path, args)
+            .spawn()
+            .map_err(|e| format!("启动 Bottles 失败: {e}"))
+    }
+
+    /// 构建 `bottles-cli run` 命令，供调用方在启动前追加环境变量
+    pub fn executable_command(
+        cli: &BottlesCli,
+        bottle: &str,
+        exe_path: &str,
+        args: &[String],
+    ) -> StdCommand {
         let use_exec_path = Path::new(exe_path).is_absolute();
         let mut cmd = if use_exec_path {
             cli.with_args_sync(&["run", "-e", exe_path, "-b", bottle])
@@ -119,8 +154,7 @@ impl BottlesService {
         if !args.is_empty() {
             cmd.arg("--").args(args);
         }
-
-        cmd.spawn().map_err(|e| format!("启动 Bottles 失败: {e}"))
+        cmd
     }
 
     fn parse_bottles_json(raw: &str) -> Vec<String> {
@@ -1,9 +1,10 @@
-#[cfg(target_os = "linux")]
 use super::BottlesService;
+use super::proton::{ProtonService, ProtonVersion};
+use crate::commands::error::AppError;
 use crate::commands::settings::SettingsState;
 use crate::models::{
     Capabilities, IntegrationOptions, IntegrationSettingsInput, IntegrationStatus,
-    SETTING_BOTTLES_DEFAULT, SETTING_BOTTLES_ENABLED,
+    SETTING_BOTTLES_DEFAULT, SETTING_BOTTLES_ENABLED, SettingsFieldError,
 };
 use std::sync::Arc;
 use tauri::State;
@@ -98,21 +99,76 @@ pub async fn set_bottles_integration_settings(
 
 /// 获取全部能力（按集成返回）
 #[tauri::command]
-pub async fn get_capabilities(state: State<'_, SettingsState>) -> Result<Capabilities, String> {
+pub async fn get_capabilities(state: State<'_, SettingsState>) -> Result<Capabilities, AppError> {
     let mut integrations = Vec::new();
     integrations.push(get_bottles_integration_status(state.db.clone()).await?);
     Ok(Capabilities { integrations })
 }
 
+/// 新建 bottle 可选的环境类型
+const BOTTLE_ENVIRONMENTS: &[&str] = &["gaming", "application", "custom"];
+
+/// 新建 Bottles bottle，返回刷新后的 Bottles 集成状态（含最新 bottle 列表）
+#[tauri::command]
+pub async fn create_bottle(
+    name: String,
+    environment: String,
+    state: State<'_, SettingsState>,
+) -> Result<IntegrationStatus, AppError> {
+    let name = name.trim();
+    let invalid = |message: String| AppError::Validation {
+        message: message.clone(),
+        fields: vec![SettingsFieldError {
+            field: "name".into(),
+            message,
+        }],
+    };
+    if name.is_empty() {
+        return Err(invalid("bottle 名称不能为空".to_string()));
+    }
+    if name.contains(['/', '\\', '\0', '\n']) || name.starts_with('.') {
+        return Err(invalid(format!("bottle 名称包含不允许的字符: {}", name)));
+    }
+    let environment = environment.trim().to_lowercase();
+    if !BOTTLE_ENVIRONMENTS.contains(&environment.as_str()) {
+        return Err(AppError::Validation {
+            message: format!("未知的 bottle 环境类型: {}", environment),
+            fields: vec![SettingsFieldError {
+                field: "environment".into(),
+                message: format!("可选: {}", BOTTLE_ENVIRONMENTS.join(", ")),
+            }],
+        });
+    }
+
+    let cli = BottlesService::detect_cli()
+        .await
+        .ok_or_else(|| AppError::RuntimeMissing("未检测到 Bottles CLI".to_string()))?;
+    let existing = BottlesService::list_bottles(&cli).await?;
+    if existing.iter().any(|b| b.eq_ignore_ascii_case(name)) {
+        return Err(invalid(format!("已存在同名 bottle: {}", name)));
+    }
+    BottlesService::create_bottle(&cli, name, &environment).await?;
+
+    Ok(get_bottles_integration_status(state.db.clone()).await?)
+}
+
+/// 列出已安装的 Steam Proton 版本（新版本在前）
+#[tauri::command]
+pub async fn list_proton_versions() -> Result<Vec<ProtonVersion>, AppError> {
+    tokio::task::spawn_blocking(ProtonService::list_versions)
+        .await
+        .map_err(|e| AppError::Other(format!("检测 Proton 失败: {}", e)))
+}
+
 /// 获取单个集成状态
 #[tauri::command]
 pub async fn get_integration_status(
     key: String,
     state: State<'_, SettingsState>,
-) -> Result<IntegrationStatus, String> {
+) -> Result<IntegrationStatus, AppError> {
     match key.as_str() {
-        "bottles" => get_bottles_integration_status(state.db.clone()).await,
-        _ => Err("未知集成".to_string()),
+        "bottles" => Ok(get_bottles_integration_status(state.db.clone()).await?),
+        _ => Err(AppError::NotFound("未知集成".to_string())),
     }
 }
 
@@ -121,10 +177,10 @@ pub async fn get_integration_status(
 pub async fn set_integration_settings(
     input: IntegrationSettingsInput,
     state: State<'_, SettingsState>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let mut db_lock = state.db.lock().await;
     match input.key.as_str() {
-        "bottles" => set_bottles_integration_settings(input, &mut *db_lock).await,
-        _ => Err("未知集成".to_string()),
+        "bottles" => Ok(set_bottles_integration_settings(input, &mut *db_lock).await?),
+        _ => Err(AppError::NotFound("未知集成".to_string())),
     }
 }
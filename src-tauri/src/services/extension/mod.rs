@@ -5,6 +5,7 @@
 pub mod bottles;
 
 pub mod integrations;
+pub mod proton;
 
 #[cfg(not(target_os = "linux"))]
 pub mod bottles {
@@ -41,6 +42,14 @@ pub mod bottles {
             Ok(Vec::new())
         }
 
+        pub async fn create_bottle(
+            _cli: &BottlesCli,
+            _name: &str,
+            _environment: &str,
+        ) -> Result<(), String> {
+            Err("Bottles 仅支持在 Linux 上运行".to_string())
+        }
+
         pub fn run_executable(
             _cli: &BottlesCli,
             _bottle: &str,
@@ -54,3 +63,4 @@ pub mod bottles {
 
 // 导出 BottlesService 以便上层直接使用 `crate::services::BottlesService`
 pub use bottles::BottlesService;
+pub use proton::ProtonService;
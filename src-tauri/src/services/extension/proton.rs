@@ -0,0 +1,150 @@
+//! Steam Proton 检测：在 Steam 安装目录的 `steamapps/common` 与 `compatibilitytools.d`
+//! 下查找带 `proton` 启动脚本的版本。
+
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::iter::Peekable;
+use std::path::{Path, PathBuf};
+use std::str::Chars;
+
+/// 已安装的 Proton 版本
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtonVersion {
+    /// 版本目录名（如 `Proton 9.0`、`GE-Proton9-20`），`GameConfig.proton_version` 保存此值
+    pub name: String,
+    /// 版本目录
+    pub path: String,
+    /// 第三方兼容工具（`compatibilitytools.d`，如 GE-Proton）；否则为 Steam 官方版本
+    pub custom: bool,
+}
+
+pub struct ProtonService;
+
+impl ProtonService {
+    /// 列出已安装的 Proton 版本（新版本在前）；非 Linux 平台返回空列表
+    pub fn list_versions() -> Vec<ProtonVersion> {
+        if !cfg!(target_os = "linux") {
+            return Vec::new();
+        }
+
+        let mut search_dirs = Vec::new();
+        for root in Self::steam_roots() {
+            search_dirs.push((root.join("steamapps").join("common"), false));
+            search_dirs.push((root.join("compatibilitytools.d"), true));
+        }
+        search_dirs.push((PathBuf::from("/usr/share/steam/compatibilitytools.d"), true));
+
+        let mut seen = Vec::new();
+        let mut versions = Vec::new();
+        for (dir, custom) in search_dirs {
+            let Ok(entries) = std::fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if !path.join("proton").is_file() {
+                    continue;
+                }
+                let canonical = crate::utils::path::canonicalize(&path);
+                if seen.contains(&canonical) {
+                    continue;
+                }
+                seen.push(canonical);
+                versions.push(ProtonVersion {
+                    name: entry.file_name().to_string_lossy().to_string(),
+                    path: path.to_string_lossy().to_string(),
+                    custom,
+                });
+            }
+        }
+
+        versions.sort_by(|a, b| natural_cmp(&b.name, &a.name));
+        versions
+    }
+
+    /// 按名称查找 Proton 版本；`name` 为空时返回最新版本
+    pub fn find_version(name: Option<&str>) -> Option<ProtonVersion> {
+        let versions = Self::list_versions();
+        match name.map(str::trim).filter(|n| !n.is_empty()) {
+            Some(name) => versions.into_iter().find(|v| v.name == name),
+            None => versions.into_iter().next(),
+        }
+    }
+
+    /// Steam 客户端安装目录（`STEAM_COMPAT_CLIENT_INSTALL_PATH`），未安装 Steam 时为空
+    pub fn steam_client_dir() -> Option<PathBuf> {
+        Self::steam_roots().into_iter().next()
+    }
+
+    /// 可能的 Steam 安装目录（原生包与 Flatpak），按优先级排列并去重
+    fn steam_roots() -> Vec<PathBuf> {
+        let Some(home) = std::env::var_os("HOME").map(PathBuf::from) else {
+            return Vec::new();
+        };
+        let candidates = [
+            home.join(".steam").join("root"),
+            home.join(".steam").join("steam"),
+            home.join(".local").join("share").join("Steam"),
+            home.join(".var")
+                .join("app")
+                .join("com.valvesoftware.Steam")
+                .join(".local")
+                .join("share")
+                .join("Steam"),
+        ];
+
+        let mut roots: Vec<PathBuf> = Vec::new();
+        for candidate in candidates {
+            if !is_steam_root(&candidate) {
+                continue;
+            }
+            let canonical = crate::utils::path::canonicalize(&candidate);
+            if !roots.contains(&canonical) {
+                roots.push(canonical);
+            }
+        }
+        roots
+    }
+}
+
+fn is_steam_root(dir: &Path) -> bool {
+    dir.join("steamapps").is_dir() || dir.join("compatibilitytools.d").is_dir()
+}
+
+/// 按数字大小比较名称中的数字段（`Proton 10.0` 排在 `Proton 9.0` 之后）
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a.chars().peekable(), b.chars().peekable());
+    loop {
+        match (a.peek().copied(), b.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let (x, y) = (take_number(&mut a), take_number(&mut b));
+                let ordering = x.len().cmp(&y.len()).then_with(|| x.cmp(&y));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(x), Some(y)) => {
+                let ordering = x.to_ascii_lowercase().cmp(&y.to_ascii_lowercase());
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+                a.next();
+                b.next();
+            }
+        }
+    }
+}
+
+/// 取出开头的连续数字（去掉前导零）
+fn take_number(chars: &mut Peekable<Chars<'_>>) -> String {
+    let mut digits = String::new();
+    while let Some(c) = chars.peek().copied().filter(char::is_ascii_digit) {
+        digits.push(c);
+        chars.next();
+    }
+    digits.trim_start_matches('0').to_string()
+}
@@ -3,9 +3,12 @@
 /// 合并自 `services/path.rs`（FileService）和 `services/utils.rs`（ArchiveService）。
 /// 路径工具函数（ensure_dir, canonicalize_path, is_within_dir）保留在 `services/path.rs`；
 /// 时间工具函数（now_unix_ms）保留在 `services/utils.rs`。
+use std::cell::Cell;
 use std::ffi::OsStr;
 use std::fs::File;
+use std::io::Read;
 use std::path::{Component, Path, PathBuf};
+use std::rc::Rc;
 
 use crate::utils::path::ensure_dir;
 use pelite::pe32::Pe as _;
@@ -13,6 +16,11 @@ use pelite::pe64::Pe as _;
 
 // ── FileService ──────────────────────────────────────────────────────────────
 
+/// 封面缩略图文件名（与完整封面位于同一目录）
+pub const COVER_THUMB_FILE: &str = "cover_thumb.webp";
+/// 缩略图最长边像素
+const COVER_THUMB_MAX_SIDE: u32 = 400;
+
 /// 文件管理服务
 pub struct FileService;
 
@@ -39,6 +47,23 @@ impl FileService {
             .join("Crash Reports")
     }
 
+    /// 获取默认 Wine 前缀目录
+    pub fn game_wine_prefix_dir(&self, container_root: &Path, profile_key: &str) -> PathBuf {
+        self.game_profile_dir(container_root, profile_key)
+            .join("Wine Prefix")
+    }
+
+    /// 获取 Proton 兼容数据目录（`STEAM_COMPAT_DATA_PATH`，其中的 `pfx` 为 Wine 前缀）
+    pub fn game_proton_compat_dir(&self, container_root: &Path, profile_key: &str) -> PathBuf {
+        self.game_profile_dir(container_root, profile_key)
+            .join("Proton")
+    }
+
+    /// 获取游戏存档备份目录（`backups/<profile_key>`）
+    pub fn game_save_backups_dir(&self, container_root: &Path, profile_key: &str) -> PathBuf {
+        container_root.join("backups").join(profile_key)
+    }
+
     /// 获取游戏配置文件路径
     pub fn game_config_path(&self, container_root: &Path, profile_key: &str) -> PathBuf {
         self.game_profile_dir(container_root, profile_key)
@@ -79,6 +104,22 @@ impl FileService {
         Ok(())
     }
 
+    /// 将 profile 目录复制到新 key，原目录不存在或新目录已存在时跳过，返回是否复制
+    pub fn copy_profile_dir(
+        &self,
+        container_root: &Path,
+        old_key: &str,
+        new_key: &str,
+    ) -> Result<bool, String> {
+        let old_dir = self.game_profile_dir(container_root, old_key);
+        let new_dir = self.game_profile_dir(container_root, new_key);
+        if old_key == new_key || !old_dir.is_dir() || new_dir.exists() {
+            return Ok(false);
+        }
+        ArchiveService::new().copy_dir_all(&old_dir, &new_dir)?;
+        Ok(true)
+    }
+
     /// 查找游戏封面图片
     pub fn find_cover_image(&self, game_path: &Path) -> Option<PathBuf> {
         // 尝试多个可能的封面位置
@@ -117,7 +158,97 @@ impl FileService {
         }
 
         // 尝试在icon目录中查找任何图片
-        self.find_image_in_dirs(game_path, &["icon", "icons", "www/icon", "www/icons"])
+        if let Some(path) =
+            self.find_image_in_dirs(game_path, &["icon", "icons", "www/icon", "www/icons"])
+        {
+            return Some(path);
+        }
+
+        // Linux 原生游戏：.desktop 的 Icon= 或可执行文件旁的同名图片
+        #[cfg(target_os = "linux")]
+        if let Some(path) = self.find_linux_icon(game_path) {
+            return Some(path);
+        }
+
+        None
+    }
+
+    /// 查找 Linux 原生游戏的图标：优先解析 .desktop 文件的 `Icon=`，其次找 ELF 旁的同名图片
+    #[cfg(target_os = "linux")]
+    fn find_linux_icon(&self, game_path: &Path) -> Option<PathBuf> {
+        let mut files: Vec<PathBuf> = std::fs::read_dir(game_path)
+            .ok()?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file())
+            .collect();
+        files.sort();
+
+        let from_desktop = files
+            .iter()
+            .filter(|path| path.extension().is_some_and(|ext| ext == "desktop"))
+            .filter_map(|path| std::fs::read_to_string(path).ok())
+            .filter_map(|content| parse_desktop_icon(&content))
+            .find_map(|icon| self.resolve_desktop_icon(game_path, &icon));
+        if from_desktop.is_some() {
+            return from_desktop;
+        }
+
+        files
+            .iter()
+            .filter(|path| crate::utils::path::is_linux_native_executable(path))
+            .find_map(|exe| self.find_sidecar_icon(exe))
+    }
+
+    /// 解析 `Icon=` 的值：绝对路径、相对游戏目录的路径或图标主题中的图标名
+    #[cfg(target_os = "linux")]
+    fn resolve_desktop_icon(&self, game_path: &Path, icon: &str) -> Option<PathBuf> {
+        let is_usable = |path: &Path| path.is_file() && self.is_image_file(path);
+
+        let direct = Path::new(icon);
+        if direct.is_absolute() || icon.contains('/') {
+            let path = if direct.is_absolute() {
+                direct.to_path_buf()
+            } else {
+                game_path.join(direct)
+            };
+            return is_usable(&path).then_some(path);
+        }
+
+        // 图标名：先找游戏目录，再按尺寸从大到小查找 hicolor 主题与 pixmaps
+        let names: Vec<String> = if self.is_image_file(direct) {
+            vec![icon.to_string()]
+        } else {
+            ["png", "jpg", "jpeg", "webp", "bmp", "ico"]
+                .iter()
+                .map(|ext| format!("{}.{}", icon, ext))
+                .collect()
+        };
+        let mut dirs = vec![game_path.to_path_buf()];
+        let data_dirs = std::env::var("XDG_DATA_DIRS")
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+        let mut icon_roots: Vec<PathBuf> = Vec::new();
+        if let Some(home) = std::env::var_os("HOME") {
+            icon_roots.push(PathBuf::from(home).join(".local/share/icons"));
+        }
+        icon_roots.extend(
+            data_dirs
+                .split(':')
+                .filter(|d| !d.is_empty())
+                .map(|d| Path::new(d).join("icons")),
+        );
+        for root in &icon_roots {
+            for size in ["512x512", "256x256", "128x128", "96x96", "64x64", "48x48"] {
+                dirs.push(root.join("hicolor").join(size).join("apps"));
+            }
+        }
+        dirs.push(PathBuf::from("/usr/share/pixmaps"));
+
+        dirs.iter()
+            .flat_map(|dir| names.iter().map(move |name| dir.join(name)))
+            .find(|path| is_usable(path))
     }
 
     /// 在icon目录中查找图片
@@ -178,6 +309,88 @@ impl FileService {
         None
     }
 
+    /// 从 macOS `.app` 包中提取 `CFBundleIconFile` 指向的 .icns 图标并保存为封面
+    ///
+    /// `game_dir` 可以是 `.app` 本身，也可以是包含 `.app` 的游戏目录。
+    #[cfg(target_os = "macos")]
+    pub fn save_app_bundle_icon_to_profile(
+        &self,
+        container_root: &Path,
+        profile_key: &str,
+        game_dir: &Path,
+    ) -> Option<PathBuf> {
+        let bundle = self.find_app_bundle(game_dir)?;
+        let contents = bundle.join("Contents");
+        let plist = plist::Value::from_file(contents.join("Info.plist")).ok()?;
+        let icon_name = plist
+            .as_dictionary()?
+            .get("CFBundleIconFile")?
+            .as_string()?
+            .to_string();
+        let icon_file = if icon_name.to_lowercase().ends_with(".icns") {
+            icon_name
+        } else {
+            format!("{}.icns", icon_name)
+        };
+        let png = self.convert_icns_to_png(&contents.join("Resources").join(icon_file))?;
+
+        // 先写入临时文件，再统一经过 save_cover_to_profile（同时生成缩略图）
+        let tmp = std::env::temp_dir().join(format!("{}-app-icon.png", profile_key));
+        std::fs::write(&tmp, png).ok()?;
+        let saved = self.save_cover_to_profile(container_root, profile_key, &tmp);
+        let _ = std::fs::remove_file(&tmp);
+
+        match saved {
+            Ok(path) => {
+                tracing::debug!(profile_key = %profile_key, "从 .app 包提取图标成功");
+                Some(path)
+            }
+            Err(e) => {
+                tracing::debug!(profile_key = %profile_key, error = %e, "保存 .app 图标失败");
+                None
+            }
+        }
+    }
+
+    /// 查找游戏目录本身或其下第一层的 `.app` 包
+    #[cfg(target_os = "macos")]
+    fn find_app_bundle(&self, game_dir: &Path) -> Option<PathBuf> {
+        let is_app = |path: &Path| {
+            path.is_dir()
+                && path
+                    .extension()
+                    .and_then(OsStr::to_str)
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("app"))
+        };
+        if is_app(game_dir) {
+            return Some(game_dir.to_path_buf());
+        }
+        let mut bundles: Vec<PathBuf> = std::fs::read_dir(game_dir)
+            .ok()?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| is_app(path))
+            .collect();
+        bundles.sort();
+        bundles.into_iter().next()
+    }
+
+    /// 将 .icns 中尺寸最大的可解码图像转换为 PNG
+    #[cfg(target_os = "macos")]
+    fn convert_icns_to_png(&self, icns_path: &Path) -> Option<Vec<u8>> {
+        let file = File::open(icns_path).ok()?;
+        let family = icns::IconFamily::read(std::io::BufReader::new(file)).ok()?;
+        let mut types = family.available_icons();
+        types.sort_by_key(|t| std::cmp::Reverse(t.pixel_width()));
+
+        types.into_iter().find_map(|icon_type| {
+            let image = family.get_icon_with_type(icon_type).ok()?;
+            let mut png = Vec::new();
+            image.write_png(&mut png).ok()?;
+            Some(png)
+        })
+    }
+
     /// 查找与可执行文件同名的图片
     fn find_sidecar_icon(&self, exe_path: &Path) -> Option<PathBuf> {
         let parent = exe_path.parent()?;
@@ -221,6 +434,7 @@ impl FileService {
 
         let target = profile_dir.join(format!("cover.{}", ext));
         std::fs::write(&target, data).ok()?;
+        self.refresh_cover_thumbnail(&target);
 
         tracing::debug!(
             profile_key = %profile_key,
@@ -271,13 +485,62 @@ impl FileService {
             false
         };
 
-        if !is_same {
+        // 重复扫描时源文件通常没有变化，内容一致就不再复制，缩略图也只在缺失时补生成
+        if is_same || same_file_contents(source_path, &target) {
+            self.ensure_cover_thumbnail(&target);
+        } else {
             std::fs::copy(source_path, &target).map_err(|e| format!("保存封面失败: {}", e))?;
+            self.refresh_cover_thumbnail(&target);
         }
+        remove_stale_covers(&profile_dir, &target, source_path);
+
+        Ok(target)
+    }
+
+    /// 为封面生成缩略图（最长边不超过 400px 的 WebP），返回缩略图路径
+    pub fn make_thumbnail(&self, cover: &Path) -> Result<PathBuf, String> {
+        let dir = cover
+            .parent()
+            .ok_or_else(|| format!("无效的封面路径: {}", cover.display()))?;
+        let img = image::open(cover).map_err(|e| format!("读取封面失败: {}", e))?;
+        let thumb = if img.width().max(img.height()) > COVER_THUMB_MAX_SIDE {
+            img.thumbnail(COVER_THUMB_MAX_SIDE, COVER_THUMB_MAX_SIDE)
+        } else {
+            img
+        };
 
+        // WebP 编码器只支持 8 位 RGB(A)
+        let target = dir.join(COVER_THUMB_FILE);
+        image::DynamicImage::ImageRgba8(thumb.to_rgba8())
+            .save_with_format(&target, image::ImageFormat::WebP)
+            .map_err(|e| format!("保存缩略图失败: {}", e))?;
         Ok(target)
     }
 
+    /// 重新生成缩略图；失败时删除旧缩略图，避免显示过期的封面
+    fn refresh_cover_thumbnail(&self, cover: &Path) {
+        if let Err(e) = self.make_thumbnail(cover) {
+            tracing::debug!(cover = %cover.display(), error = %e, "生成封面缩略图失败");
+            if let Some(dir) = cover.parent() {
+                let _ = std::fs::remove_file(dir.join(COVER_THUMB_FILE));
+            }
+        }
+    }
+
+    /// 返回封面对应的缩略图；缩略图不存在或比封面旧时返回 `None`
+    pub fn cover_thumbnail(&self, cover: &Path) -> Option<PathBuf> {
+        let thumb = cover.parent()?.join(COVER_THUMB_FILE);
+        let modified = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        (modified(&thumb)? >= modified(cover)?).then_some(thumb)
+    }
+
+    /// 缩略图缺失或过期时为已有封面补生成缩略图
+    pub fn ensure_cover_thumbnail(&self, cover: &Path) {
+        if self.cover_thumbnail(cover).is_none() {
+            self.refresh_cover_thumbnail(cover);
+        }
+    }
+
     /// 在指定目录中查找图片文件
     fn find_image_in_dirs(&self, base_path: &Path, dirs: &[&str]) -> Option<PathBuf> {
         for dir in dirs {
@@ -413,8 +676,47 @@ impl Default for FileService {
     }
 }
 
+/// 读取 .desktop 文件 `[Desktop Entry]` 段中的 `Icon=` 值
+#[cfg(target_os = "linux")]
+fn parse_desktop_icon(content: &str) -> Option<String> {
+    let mut in_entry = false;
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_entry = line == "[Desktop Entry]";
+            continue;
+        }
+        if in_entry
+            && let Some(value) = line.strip_prefix("Icon")
+            && let Some(value) = value.trim_start().strip_prefix('=')
+        {
+            let value = value.trim();
+            return (!value.is_empty()).then(|| value.to_string());
+        }
+    }
+    None
+}
+
 // ── ArchiveService ───────────────────────────────────────────────────────────
 
+/// 解压进度回调：`(已完成, 总量)`
+///
+/// zip 按条目计数；tar.gz 无法廉价地预知条目数，按已读取的压缩字节数计算。
+pub type ExtractProgress<'a> = &'a dyn Fn(u64, u64);
+
+/// 统计已读取字节数的 Reader，用于估算 tar.gz 解压进度
+struct CountingReader<R> {
+    inner: R,
+    read: Rc<Cell<u64>>,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.read.set(self.read.get() + n as u64);
+        Ok(n)
+    }
+}
+
 /// 解压服务
 /// 负责处理各种压缩格式的解压操作
 pub struct ArchiveService;
@@ -425,10 +727,21 @@ impl ArchiveService {
     }
 
     /// 解压 tar.gz 文件
-    pub fn extract_tar_gz(&self, archive_path: &Path, dest_dir: &Path) -> Result<(), String> {
+    pub fn extract_tar_gz(
+        &self,
+        archive_path: &Path,
+        dest_dir: &Path,
+        progress: Option<ExtractProgress>,
+    ) -> Result<(), String> {
         let file = File::open(archive_path)
             .map_err(|e| format!("无法打开文件 {}: {}", archive_path.display(), e))?;
-        let gz = flate2::read::GzDecoder::new(file);
+        let total = file.metadata().map(|m| m.len()).unwrap_or(0);
+        let read = Rc::new(Cell::new(0));
+        let counting = CountingReader {
+            inner: file,
+            read: read.clone(),
+        };
+        let gz = flate2::read::GzDecoder::new(counting);
         let mut archive = tar::Archive::new(gz);
 
         for entry in archive
@@ -448,25 +761,39 @@ impl ArchiveService {
             entry
                 .unpack(&out_path)
                 .map_err(|e| format!("tar 解压错误: {}", e))?;
+
+            if let Some(progress) = progress {
+                progress(read.get().min(total), total);
+            }
         }
 
         Ok(())
     }
 
     /// 解压 zip 文件
-    pub fn extract_zip(&self, archive_path: &Path, dest_dir: &Path) -> Result<(), String> {
+    pub fn extract_zip(
+        &self,
+        archive_path: &Path,
+        dest_dir: &Path,
+        progress: Option<ExtractProgress>,
+    ) -> Result<(), String> {
         let file = File::open(archive_path)
             .map_err(|e| format!("无法打开文件 {}: {}", archive_path.display(), e))?;
         let mut zip = zip::ZipArchive::new(file).map_err(|e| format!("zip 打开错误: {}", e))?;
 
+        let total = zip.len() as u64;
+        // 符号链接在全部文件写入后再创建，避免后续条目经由链接写到解压目录之外
+        let mut symlinks = Vec::new();
         for i in 0..zip.len() {
+            if let Some(progress) = progress {
+                progress(i as u64, total);
+            }
             let mut entry = zip
                 .by_index(i)
                 .map_err(|e| format!("zip 条目错误: {}", e))?;
-            let name = entry
-                .enclosed_name()
-                .ok_or_else(|| "zip 条目中包含不安全的路径".to_string())?;
-            let out_path = dest_dir.join(&name);
+            // 与 tar / 7z 一致使用 safe_join，拒绝任何 `..` 与绝对路径
+            let name = entry.name().replace('\\', "/");
+            let out_path = self.safe_join(dest_dir, Path::new(&name))?;
 
             if entry.is_dir() {
                 self.ensure_dir(&out_path)?;
@@ -477,15 +804,168 @@ impl ArchiveService {
                 self.ensure_dir(parent)?;
             }
 
+            // S_IFLNK：条目内容为链接目标
+            if entry
+                .unix_mode()
+                .is_some_and(|mode| mode & 0o170000 == 0o120000)
+            {
+                let mut target = String::new();
+                entry
+                    .read_to_string(&mut target)
+                    .map_err(|e| format!("zip 解压错误: {}", e))?;
+                symlinks.push((out_path, target));
+                continue;
+            }
+
             let mut out = File::create(&out_path).map_err(|e| format!("zip 写入错误: {}", e))?;
             std::io::copy(&mut entry, &mut out).map_err(|e| format!("zip 解压错误: {}", e))?;
+
+            // 还原 Unix 权限位，否则 Linux 版 nw / 启动脚本会失去可执行权限
+            #[cfg(unix)]
+            if let Some(mode) = entry.unix_mode() {
+                use std::os::unix::fs::PermissionsExt;
+                let _ = std::fs::set_permissions(
+                    &out_path,
+                    std::fs::Permissions::from_mode(mode & 0o7777),
+                );
+            }
+        }
+
+        for (link, target) in symlinks {
+            self.restore_symlink(dest_dir, &link, &target);
+        }
+
+        if let Some(progress) = progress {
+            progress(total, total);
+        }
+
+        Ok(())
+    }
+
+    /// 解压 7z 文件
+    pub fn extract_7z(&self, archive_path: &Path, dest_dir: &Path) -> Result<(), String> {
+        let mut reader =
+            sevenz_rust::SevenZReader::open(archive_path, sevenz_rust::Password::empty())
+                .map_err(|e| format!("7z 打开错误: {}", e))?;
+
+        // 回调只能返回 sevenz_rust::Error，这里记录自己的错误后中止遍历
+        let mut failure: Option<String> = None;
+        reader
+            .for_each_entries(|entry, data| {
+                let name = entry.name().replace('\\', "/");
+                let out_path = match self.safe_join(dest_dir, Path::new(&name)) {
+                    Ok(path) => path,
+                    Err(e) => {
+                        failure = Some(e);
+                        return Ok(false);
+                    }
+                };
+
+                let result = if entry.is_directory() {
+                    self.ensure_dir(&out_path)
+                } else {
+                    out_path
+                        .parent()
+                        .map_or(Ok(()), |parent| self.ensure_dir(parent))
+                        .and_then(|_| {
+                            File::create(&out_path).map_err(|e| format!("7z 写入错误: {}", e))
+                        })
+                        .and_then(|mut out| {
+                            std::io::copy(data, &mut out)
+                                .map(|_| ())
+                                .map_err(|e| format!("7z 解压错误: {}", e))
+                        })
+                };
+
+                match result {
+                    Ok(()) => Ok(true),
+                    Err(e) => {
+                        failure = Some(e);
+                        Ok(false)
+                    }
+                }
+            })
+            .map_err(|e| format!("7z 解压错误: {}", e))?;
+
+        match failure {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// 将文件或目录打包为 zip：`entries` 为 `(磁盘路径, 压缩包内路径)`，目录递归添加
+    ///
+    /// 先写入临时文件再重命名，失败时不会留下不完整的压缩包；符号链接会被跳过。
+    pub fn create_zip(&self, dest: &Path, entries: &[(PathBuf, String)]) -> Result<(), String> {
+        if let Some(parent) = dest.parent() {
+            self.ensure_dir(parent)?;
+        }
+        let tmp = dest.with_extension("zip.tmp");
+        let file =
+            File::create(&tmp).map_err(|e| format!("无法创建文件 {}: {}", tmp.display(), e))?;
+        let mut zip = zip::ZipWriter::new(file);
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+
+        let result = entries
+            .iter()
+            .try_for_each(|(path, name)| self.add_to_zip(&mut zip, path, name, options))
+            .and_then(|_| {
+                zip.finish()
+                    .map(|_| ())
+                    .map_err(|e| format!("zip 写入错误: {}", e))
+            })
+            .and_then(|_| {
+                std::fs::rename(&tmp, dest).map_err(|e| format!("保存压缩包失败: {}", e))
+            });
+        if result.is_err() {
+            let _ = std::fs::remove_file(&tmp);
         }
+        result
+    }
 
+    fn add_to_zip(
+        &self,
+        zip: &mut zip::ZipWriter<File>,
+        path: &Path,
+        name: &str,
+        options: zip::write::SimpleFileOptions,
+    ) -> Result<(), String> {
+        let file_type = std::fs::symlink_metadata(path)
+            .map_err(|e| format!("读取文件信息失败 {}: {}", path.display(), e))?
+            .file_type();
+
+        if file_type.is_dir() {
+            zip.add_directory(format!("{}/", name), options)
+                .map_err(|e| format!("zip 写入错误: {}", e))?;
+            for entry in std::fs::read_dir(path).map_err(|e| format!("读取目录错误: {}", e))?
+            {
+                let entry = entry.map_err(|e| format!("读取目录条目错误: {}", e))?;
+                let child = format!("{}/{}", name, entry.file_name().to_string_lossy());
+                self.add_to_zip(zip, &entry.path(), &child, options)?;
+            }
+        } else if file_type.is_file() {
+            zip.start_file(name, options)
+                .map_err(|e| format!("zip 写入错误: {}", e))?;
+            let mut src =
+                File::open(path).map_err(|e| format!("无法打开文件 {}: {}", path.display(), e))?;
+            std::io::copy(&mut src, zip).map_err(|e| format!("zip 写入错误: {}", e))?;
+        }
         Ok(())
     }
 
     /// 根据文件扩展名自动选择解压方法
     pub fn extract_auto(&self, archive_path: &Path, dest_dir: &Path) -> Result<(), String> {
+        self.extract_auto_with_progress(archive_path, dest_dir, None)
+    }
+
+    /// 根据文件扩展名自动选择解压方法，并在解压过程中回调进度（7z 不支持进度）
+    pub fn extract_auto_with_progress(
+        &self,
+        archive_path: &Path,
+        dest_dir: &Path,
+        progress: Option<ExtractProgress>,
+    ) -> Result<(), String> {
         let ext = archive_path
             .extension()
             .and_then(OsStr::to_str)
@@ -498,12 +978,13 @@ impl ArchiveService {
             .ok_or_else(|| "无效的文件名".to_string())?;
 
         if file_name.ends_with(".tar.gz") {
-            return self.extract_tar_gz(archive_path, dest_dir);
+            return self.extract_tar_gz(archive_path, dest_dir, progress);
         }
 
         match ext {
-            "zip" => self.extract_zip(archive_path, dest_dir),
-            "gz" => self.extract_tar_gz(archive_path, dest_dir),
+            "zip" => self.extract_zip(archive_path, dest_dir, progress),
+            "gz" => self.extract_tar_gz(archive_path, dest_dir, progress),
+            "7z" => self.extract_7z(archive_path, dest_dir),
             _ => Err(format!("不支持的压缩格式: {}", ext)),
         }
     }
@@ -558,6 +1039,61 @@ impl ArchiveService {
         Ok(out)
     }
 
+    /// 在解压目录内重建符号链接（如 macOS framework 的 `Versions/Current`）；
+    /// 目标为绝对路径或指向解压目录之外时跳过
+    fn restore_symlink(&self, dest_dir: &Path, link: &Path, target: &str) {
+        let target_path = Path::new(target);
+        if !self.symlink_target_inside(dest_dir, link, target_path) {
+            tracing::warn!(link = %link.display(), target, "跳过指向解压目录之外的符号链接");
+            return;
+        }
+
+        #[cfg(unix)]
+        if let Err(e) = std::os::unix::fs::symlink(target_path, link) {
+            tracing::warn!(link = %link.display(), error = %e, "创建符号链接失败");
+        }
+        #[cfg(not(unix))]
+        tracing::warn!(link = %link.display(), target, "当前平台不还原符号链接，已跳过");
+    }
+
+    /// 按实际路径逐段解析链接目标，确认不会离开解压目录
+    ///
+    /// `..` 只允许出现在已存在的目录之后，避免之后创建的链接改变它的指向。
+    fn symlink_target_inside(&self, dest_dir: &Path, link: &Path, target: &Path) -> bool {
+        let Ok(root) = dest_dir.canonicalize() else {
+            return false;
+        };
+        let Some(mut current) = link.parent().and_then(|p| p.canonicalize().ok()) else {
+            return false;
+        };
+        if !current.starts_with(&root) {
+            return false;
+        }
+
+        for comp in target.components() {
+            match comp {
+                Component::Normal(p) => {
+                    current.push(p);
+                    if let Ok(real) = current.canonicalize() {
+                        current = real;
+                    }
+                }
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if !current.is_dir() {
+                        return false;
+                    }
+                    current.pop();
+                }
+                Component::Prefix(_) | Component::RootDir => return false,
+            }
+            if !current.starts_with(&root) {
+                return false;
+            }
+        }
+        true
+    }
+
     /// 如果目录存在则删除
     pub fn remove_dir_if_exists(&self, path: &Path) -> Result<(), String> {
         if path.exists() {
@@ -585,7 +1121,7 @@ impl ArchiveService {
     }
 
     /// 递归复制目录
-    fn copy_dir_all(&self, src: &Path, dst: &Path) -> Result<(), String> {
+    pub fn copy_dir_all(&self, src: &Path, dst: &Path) -> Result<(), String> {
         self.ensure_dir(dst)?;
         for entry in std::fs::read_dir(src).map_err(|e| format!("读取目录错误: {}", e))? {
             let entry = entry.map_err(|e| format!("读取目录条目错误: {}", e))?;
@@ -609,3 +1145,124 @@ impl Default for ArchiveService {
         Self::new()
     }
 }
+
+/// 两个文件大小相同且内容一致
+fn same_file_contents(a: &Path, b: &Path) -> bool {
+    let (Ok(meta_a), Ok(meta_b)) = (std::fs::metadata(a), std::fs::metadata(b)) else {
+        return false;
+    };
+    if !meta_a.is_file() || !meta_b.is_file() || meta_a.len() != meta_b.len() {
+        return false;
+    }
+    matches!((std::fs::read(a), std::fs::read(b)), (Ok(x), Ok(y)) if x == y)
+}
+
+/// 删除扩展名与当前封面不同的旧 `cover.*`，避免 profile 中同时留下 cover.png 与 cover.jpg
+fn remove_stale_covers(profile_dir: &Path, current: &Path, source: &Path) {
+    let Ok(entries) = std::fs::read_dir(profile_dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let is_cover = path.file_stem().is_some_and(|stem| stem == "cover")
+            && image::ImageFormat::from_path(&path).is_ok();
+        if is_cover && path != current && path != source && path.is_file() {
+            let _ = std::fs::remove_file(&path);
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::os::unix::fs::PermissionsExt;
+    use zip::write::SimpleFileOptions;
+
+    /// 在 `dir` 中生成 test.zip，条目由 `build` 写入
+    fn make_zip(dir: &Path, build: impl FnOnce(&mut zip::ZipWriter<File>)) -> PathBuf {
+        let path = dir.join("test.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&path).unwrap());
+        build(&mut writer);
+        writer.finish().unwrap();
+        path
+    }
+
+    #[test]
+    fn extract_zip_keeps_executable_bit() {
+        let tmp = tempfile::tempdir().unwrap();
+        let archive = make_zip(tmp.path(), |zip| {
+            let options = SimpleFileOptions::default().unix_permissions(0o755);
+            zip.start_file("nw", options).unwrap();
+            zip.write_all(b"#!/bin/sh\n").unwrap();
+        });
+        let dest = tmp.path().join("out");
+
+        ArchiveService::new()
+            .extract_zip(&archive, &dest, None)
+            .unwrap();
+
+        let mode = std::fs::metadata(dest.join("nw"))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o755);
+    }
+
+    #[test]
+    fn extract_zip_rejects_parent_dir_entries() {
+        let tmp = tempfile::tempdir().unwrap();
+        let archive = make_zip(tmp.path(), |zip| {
+            zip.start_file("../evil", SimpleFileOptions::default())
+                .unwrap();
+            zip.write_all(b"evil").unwrap();
+        });
+        let dest = tmp.path().join("out");
+
+        let result = ArchiveService::new().extract_zip(&archive, &dest, None);
+
+        assert!(result.is_err());
+        assert!(!tmp.path().join("evil").exists());
+    }
+
+    #[test]
+    fn extract_zip_skips_symlinks_leaving_dest_dir() {
+        let tmp = tempfile::tempdir().unwrap();
+        let outside = tmp.path().join("outside");
+        std::fs::create_dir(&outside).unwrap();
+        let archive = make_zip(tmp.path(), |zip| {
+            let options = SimpleFileOptions::default();
+            zip.add_symlink("parent", "../outside", options).unwrap();
+            zip.add_symlink("absolute", outside.to_str().unwrap(), options)
+                .unwrap();
+        });
+        let dest = tmp.path().join("out");
+
+        ArchiveService::new()
+            .extract_zip(&archive, &dest, None)
+            .unwrap();
+
+        assert!(std::fs::symlink_metadata(dest.join("parent")).is_err());
+        assert!(std::fs::symlink_metadata(dest.join("absolute")).is_err());
+    }
+
+    #[test]
+    fn extract_zip_recreates_symlinks_inside_dest_dir() {
+        let tmp = tempfile::tempdir().unwrap();
+        let archive = make_zip(tmp.path(), |zip| {
+            let options = SimpleFileOptions::default();
+            zip.start_file("Versions/A/lib", options).unwrap();
+            zip.write_all(b"lib").unwrap();
+            zip.add_symlink("Versions/Current", "A", options).unwrap();
+        });
+        let dest = tmp.path().join("out");
+
+        ArchiveService::new()
+            .extract_zip(&archive, &dest, None)
+            .unwrap();
+
+        let link = dest.join("Versions").join("Current");
+        assert_eq!(std::fs::read_link(&link).unwrap(), Path::new("A"));
+        assert_eq!(std::fs::read(link.join("lib")).unwrap(), b"lib");
+    }
+}
@@ -0,0 +1,65 @@
+//! 启动参数占位符：在启动前把 `${game_dir}` 等替换为该游戏的实际路径。
+//!
+//! 只替换已知占位符，其它 `${...}` 原样保留，参数中的字面 `$` 不受影响。
+
+/// 可用的占位符名称
+pub const ARG_TEMPLATE_TOKENS: &[&str] =
+    &["game_dir", "profile_dir", "user_data_dir", "container_root"];
+
+/// 替换参数中的已知占位符；`vars` 为 (占位符名称, 值)
+pub fn expand(arg: &str, vars: &[(&str, String)]) -> String {
+    let mut result = String::with_capacity(arg.len());
+    let mut rest = arg;
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let value = after.find('}').and_then(|end| {
+            let name = &after[..end];
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| (value, end))
+        });
+        match value {
+            Some((value, end)) => {
+                result.push_str(value);
+                rest = &after[end + 1..];
+            }
+            None => {
+                result.push_str("${");
+                rest = after;
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// 参数中形似占位符（`${小写字母与下划线}`）但不是已知占位符的名称
+pub fn unknown_tokens(arg: &str) -> Vec<&str> {
+    let mut unknown = Vec::new();
+    let mut rest = arg;
+    while let Some(start) = rest.find("${") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find('}') else {
+            break;
+        };
+        let name = &after[..end];
+        if !name.is_empty()
+            && name.chars().all(|c| c.is_ascii_lowercase() || c == '_')
+            && !ARG_TEMPLATE_TOKENS.contains(&name)
+        {
+            unknown.push(name);
+        }
+        rest = &after[end + 1..];
+    }
+    unknown
+}
+
+/// 可用占位符列表（用于错误信息）
+pub fn token_list() -> String {
+    ARG_TEMPLATE_TOKENS
+        .iter()
+        .map(|token| format!("${{{}}}", token))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,62 @@
+//! 启动钩子：游戏启动前、退出后执行用户配置的命令（需在全局设置中允许）。
+
+use std::path::Path;
+use std::process::Command;
+
+/// 钩子命令中可用的游戏目录环境变量
+pub const HOOK_ENV_GAME_DIR: &str = "GAMEMANAGER_GAME_DIR";
+/// 钩子命令中可用的 profile 目录环境变量
+pub const HOOK_ENV_PROFILE_DIR: &str = "GAMEMANAGER_PROFILE_DIR";
+/// 钩子命令中可用的游戏 ID 环境变量
+pub const HOOK_ENV_GAME_ID: &str = "GAMEMANAGER_GAME_ID";
+
+/// 钩子执行上下文
+#[derive(Debug, Clone)]
+pub struct HookContext {
+    pub game_id: String,
+    pub game_dir: String,
+    pub profile_dir: String,
+}
+
+/// 在游戏目录下通过系统 shell 执行钩子命令（阻塞），非零退出码视为失败。
+///
+/// `name` 仅用于错误信息与日志；空命令直接返回成功。
+pub fn run_hook(name: &str, command: &str, ctx: &HookContext) -> Result<(), String> {
+    let command = command.trim();
+    if command.is_empty() {
+        return Ok(());
+    }
+
+    let mut cmd = shell_command(command);
+    cmd.current_dir(Path::new(&ctx.game_dir))
+        .env(HOOK_ENV_GAME_ID, &ctx.game_id)
+        .env(HOOK_ENV_GAME_DIR, &ctx.game_dir)
+        .env(HOOK_ENV_PROFILE_DIR, &ctx.profile_dir);
+
+    tracing::info!(game_id = %ctx.game_id, hook = name, command, "执行启动钩子");
+    let status = cmd
+        .status()
+        .map_err(|e| format!("执行{}失败: {}", name, e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(match status.code() {
+            Some(code) => format!("{}退出码非零: {}", name, code),
+            None => format!("{}被信号终止", name),
+        })
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.args(["/C", command]);
+    cmd
+}
+
+#[cfg(not(target_os = "windows"))]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.args(["-c", command]);
+    cmd
+}
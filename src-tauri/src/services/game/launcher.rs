@@ -1,8 +1,39 @@
 use crate::db::schema::Game;
-use crate::models::{EngineType, GameConfig, LaunchResult};
+use crate::models::game::XDG_DIR_VARS;
+use crate::models::{EngineType, ExternalToolPaths, GameConfig, LaunchResult, SandboxHome};
+use crate::services::game::arg_template;
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
 use std::process::{Child, Command};
 
+/// 启动 NW.js 游戏所用的运行时
+pub struct NwjsRuntime {
+    pub dir: PathBuf,
+    /// 远程调试（devtools）端口，仅 SDK 版运行时支持
+    pub devtools_port: Option<u16>,
+}
+
+/// 全局设置中与启动相关的部分
+#[derive(Default)]
+pub struct LaunchSettings {
+    /// 外部工具路径，wine 未配置时从 PATH 查找
+    pub tools: ExternalToolPaths,
+    /// 该引擎类型的默认启动参数，放在游戏参数之前
+    pub default_args: Vec<String>,
+    /// 全局设置是否在终端窗口中启动，游戏配置中的设置优先
+    pub launch_in_terminal: bool,
+}
+
+impl LaunchSettings {
+    /// 本次启动是否在终端窗口中运行
+    pub fn in_terminal(&self, config: Option<&GameConfig>) -> bool {
+        config
+            .and_then(|c| c.launch_in_terminal)
+            .unwrap_or(self.launch_in_terminal)
+    }
+}
+
 /// 游戏启动服务
 pub struct LauncherService {
     file_service: crate::services::fs::FileService,
@@ -12,9 +43,88 @@ struct LaunchOptions {
     runner: String,
     entry_path: Option<String>,
     args: Vec<String>,
-    sandbox_home: bool,
+    env: HashMap<String, String>,
+    sandbox_home: SandboxHome,
     use_bottles: bool,
     bottle_name: Option<String>,
+    proton_version: Option<String>,
+    wine_binary: Option<String>,
+    easyrpg_binary: Option<String>,
+    wine_prefix: Option<String>,
+    locale: Option<String>,
+    use_gamemode: bool,
+    use_mangohud: bool,
+    launch_in_terminal: bool,
+}
+
+/// 一次启动实际使用的启动实现
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LaunchPath {
+    Nwjs,
+    Mkxpz,
+    Proton,
+    Bottles,
+    RpgMaker,
+    RenPy,
+    RpgMaker2000,
+    WolfRpg,
+    KiriKiri,
+    Godot,
+    Unity,
+    Other,
+}
+
+/// 根据引擎类型、运行器和可用运行时选择唯一的启动实现
+fn launch_path(
+    engine_type: &EngineType,
+    options: &LaunchOptions,
+    has_nwjs: bool,
+    has_mkxpz: bool,
+) -> LaunchPath {
+    let use_nwjs = has_nwjs
+        && (options.runner == "nwjs"
+            || (options.runner == "auto"
+                && matches!(
+                    engine_type,
+                    EngineType::RpgMakerMV
+                        | EngineType::RpgMakerMZ
+                        | EngineType::Tyrano
+                        | EngineType::Html
+                )));
+    if use_nwjs {
+        return LaunchPath::Nwjs;
+    }
+
+    let use_mkxpz = has_mkxpz
+        && (options.runner == "mkxpz"
+            || (options.runner == "auto"
+                && matches!(
+                    engine_type,
+                    EngineType::RpgMakerXP | EngineType::RpgMakerVX | EngineType::RpgMakerVXAce
+                )));
+    if use_mkxpz {
+        return LaunchPath::Mkxpz;
+    }
+    if options.runner == "proton" {
+        return LaunchPath::Proton;
+    }
+    if options.use_bottles {
+        return LaunchPath::Bottles;
+    }
+
+    match engine_type {
+        EngineType::RpgMakerXP | EngineType::RpgMakerVX | EngineType::RpgMakerVXAce => {
+            LaunchPath::RpgMaker
+        }
+        EngineType::RenPy => LaunchPath::RenPy,
+        EngineType::RpgMaker2000 => LaunchPath::RpgMaker2000,
+        EngineType::WolfRpg => LaunchPath::WolfRpg,
+        EngineType::KiriKiri => LaunchPath::KiriKiri,
+        EngineType::Godot => LaunchPath::Godot,
+        EngineType::Unity => LaunchPath::Unity,
+        // Other 及未识别引擎使用通用启动
+        _ => LaunchPath::Other,
+    }
 }
 
 impl LauncherService {
@@ -34,19 +144,39 @@ impl LauncherService {
         nwjs_runtime_dir: Option<&Path>,
         config: Option<&GameConfig>,
     ) -> Result<LaunchResult, String> {
-        self.launch_game_with_runtimes(game, container_root, nwjs_runtime_dir, None, config)
-            .await
+        let nwjs_runtime = nwjs_runtime_dir.map(|dir| NwjsRuntime {
+            dir: dir.to_path_buf(),
+            devtools_port: None,
+        });
+        let child = self
+            .launch_game_with_runtimes(
+                game,
+                container_root,
+                nwjs_runtime.as_ref(),
+                None,
+                &LaunchSettings::default(),
+                config,
+            )
+            .await?;
+        Ok(LaunchResult {
+            pid: child.id(),
+            devtools_port: None,
+            tracked: true,
+        })
     }
 
-    /// 启动游戏（完整运行时参数版，支持 NW.js 和 mkxp-z）
+    /// 启动游戏（完整运行时参数版，支持 NW.js、mkxp-z、EasyRPG 和 Wine），返回子进程供调用方跟踪退出
+    ///
+    /// `settings` 为全局设置中的外部工具路径与引擎默认参数。
     pub async fn launch_game_with_runtimes(
         &self,
         game: &Game,
         container_root: &Path,
-        nwjs_runtime_dir: Option<&Path>,
+        nwjs_runtime: Option<&NwjsRuntime>,
         mkxpz_runtime_dir: Option<&Path>,
+        settings: &LaunchSettings,
         config: Option<&GameConfig>,
-    ) -> Result<LaunchResult, String> {
+    ) -> Result<Child, String> {
         // 检查游戏路径是否存在
         let game_path = Path::new(&game.game_path);
         if !game_path.exists() {
@@ -57,55 +187,87 @@ impl LauncherService {
         self.file_service
             .ensure_game_dirs(container_root, &game.profile_key)?;
 
-        let options = self.resolve_launch_options(config);
+        let mut options = self.resolve_launch_options(config, &settings.default_args);
+        let configured = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+        };
+        options.wine_binary = configured(&settings.tools.wine_binary);
+        options.easyrpg_binary = configured(&settings.tools.easyrpg_binary);
+        options.launch_in_terminal = settings.in_terminal(config);
+        self.expand_arg_templates(&mut options, game, container_root);
+        // 手动指定的入口不存在时报错，不回退到自动查找，避免误启动配置工具等其它程序
+        if let Some(entry) = options.entry_path.as_deref()
+            && self.resolve_entry_path(game_path, Some(entry)).is_none()
+        {
+            return Err(format!("入口文件不存在: {}", entry));
+        }
 
         // 根据引擎类型和运行器选择启动策略
-        let engine_type = EngineType::from_str(&game.engine_type);
-        let use_nwjs = nwjs_runtime_dir.is_some()
-            && (options.runner == "nwjs"
-                || (options.runner == "auto"
-                    && (matches!(engine_type, EngineType::RpgMakerMV | EngineType::RpgMakerMZ)
-                        || matches!(engine_type, EngineType::Html))));
-
-        let use_mkxpz = !use_nwjs
-            && mkxpz_runtime_dir.is_some()
-            && (options.runner == "mkxpz"
-                || (options.runner == "auto"
-                    && matches!(
-                        engine_type,
-                        EngineType::RpgMakerVX | EngineType::RpgMakerVXAce
-                    )));
-
-        let child = if use_nwjs {
-            self.launch_nwjs_game(game, game_path, container_root, nwjs_runtime_dir, &options)
-                .await?
-        } else if use_mkxpz {
-            self.launch_mkxpz_game(game, game_path, container_root, mkxpz_runtime_dir, &options)
-                .await?
-        } else {
-            match engine_type {
-                EngineType::RpgMakerVX | EngineType::RpgMakerVXAce => {
-                    self.launch_rpg_maker_game(game, game_path, container_root, &options)
-                        .await?
-                }
-                EngineType::RenPy => {
-                    self.launch_renpy_game(game, game_path, container_root, &options)
-                        .await?
-                }
-                // Unity、Godot、Other 及未识别引擎使用通用启动
-                _ => {
-                    self.launch_other_game(game, game_path, container_root, &options)
-                        .await?
-                }
+        let launch_path = launch_path(
+            &EngineType::from_str(&game.engine_type),
+            &options,
+            nwjs_runtime.is_some(),
+            mkxpz_runtime_dir.is_some(),
+        );
+        let child = match launch_path {
+            LaunchPath::Nwjs => {
+                self.launch_nwjs_game(game, game_path, container_root, nwjs_runtime, &options)
+                    .await?
+            }
+            LaunchPath::Mkxpz => {
+                self.launch_mkxpz_game(game, game_path, container_root, mkxpz_runtime_dir, &options)
+                    .await?
+            }
+            LaunchPath::Proton => {
+                self.launch_proton_game(game, game_path, container_root, &options)
+                    .await?
+            }
+            LaunchPath::Bottles => {
+                self.launch_bottles_game(game, game_path, container_root, &options)
+                    .await?
+            }
+            LaunchPath::RpgMaker => {
+                self.launch_rpg_maker_game(game, game_path, container_root, &options)
+                    .await?
+            }
+            LaunchPath::RenPy => {
+                self.launch_renpy_game(game, game_path, container_root, &options)
+                    .await?
+            }
+            LaunchPath::RpgMaker2000 => {
+                self.launch_rpg_maker_2000_game(game, game_path, container_root, &options)
+                    .await?
+            }
+            LaunchPath::WolfRpg => {
+                self.launch_wolf_rpg_game(game, game_path, container_root, &options)
+                    .await?
+            }
+            LaunchPath::KiriKiri => {
+                self.launch_kirikiri_game(game, game_path, container_root, &options)
+                    .await?
+            }
+            LaunchPath::Godot => {
+                self.launch_godot_game(game, game_path, container_root, &options)
+                    .await?
+            }
+            LaunchPath::Unity => {
+                self.launch_unity_game(game, game_path, container_root, &options)
+                    .await?
+            }
+            LaunchPath::Other => {
+                self.launch_other_game(game, game_path, container_root, &options)
+                    .await?
             }
         };
 
-        let pid = child.id();
-
-        Ok(LaunchResult { pid })
+        Ok(child)
     }
 
-    /// 启动 RPG Maker (VX/VX Ace) 游戏
+    /// 启动 RPG Maker (XP/VX/VX Ace) 游戏
     async fn launch_rpg_maker_game(
         &self,
         game: &Game,
@@ -117,16 +279,132 @@ impl LauncherService {
         let exe_path = self.find_rpg_maker_executable(game_path, options.entry_path.as_deref())?;
 
         // 设置工作目录为游戏目录
-        let mut cmd = Command::new(&exe_path);
+        self.launch_executable(game, &exe_path, game_path, container_root, options)
+    }
+
+    /// 启动 RPG Maker 2000/2003 游戏
+    ///
+    /// 配置了 EasyRPG Player 时用它原生运行，否则运行游戏自带的 RPG_RT.exe（Linux 上经由 wine）。
+    async fn launch_rpg_maker_2000_game(
+        &self,
+        game: &Game,
+        game_path: &Path,
+        container_root: &Path,
+        options: &LaunchOptions,
+    ) -> Result<Child, String> {
+        let Some(easyrpg) = options.easyrpg_binary.as_deref() else {
+            let exe_path = self
+                .resolve_entry_path(game_path, options.entry_path.as_deref())
+                .or_else(|| Some(game_path.join("RPG_RT.exe")).filter(|path| path.is_file()))
+                .ok_or_else(|| "未找到 RPG_RT.exe，请在设置中指定 EasyRPG Player".to_string())?;
+            return self.launch_executable(game, &exe_path, game_path, container_root, options);
+        };
+
+        let player = self
+            .find_program(easyrpg)
+            .ok_or_else(|| format!("未找到 EasyRPG Player: {}，请检查设置中的路径", easyrpg))?;
+        let mut cmd = self.game_command(player, options);
+        cmd.arg("--project-path").arg(game_path);
         cmd.current_dir(game_path);
 
         self.apply_home_sandbox(&mut cmd, container_root, &game.profile_key, options);
+        self.apply_env(&mut cmd, options);
         self.apply_args(&mut cmd, options);
 
-        // 启动进程
-        let child = cmd.spawn().map_err(|e| format!("启动游戏失败: {}", e))?;
+        self.spawn(&mut cmd, options)
+            .map_err(|e| format!("启动 EasyRPG Player 失败: {}", e))
+    }
 
-        Ok(child)
+    /// 启动 WOLF RPG 游戏（Game.exe，Linux 上经由 wine）
+    async fn launch_wolf_rpg_game(
+        &self,
+        game: &Game,
+        game_path: &Path,
+        container_root: &Path,
+        options: &LaunchOptions,
+    ) -> Result<Child, String> {
+        let exe_path = match self.resolve_entry_path(game_path, options.entry_path.as_deref()) {
+            Some(path) => path,
+            None => Some(game_path.join("Game.exe"))
+                .filter(|path| path.is_file())
+                .ok_or_else(|| "未找到 WOLF RPG 的 Game.exe".to_string())?,
+        };
+        self.launch_executable(game, &exe_path, game_path, container_root, options)
+    }
+
+    /// 启动吉里吉里游戏（Windows 可执行文件 + .xp3 数据包）
+    async fn launch_kirikiri_game(
+        &self,
+        game: &Game,
+        game_path: &Path,
+        container_root: &Path,
+        options: &LaunchOptions,
+    ) -> Result<Child, String> {
+        let exe_path = self.find_kirikiri_executable(game_path, options.entry_path.as_deref())?;
+        self.launch_executable(game, &exe_path, game_path, container_root, options)
+    }
+
+    /// 启动 Godot 游戏（导出的可执行文件 + 同名 .pck）
+    async fn launch_godot_game(
+        &self,
+        game: &Game,
+        game_path: &Path,
+        container_root: &Path,
+        options: &LaunchOptions,
+    ) -> Result<Child, String> {
+        let exe_path = self.find_godot_executable(game_path, options.entry_path.as_deref())?;
+
+        // Godot 按可执行文件所在目录查找同名 .pck
+        let working_dir = exe_path.parent().unwrap_or(game_path).to_path_buf();
+        self.launch_executable(game, &exe_path, &working_dir, container_root, options)
+    }
+
+    /// 启动 Unity 游戏（工作目录为游戏根目录，Unity 从中查找 `<名称>_Data`）
+    async fn launch_unity_game(
+        &self,
+        game: &Game,
+        game_path: &Path,
+        container_root: &Path,
+        options: &LaunchOptions,
+    ) -> Result<Child, String> {
+        let exe_path = self.find_unity_executable(game_path, options.entry_path.as_deref())?;
+        self.launch_executable(game, &exe_path, game_path, container_root, options)
+    }
+
+    /// 以指定工作目录启动可执行文件；Linux 上的 .exe（PE 文件）经由 wine 启动
+    fn launch_executable(
+        &self,
+        game: &Game,
+        exe_path: &Path,
+        working_dir: &Path,
+        container_root: &Path,
+        options: &LaunchOptions,
+    ) -> Result<Child, String> {
+        let needs_wine = cfg!(target_os = "linux")
+            && exe_path
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| e.eq_ignore_ascii_case("exe"));
+
+        let mut cmd = if needs_wine {
+            let wine = self.find_wine_executable(options.wine_binary.as_deref())?;
+            let mut cmd = self.game_command(wine, options);
+            cmd.arg(exe_path);
+            cmd
+        } else {
+            self.game_command(exe_path, options)
+        };
+        cmd.current_dir(working_dir);
+
+        self.apply_home_sandbox(&mut cmd, container_root, &game.profile_key, options);
+        if needs_wine {
+            self.apply_wine_prefix(&mut cmd, container_root, &game.profile_key, options)?;
+        }
+        self.apply_env(&mut cmd, options);
+        self.apply_args(&mut cmd, options);
+
+        self.spawn(&mut cmd, options)
+            .map_err(|e| format!("启动游戏失败: {}", e))
     }
 
     /// 启动 NWjs 游戏
@@ -135,17 +413,22 @@ impl LauncherService {
         game: &Game,
         game_path: &Path,
         container_root: &Path,
-        nwjs_runtime_dir: Option<&Path>,
+        nwjs_runtime: Option<&NwjsRuntime>,
         options: &LaunchOptions,
     ) -> Result<Child, String> {
         // 查找nw可执行文件
-        let nw_path = self.find_nwjs_executable(game_path, nwjs_runtime_dir)?;
+        let nw_path =
+            self.find_nwjs_executable(game_path, nwjs_runtime.map(|r| r.dir.as_path()))?;
 
-        let mut cmd = Command::new(&nw_path);
+        let mut cmd = self.game_command(&nw_path, options);
         cmd.current_dir(game_path);
 
         self.apply_nwjs_sandbox(&mut cmd, container_root, &game.profile_key, options);
+        self.apply_env(&mut cmd, options);
         self.apply_args(&mut cmd, options);
+        if let Some(port) = nwjs_runtime.and_then(|r| r.devtools_port) {
+            cmd.arg(format!("--remote-debugging-port={}", port));
+        }
 
         let app_path = self.resolve_nwjs_app_path(game_path, options.entry_path.as_deref());
         let final_app_path = if app_path
@@ -169,8 +452,8 @@ impl LauncherService {
         };
         cmd.arg(&final_app_path);
 
-        let child = cmd
-            .spawn()
+        let child = self
+            .spawn(&mut cmd, options)
             .map_err(|e| format!("启动NWjs游戏失败: {}", e))?;
 
         Ok(child)
@@ -184,20 +467,33 @@ impl LauncherService {
         container_root: &Path,
         options: &LaunchOptions,
     ) -> Result<Child, String> {
+        let mut cmd = self.renpy_command(game_path, container_root, &game.profile_key, options)?;
+        let child = self
+            .spawn(&mut cmd, options)
+            .map_err(|e| format!("启动RenPy游戏失败: {}", e))?;
+
+        Ok(child)
+    }
+
+    /// 构建 RenPy 游戏的启动命令
+    fn renpy_command(
+        &self,
+        game_path: &Path,
+        container_root: &Path,
+        profile_key: &str,
+        options: &LaunchOptions,
+    ) -> Result<Command, String> {
         // 查找RenPy可执行文件
         let exe_path = self.find_renpy_executable(game_path, options.entry_path.as_deref())?;
 
-        let mut cmd = Command::new(&exe_path);
+        let mut cmd = self.game_command(&exe_path, options);
         cmd.current_dir(game_path);
 
-        self.apply_home_sandbox(&mut cmd, container_root, &game.profile_key, options);
+        self.apply_home_sandbox(&mut cmd, container_root, profile_key, options);
+        self.apply_renpy_save_dir(&mut cmd, container_root, profile_key, options);
+        self.apply_env(&mut cmd, options);
         self.apply_args(&mut cmd, options);
-
-        let child = cmd
-            .spawn()
-            .map_err(|e| format!("启动RenPy游戏失败: {}", e))?;
-
-        Ok(child)
+        Ok(cmd)
     }
 
     async fn launch_other_game(
@@ -215,39 +511,109 @@ impl LauncherService {
                 .ok_or_else(|| "未配置入口文件".to_string())?,
         };
 
-        if options.use_bottles {
-            #[cfg(not(target_os = "linux"))]
-            {
-                let _ = entry_path;
-                return Err("Bottles 仅支持在 Linux 上运行".to_string());
-            }
+        let mut cmd = self.game_command(&entry_path, options);
+        cmd.current_dir(game_path);
 
-            #[cfg(target_os = "linux")]
-            {
-                use crate::services::extension::BottlesService;
-                let cli = BottlesService::detect_cli_sync()
-                    .ok_or_else(|| "未检测到 Bottles CLI".to_string())?;
-                let bottle = options
-                    .bottle_name
-                    .clone()
-                    .ok_or_else(|| "未选择 Bottles bottle".to_string())?;
-                let program = entry_path.to_string_lossy().to_string();
-                if program.trim().is_empty() {
-                    return Err("入口程序为空".to_string());
-                }
-                return BottlesService::run_executable(&cli, &bottle, &program, &options.args);
-            }
+        self.apply_home_sandbox(&mut cmd, container_root, &game.profile_key, options);
+        self.apply_env(&mut cmd, options);
+        self.apply_args(&mut cmd, options);
+
+        let child = self
+            .spawn(&mut cmd, options)
+            .map_err(|e| format!("启动游戏失败: {}", e))?;
+
+        Ok(child)
+    }
+
+    /// 通过 Steam Proton 运行 Windows 游戏（`proton run <exe>`），兼容数据目录位于 profile 内
+    async fn launch_proton_game(
+        &self,
+        game: &Game,
+        game_path: &Path,
+        container_root: &Path,
+        options: &LaunchOptions,
+    ) -> Result<Child, String> {
+        let entry_path = self
+            .resolve_entry_path(game_path, options.entry_path.as_deref())
+            .or_else(|| self.find_executable_by_extension(game_path, &["exe"]))
+            .ok_or_else(|| "未配置入口文件".to_string())?;
+
+        if !cfg!(target_os = "linux") {
+            return Err("Proton 仅支持在 Linux 上运行".to_string());
         }
 
-        let mut cmd = Command::new(&entry_path);
+        use crate::services::extension::ProtonService;
+        let proton =
+            ProtonService::find_version(options.proton_version.as_deref()).ok_or_else(|| {
+                match options.proton_version.as_deref() {
+                    Some(name) => format!("未找到 Proton 版本: {}", name),
+                    None => "未检测到已安装的 Proton，请先在 Steam 中安装".to_string(),
+                }
+            })?;
+
+        let mut cmd = self.game_command(Path::new(&proton.path).join("proton"), options);
+        cmd.arg("run").arg(&entry_path);
         cmd.current_dir(game_path);
 
         self.apply_home_sandbox(&mut cmd, container_root, &game.profile_key, options);
+        let compat_dir = self
+            .file_service
+            .game_proton_compat_dir(container_root, &game.profile_key);
+        crate::utils::path::ensure_dir(&compat_dir)?;
+        cmd.env("STEAM_COMPAT_DATA_PATH", &compat_dir);
+        // Proton 要求设置此变量；未安装 Steam 客户端时指向兼容数据目录即可运行
+        let client_dir = ProtonService::steam_client_dir().unwrap_or_else(|| compat_dir.clone());
+        cmd.env("STEAM_COMPAT_CLIENT_INSTALL_PATH", client_dir);
+        self.apply_env(&mut cmd, options);
         self.apply_args(&mut cmd, options);
 
-        let child = cmd.spawn().map_err(|e| format!("启动游戏失败: {}", e))?;
+        self.spawn(&mut cmd, options)
+            .map_err(|e| format!("启动 Proton 失败: {}", e))
+    }
+
+    /// 通过 Bottles 运行 Windows 游戏（任意引擎类型，入口为 .exe）
+    async fn launch_bottles_game(
+        &self,
+        game: &Game,
+        game_path: &Path,
+        container_root: &Path,
+        options: &LaunchOptions,
+    ) -> Result<Child, String> {
+        let entry_path = self
+            .resolve_entry_path(game_path, options.entry_path.as_deref())
+            .or_else(|| self.find_executable_by_extension(game_path, &["exe"]))
+            .ok_or_else(|| "未配置入口文件".to_string())?;
 
-        Ok(child)
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = (entry_path, game, container_root);
+            Err("Bottles 仅支持在 Linux 上运行".to_string())
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            use crate::services::extension::BottlesService;
+            let cli = BottlesService::detect_cli_sync()
+                .ok_or_else(|| "未检测到 Bottles CLI".to_string())?;
+            let bottle = options
+                .bottle_name
+                .clone()
+                .ok_or_else(|| "未选择 Bottles bottle".to_string())?;
+            let program = entry_path.to_string_lossy().to_string();
+            if program.trim().is_empty() {
+                return Err("入口程序为空".to_string());
+            }
+
+            // 参数由 bottles-cli 在 `--` 之后转交给游戏
+            let mut cmd =
+                BottlesService::executable_command(&cli, &bottle, &program, &options.args);
+            cmd.current_dir(game_path);
+            self.apply_home_sandbox(&mut cmd, container_root, &game.profile_key, options);
+            self.apply_env(&mut cmd, options);
+
+            self.spawn(&mut cmd, options)
+                .map_err(|e| format!("启动 Bottles 失败: {}", e))
+        }
     }
 
     /// 使用 mkxp-z 原生运行 RPG Maker (XP/VX/VX Ace) 游戏
@@ -265,17 +631,28 @@ impl LauncherService {
             .find_mkxpz_in_dir(runtime_dir)
             .ok_or_else(|| format!("在 {} 中找不到 mkxp-z 可执行文件", runtime_dir.display()))?;
 
-        let mut cmd = Command::new(&binary);
-        cmd.current_dir(game_path);
+        // mkxp-z 从工作目录读取 Game.ini；指定了入口时使用入口所在目录
+        let working_dir = match self.resolve_entry_path(game_path, options.entry_path.as_deref()) {
+            Some(entry) if entry.is_dir() => entry,
+            Some(entry) => entry
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| game_path.to_path_buf()),
+            None => game_path.to_path_buf(),
+        };
+
+        let mut cmd = self.game_command(&binary, options);
+        cmd.current_dir(&working_dir);
 
         // mkxp-z 会从 HOME/XDG 数据目录读取用户配置；使用游戏 profile
         // 作为 HOME，既隔离存档/配置，也不向游戏安装目录写入 mkxp.json。
         self.apply_home_sandbox(&mut cmd, container_root, &game.profile_key, options);
+        self.apply_env(&mut cmd, options);
 
         self.apply_args(&mut cmd, options);
 
-        let child = cmd
-            .spawn()
+        let child = self
+            .spawn(&mut cmd, options)
             .map_err(|e| format!("启动 mkxp-z 游戏失败: {}", e))?;
 
         Ok(child)
@@ -337,6 +714,252 @@ impl LauncherService {
         Err("未找到RPG Maker可执行文件".to_string())
     }
 
+    /// 查找 Godot 可执行文件：优先选择与 .pck 同名的可执行文件
+    fn find_godot_executable(
+        &self,
+        game_path: &Path,
+        entry_path: Option<&str>,
+    ) -> Result<PathBuf, String> {
+        if let Some(path) = self.resolve_entry_path(game_path, entry_path) {
+            return Ok(path);
+        }
+
+        let pck_stems: HashSet<String> = list_dir(game_path)
+            .iter()
+            .filter(|path| path.is_file() && has_extension(path, "pck"))
+            .map(|path| lower_stem(path))
+            .collect();
+
+        self.find_executable_for_stems(game_path, &pck_stems)
+            .or_else(|| self.find_root_executable(game_path))
+            .or_else(|| self.find_executable_by_extension(game_path, &["exe"]))
+            .ok_or_else(|| "未找到 Godot 可执行文件".to_string())
+    }
+
+    /// 查找吉里吉里可执行文件：优先与 .xp3 同名，其次按目录名匹配的 .exe
+    fn find_kirikiri_executable(
+        &self,
+        game_path: &Path,
+        entry_path: Option<&str>,
+    ) -> Result<PathBuf, String> {
+        if let Some(path) = self.resolve_entry_path(game_path, entry_path) {
+            return Ok(path);
+        }
+
+        let xp3_stems: HashSet<String> = list_dir(game_path)
+            .iter()
+            .filter(|path| path.is_file() && has_extension(path, "xp3"))
+            .map(|path| lower_stem(path))
+            .collect();
+
+        self.find_executable_for_stems(game_path, &xp3_stems)
+            .filter(|path| has_extension(path, "exe"))
+            .or_else(|| self.find_executable_by_extension(game_path, &["exe"]))
+            .ok_or_else(|| "未找到吉里吉里游戏的可执行文件".to_string())
+    }
+
+    /// 查找 Unity 可执行文件：与含 `globalgamemanagers` 的 `<名称>_Data` 目录同名
+    fn find_unity_executable(
+        &self,
+        game_path: &Path,
+        entry_path: Option<&str>,
+    ) -> Result<PathBuf, String> {
+        if let Some(path) = self.resolve_entry_path(game_path, entry_path) {
+            return Ok(path);
+        }
+
+        let data_stems: HashSet<String> = list_dir(game_path)
+            .iter()
+            .filter(|path| path.is_dir() && path.join("globalgamemanagers").is_file())
+            .filter_map(|path| {
+                let name = path.file_name()?.to_str()?.to_lowercase();
+                name.strip_suffix("_data").map(str::to_string)
+            })
+            .collect();
+
+        self.find_executable_for_stems(game_path, &data_stems)
+            .ok_or_else(|| "未找到与 _Data 目录匹配的 Unity 可执行文件".to_string())
+    }
+
+    /// 在游戏根目录中查找文件名（不含扩展名）属于 `stems` 的可执行文件
+    ///
+    /// 本平台可执行文件在前，其它平台的 .exe 在后（Linux 上经由 wine 启动）。
+    fn find_executable_for_stems(
+        &self,
+        game_path: &Path,
+        stems: &HashSet<String>,
+    ) -> Option<PathBuf> {
+        if stems.is_empty() {
+            return None;
+        }
+
+        let mut candidates: Vec<PathBuf> = list_dir(game_path)
+            .into_iter()
+            .filter(|path| {
+                path.is_file()
+                    && stems.contains(&lower_stem(path))
+                    && EXECUTABLE_EXTENSIONS.iter().any(|ext| {
+                        if ext.is_empty() {
+                            path.extension().is_none()
+                        } else {
+                            has_extension(path, ext)
+                        }
+                    })
+            })
+            .collect();
+        candidates.sort_by_key(|path| {
+            (
+                has_extension(path, "exe") != cfg!(target_os = "windows"),
+                path.to_string_lossy().to_lowercase(),
+            )
+        });
+        candidates.into_iter().next()
+    }
+
+    /// 查找 wine 可执行文件：优先使用设置中的路径，否则从 PATH 查找
+    fn find_wine_executable(&self, configured: Option<&str>) -> Result<PathBuf, String> {
+        let name = configured.unwrap_or("wine");
+        if Path::new(name).components().count() > 1 && !Path::new(name).is_file() {
+            return Err(format!("设置中的 wine 路径不存在: {}", name));
+        }
+
+        self.find_program(name)
+            .ok_or_else(|| format!("未找到 {}，请先安装 Wine，或在设置中指定 wine 路径", name))
+    }
+
+    /// 创建游戏进程命令；Linux 上按配置以 `gamemoderun`、`mangohud` 包装程序
+    ///
+    /// 包装工具缺失时仅记录警告并直接启动，由调用方通过 [`Self::missing_launch_tools`] 提示用户。
+    fn game_command(&self, program: impl AsRef<OsStr>, options: &LaunchOptions) -> Command {
+        let mut wrappers = Vec::new();
+        let mut mangohud = false;
+        if cfg!(target_os = "linux") {
+            if options.use_gamemode {
+                match self.find_program("gamemoderun") {
+                    Some(path) => wrappers.push(path),
+                    None => tracing::warn!("已启用 GameMode，但未在 PATH 中找到 gamemoderun"),
+                }
+            }
+            if options.use_mangohud {
+                match self.find_program("mangohud") {
+                    Some(path) => {
+                        wrappers.push(path);
+                        mangohud = true;
+                    }
+                    None => tracing::warn!("已启用 MangoHud，但未在 PATH 中找到 mangohud"),
+                }
+            }
+        }
+
+        let mut cmd = match wrappers.split_first() {
+            Some((first, rest)) => {
+                let mut cmd = Command::new(first);
+                cmd.args(rest);
+                cmd.arg(program);
+                cmd
+            }
+            None => Command::new(program),
+        };
+        // Vulkan 隐式层按 MANGOHUD=1 启用，OpenGL 程序依赖上面的 mangohud 包装
+        if mangohud {
+            cmd.env("MANGOHUD", "1");
+        }
+        cmd
+    }
+
+    /// 启动已构建好的命令；启用终端模式时改为在终端窗口中运行同一命令
+    fn spawn(&self, cmd: &mut Command, options: &LaunchOptions) -> std::io::Result<Child> {
+        if !options.launch_in_terminal {
+            return cmd.spawn();
+        }
+        self.terminal_command(cmd)?.spawn()
+    }
+
+    /// 把命令（程序、参数、环境变量与工作目录）转为在终端窗口中运行
+    ///
+    /// Linux 使用 `$TERMINAL` 或 `x-terminal-emulator`，Windows 使用 `cmd /k`，游戏退出后保留窗口；
+    /// macOS 生成 `.command` 脚本交给 `open -a Terminal` 执行。
+    fn terminal_command(&self, cmd: &Command) -> std::io::Result<Command> {
+        if cfg!(target_os = "macos") {
+            let script = std::env::temp_dir().join(format!(
+                "gamemanager-launch-{}.command",
+                crate::utils::now_unix_ms()
+            ));
+            std::fs::write(&script, terminal_script(cmd))?;
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755))?;
+            }
+            let mut terminal = Command::new("open");
+            terminal.args(["-a", "Terminal"]).arg(script);
+            return Ok(terminal);
+        }
+
+        let mut terminal = if cfg!(target_os = "windows") {
+            let mut terminal = Command::new("cmd");
+            terminal.arg("/k");
+            terminal
+        } else {
+            let program = std::env::var("TERMINAL")
+                .ok()
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .and_then(|t| self.find_program(&t))
+                .or_else(|| self.find_program("x-terminal-emulator"))
+                .ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::NotFound,
+                        "未找到终端程序，请设置 TERMINAL 环境变量或关闭终端启动",
+                    )
+                })?;
+            let mut terminal = Command::new(program);
+            terminal.args(["-e", "sh", "-c", TERMINAL_HOLD_SCRIPT, "sh"]);
+            terminal
+        };
+        terminal.arg(cmd.get_program()).args(cmd.get_args());
+        for (key, value) in cmd.get_envs() {
+            match value {
+                Some(value) => terminal.env(key, value),
+                None => terminal.env_remove(key),
+            };
+        }
+        if let Some(dir) = cmd.get_current_dir() {
+            terminal.current_dir(dir);
+        }
+        Ok(terminal)
+    }
+
+    /// 返回游戏配置中已启用但在本机不可用的启动包装工具（非 Linux 平台始终为空）
+    pub fn missing_launch_tools(&self, config: &GameConfig) -> Vec<&'static str> {
+        if !cfg!(target_os = "linux") {
+            return Vec::new();
+        }
+        [
+            (config.use_gamemode, "gamemoderun"),
+            (config.use_mangohud, "mangohud"),
+        ]
+        .into_iter()
+        .filter(|(enabled, tool)| *enabled && self.find_program(tool).is_none())
+        .map(|(_, tool)| tool)
+        .collect()
+    }
+
+    /// 解析外部程序：带路径时检查文件是否存在，仅为程序名时从 PATH 查找
+    pub(crate) fn find_program(&self, name: &str) -> Option<PathBuf> {
+        let candidate = PathBuf::from(name);
+        if candidate.components().count() > 1 {
+            return candidate.is_file().then_some(candidate);
+        }
+
+        let output = Command::new("which").arg(name).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let path_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (!path_str.is_empty()).then(|| PathBuf::from(path_str))
+    }
+
     /// 查找 NWjs 可执行文件
     fn find_nwjs_executable(
         &self,
@@ -382,7 +1005,11 @@ impl LauncherService {
         None
     }
 
-    fn resolve_launch_options(&self, config: Option<&GameConfig>) -> LaunchOptions {
+    fn resolve_launch_options(
+        &self,
+        config: Option<&GameConfig>,
+        default_args: &[String],
+    ) -> LaunchOptions {
         if let Some(config) = config {
             let entry_path = config.entry_path.trim();
             LaunchOptions {
@@ -392,19 +1019,52 @@ impl LauncherService {
                 } else {
                     Some(entry_path.to_string())
                 },
-                args: config.args.clone(),
+                args: merge_default_args(default_args, &config.args),
+                env: config.env.clone(),
                 sandbox_home: config.sandbox_home,
                 use_bottles: config.use_bottles || config.runner == "bottles",
                 bottle_name: config.bottle_name.clone(),
+                proton_version: config
+                    .proton_version
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_string),
+                wine_binary: None,
+                easyrpg_binary: None,
+                wine_prefix: config
+                    .wine_prefix
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_string),
+                locale: config
+                    .locale
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_string),
+                use_gamemode: config.use_gamemode,
+                use_mangohud: config.use_mangohud,
+                launch_in_terminal: false,
             }
         } else {
             LaunchOptions {
                 runner: "auto".to_string(),
                 entry_path: None,
-                args: Vec::new(),
-                sandbox_home: true,
+                args: default_args.to_vec(),
+                env: HashMap::new(),
+                sandbox_home: SandboxHome::Full,
                 use_bottles: false,
                 bottle_name: None,
+                proton_version: None,
+                wine_binary: None,
+                easyrpg_binary: None,
+                wine_prefix: None,
+                locale: None,
+                use_gamemode: false,
+                use_mangohud: false,
+                launch_in_terminal: false,
             }
         }
     }
@@ -466,6 +1126,11 @@ impl LauncherService {
             return www;
         }
 
+        // 没有 package.json：使用最近的 index.html
+        if let Some(entry) = super::nwjs_entry::find_nwjs_entry(game_path) {
+            return game_path.join(entry);
+        }
+
         game_path.to_path_buf()
     }
 
@@ -499,14 +1164,70 @@ impl LauncherService {
         profile_key: &str,
         options: &LaunchOptions,
     ) {
-        if !options.sandbox_home {
-            return;
+        let unredirected = options.sandbox_home.unredirected_vars();
+        if !unredirected.is_empty() {
+            tracing::warn!(
+                profile = %profile_key,
+                dirs = %unredirected.join(", "),
+                "以下用户目录未重定向，游戏将直接读写宿主系统中的对应目录"
+            );
         }
 
         let user_data_dir = self
             .file_service
             .game_user_data_dir(container_root, profile_key);
-        cmd.env("HOME", user_data_dir);
+        if options.sandbox_home.redirects_xdg() {
+            for (var, relative) in XDG_DIR_VARS {
+                cmd.env(var, user_data_dir.join(relative));
+            }
+        }
+        if options.sandbox_home.redirects_home() {
+            cmd.env("HOME", user_data_dir);
+        }
+    }
+
+    /// 显式指定 Ren'Py 存档目录，避免依赖 HOME 推导出的平台默认位置
+    ///
+    /// Ren'Py 读取 `RENPY_PATH_TO_SAVES`（存档根目录）与 `RENPY_MULTIPERSISTENT`；
+    /// `RENPY_SAVEDIR` 同时设置，供自定义启动脚本使用。目录与沙盒 HOME 下的
+    /// `.renpy` 一致，已有存档不受影响。仅在重定向 HOME 时生效。
+    fn apply_renpy_save_dir(
+        &self,
+        cmd: &mut Command,
+        container_root: &Path,
+        profile_key: &str,
+        options: &LaunchOptions,
+    ) {
+        if !options.sandbox_home.redirects_home() {
+            return;
+        }
+
+        let saves_dir = self
+            .file_service
+            .game_user_data_dir(container_root, profile_key)
+            .join(".renpy");
+        cmd.env("RENPY_PATH_TO_SAVES", &saves_dir);
+        cmd.env("RENPY_SAVEDIR", &saves_dir);
+        cmd.env("RENPY_MULTIPERSISTENT", saves_dir.join("persistent"));
+    }
+
+    /// 设置 `WINEPREFIX`，未配置时每个游戏使用 profile 下独立的前缀
+    fn apply_wine_prefix(
+        &self,
+        cmd: &mut Command,
+        container_root: &Path,
+        profile_key: &str,
+        options: &LaunchOptions,
+    ) -> Result<(), String> {
+        let prefix = match options.wine_prefix.as_deref() {
+            Some(path) => PathBuf::from(path),
+            None => self
+                .file_service
+                .game_wine_prefix_dir(container_root, profile_key),
+        };
+        crate::utils::path::ensure_dir(&prefix)?;
+        cmd.env("WINEPREFIX", prefix);
+        Ok(())
     }
 
     fn apply_nwjs_sandbox(
@@ -516,7 +1237,11 @@ impl LauncherService {
         profile_key: &str,
         options: &LaunchOptions,
     ) {
-        if !options.sandbox_home {
+        if options.sandbox_home == SandboxHome::Off {
+            tracing::warn!(
+                profile = %profile_key,
+                "未启用沙盒，NW.js 使用默认的用户数据目录"
+            );
             return;
         }
 
@@ -532,12 +1257,55 @@ impl LauncherService {
         cmd.env("BREAKPAD_DUMP_LOCATION", crash_dir);
     }
 
+    /// 展开参数中的 `${game_dir}` 等占位符（见 [`arg_template`]）
+    fn expand_arg_templates(
+        &self,
+        options: &mut LaunchOptions,
+        game: &Game,
+        container_root: &Path,
+    ) {
+        let fs = &self.file_service;
+        let profile_dir = fs.game_profile_dir(container_root, &game.profile_key);
+        let user_data_dir = fs.game_user_data_dir(container_root, &game.profile_key);
+        let vars = [
+            ("game_dir", game.game_path.clone()),
+            ("profile_dir", profile_dir.to_string_lossy().to_string()),
+            ("user_data_dir", user_data_dir.to_string_lossy().to_string()),
+            (
+                "container_root",
+                container_root.to_string_lossy().to_string(),
+            ),
+        ];
+        for arg in &mut options.args {
+            *arg = arg_template::expand(arg, &vars);
+        }
+    }
+
     fn apply_args(&self, cmd: &mut Command, options: &LaunchOptions) {
         if !options.args.is_empty() {
             cmd.args(&options.args);
         }
     }
 
+    /// 设置游戏配置中的区域与环境变量，需在沙盒环境之后调用以便用户覆盖
+    ///
+    /// Windows 原生进程的非 Unicode 代码页由系统设置决定，环境变量只对 Wine、
+    /// MSYS/Cygwin 类运行时生效；需要时请配合 Locale Emulator 使用。
+    fn apply_env(&self, cmd: &mut Command, options: &LaunchOptions) {
+        if let Some(locale) = options.locale.as_deref() {
+            for key in ["LANG", "LC_ALL", "LC_CTYPE"] {
+                cmd.env(key, locale);
+            }
+        }
+        for (key, value) in &options.env {
+            let key = key.trim();
+            if key.is_empty() || key.contains('=') {
+                continue;
+            }
+            cmd.env(key, value);
+        }
+    }
+
     #[cfg(target_os = "windows")]
     fn find_windows_executable(&self, game_path: &Path) -> Option<PathBuf> {
         self.find_executable_by_extension(game_path, &["exe", "bat", "cmd"])
@@ -687,8 +1455,237 @@ impl LauncherService {
     }
 }
 
+/// 导出游戏可执行文件的扩展名（空字符串表示无扩展名的 Linux 可执行文件）
+const EXECUTABLE_EXTENSIONS: &[&str] = &["exe", "x86_64", "x86_32", "x86", "arm64", ""];
+
+/// 终端模式下游戏退出后保留窗口，便于查看输出
+const TERMINAL_HOLD_SCRIPT: &str =
+    r#""$@"; status=$?; printf '\n游戏已退出（%s），按回车关闭窗口' "$status"; read -r _"#;
+
+/// macOS 终端模式的启动脚本：恢复工作目录与环境变量后运行命令，脚本运行后自行删除
+fn terminal_script(cmd: &Command) -> String {
+    let quote = |s: &OsStr| format!("'{}'", s.to_string_lossy().replace('\'', r"'\''"));
+    let mut script = String::from("#!/bin/sh\nrm -f \"$0\"\n");
+    if let Some(dir) = cmd.get_current_dir() {
+        script.push_str(&format!("cd {} || exit 1\n", quote(dir.as_os_str())));
+    }
+    for (key, value) in cmd.get_envs() {
+        let key = key.to_string_lossy();
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            tracing::warn!(key = %key, "环境变量名无法写入终端脚本，已跳过");
+            continue;
+        }
+        match value {
+            Some(value) => script.push_str(&format!("export {}={}\n", key, quote(value))),
+            None => script.push_str(&format!("unset {}\n", key)),
+        }
+    }
+    let line: Vec<String> = std::iter::once(cmd.get_program())
+        .chain(cmd.get_args())
+        .map(quote)
+        .collect();
+    script.push_str(&format!(
+        "set -- {}\n{}\n",
+        line.join(" "),
+        TERMINAL_HOLD_SCRIPT
+    ));
+    script
+}
+
+/// 把引擎默认参数放在游戏参数之前；游戏参数中已有的同名选项（`--name` 或 `--name=...`）不再重复添加
+///
+/// 默认参数中以两个词给出的 `--name value` 被覆盖时，值也一并去掉。
+fn merge_default_args(defaults: &[String], args: &[String]) -> Vec<String> {
+    fn option_name(arg: &str) -> Option<&str> {
+        arg.starts_with('-')
+            .then(|| arg.split_once('=').map_or(arg, |(name, _)| name))
+    }
+    let overridden: HashSet<&str> = args.iter().filter_map(|a| option_name(a)).collect();
+    let mut merged = Vec::with_capacity(defaults.len() + args.len());
+    let mut iter = defaults.iter().peekable();
+    while let Some(arg) = iter.next() {
+        match option_name(arg) {
+            Some(name) if overridden.contains(name) => {
+                if !arg.contains('=') {
+                    iter.next_if(|value| !value.starts_with('-'));
+                }
+            }
+            _ => merged.push(arg.clone()),
+        }
+    }
+    merged.extend(args.iter().cloned());
+    merged
+}
+
+fn list_dir(dir: &Path) -> Vec<PathBuf> {
+    std::fs::read_dir(dir)
+        .map(|entries| entries.flatten().map(|entry| entry.path()).collect())
+        .unwrap_or_default()
+}
+
+fn has_extension(path: &Path, ext: &str) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case(ext))
+}
+
+fn lower_stem(path: &Path) -> String {
+    path.file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("")
+        .to_lowercase()
+}
+
 impl Default for LauncherService {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 命令中设置的环境变量值
+    fn env(cmd: &Command, key: &str) -> Option<PathBuf> {
+        cmd.get_envs()
+            .find(|(k, _)| *k == OsStr::new(key))
+            .and_then(|(_, v)| v.map(PathBuf::from))
+    }
+
+    /// 只含 `game/` 与启动脚本的 Ren'Py 目录，返回按 `sandbox_home` 构建的启动命令
+    fn renpy_command_for(sandbox_home: SandboxHome) -> (tempfile::TempDir, Command) {
+        let tmp = tempfile::tempdir().unwrap();
+        let game_path = tmp.path().join("MyGame");
+        std::fs::create_dir_all(game_path.join("game")).unwrap();
+        std::fs::write(game_path.join("MyGame.sh"), "#!/bin/sh\n").unwrap();
+
+        let config = GameConfig {
+            engine_type: "renpy".to_string(),
+            entry_path: "MyGame.sh".to_string(),
+            sandbox_home,
+            ..GameConfig::default()
+        };
+        let launcher = LauncherService::new();
+        let options = launcher.resolve_launch_options(Some(&config), &[]);
+        let cmd = launcher
+            .renpy_command(
+                &game_path,
+                &tmp.path().join("container"),
+                "mygame",
+                &options,
+            )
+            .unwrap();
+        (tmp, cmd)
+    }
+
+    #[test]
+    fn renpy_saves_go_to_sandbox_home() {
+        let (tmp, cmd) = renpy_command_for(SandboxHome::Full);
+        let saves = crate::services::fs::FileService::new()
+            .game_user_data_dir(&tmp.path().join("container"), "mygame")
+            .join(".renpy");
+
+        assert_eq!(env(&cmd, "RENPY_PATH_TO_SAVES"), Some(saves.clone()));
+        assert_eq!(env(&cmd, "RENPY_SAVEDIR"), Some(saves.clone()));
+        assert_eq!(
+            env(&cmd, "RENPY_MULTIPERSISTENT"),
+            Some(saves.join("persistent"))
+        );
+    }
+
+    /// 每个内置引擎插件在默认设置下只走一条启动实现，且与插件声明的启动策略一致
+    #[test]
+    fn each_engine_has_single_launch_path() {
+        let mut registry = crate::engines::EngineRegistry::new();
+        let warnings = registry.load(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("engines"),
+            &HashMap::new(),
+        );
+        assert!(warnings.is_empty(), "{:?}", warnings);
+
+        let expected = HashMap::from([
+            ("rpgmakermv", LaunchPath::Nwjs),
+            ("tyrano", LaunchPath::Nwjs),
+            ("html", LaunchPath::Nwjs),
+            ("rpgmakerxp", LaunchPath::Mkxpz),
+            ("rpgmakervx", LaunchPath::Mkxpz),
+            ("rpgmaker2000", LaunchPath::RpgMaker2000),
+            ("renpy", LaunchPath::RenPy),
+            ("wolfrpg", LaunchPath::WolfRpg),
+            ("kirikiri", LaunchPath::KiriKiri),
+            ("godot", LaunchPath::Godot),
+            ("unity", LaunchPath::Unity),
+            ("electron", LaunchPath::Other),
+            ("unreal", LaunchPath::Other),
+            ("other", LaunchPath::Other),
+        ]);
+        let options = LauncherService::new().resolve_launch_options(None, &[]);
+
+        let mut ids = registry.engine_ids();
+        ids.sort();
+        for id in ids {
+            let path = launch_path(&EngineType::from_str(id), &options, true, true);
+            assert_eq!(expected.get(id.as_str()), Some(&path), "{}", id);
+
+            let strategy = registry
+                .get_entry(id)
+                .unwrap()
+                .profile
+                .launch
+                .strategy
+                .as_str();
+            match strategy {
+                "nwjs" => assert_eq!(path, LaunchPath::Nwjs, "{}", id),
+                "mkxpz" => assert_eq!(path, LaunchPath::Mkxpz, "{}", id),
+                _ => assert!(
+                    !matches!(path, LaunchPath::Nwjs | LaunchPath::Mkxpz),
+                    "{}",
+                    id
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn missing_runtime_falls_back_to_engine_launcher() {
+        let options = LauncherService::new().resolve_launch_options(None, &[]);
+
+        assert_eq!(
+            launch_path(&EngineType::RpgMakerXP, &options, false, false),
+            LaunchPath::RpgMaker
+        );
+        assert_eq!(
+            launch_path(&EngineType::RpgMakerMV, &options, false, false),
+            LaunchPath::Other
+        );
+    }
+
+    #[test]
+    fn merge_default_args_drops_overridden_option_with_its_value() {
+        let strings = |args: &[&str]| args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        let defaults = strings(&["--renderer", "gl", "--fullscreen", "--lang=en"]);
+
+        assert_eq!(
+            merge_default_args(&defaults, &strings(&["--renderer", "vulkan"])),
+            strings(&["--fullscreen", "--lang=en", "--renderer", "vulkan"])
+        );
+        assert_eq!(
+            merge_default_args(&defaults, &strings(&["--renderer=vulkan", "--lang=ja"])),
+            strings(&["--fullscreen", "--renderer=vulkan", "--lang=ja"])
+        );
+        assert_eq!(
+            merge_default_args(&defaults, &strings(&["--fullscreen", "save1"])),
+            strings(&["--renderer", "gl", "--lang=en", "--fullscreen", "save1"])
+        );
+    }
+
+    #[test]
+    fn renpy_saves_untouched_without_sandbox() {
+        let (_tmp, cmd) = renpy_command_for(SandboxHome::Off);
+
+        assert_eq!(env(&cmd, "RENPY_PATH_TO_SAVES"), None);
+        assert_eq!(env(&cmd, "RENPY_SAVEDIR"), None);
+        assert_eq!(env(&cmd, "RENPY_MULTIPERSISTENT"), None);
+    }
+}
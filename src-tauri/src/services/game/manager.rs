@@ -1,35 +1,124 @@
-use crate::db::schema::Game;
-use crate::models::{AddGameInput, EngineType, GameDto, UpdateGameInput};
+use crate::db::schema::{Game, GameTag};
+use crate::models::{
+    AddGameInput, EngineType, GameDto, GameSortBy, GameStatus, SETTING_RENAMED_PROFILE_KEYS,
+    SortDirection, UpdateGameInput,
+};
+use crate::services::cover_protocol::cover_url;
 use crate::services::fs::FileService;
-use std::collections::HashSet;
-use std::path::Path;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
+/// 评分范围
+const MIN_RATING: i32 = 0;
+const MAX_RATING: i32 = 5;
+
 /// 游戏管理服务
 #[derive(Clone)]
 pub struct GameService {
     db: Arc<Mutex<toasty::Db>>,
+    /// 数据库文件路径，供 `db::bulk` 的事务写入使用
+    db_path: PathBuf,
 }
 
 impl GameService {
     /// 创建游戏服务实例
-    pub fn new(db: Arc<Mutex<toasty::Db>>) -> Self {
-        Self { db }
+    pub fn new(db: Arc<Mutex<toasty::Db>>, db_path: PathBuf) -> Self {
+        Self { db, db_path }
     }
 
-    /// 获取所有游戏列表
+    /// 获取所有游戏列表（收藏在前，组内按添加时间倒序）
     pub async fn get_all_games(&self) -> Result<Vec<Game>, String> {
         let mut db = self.db.lock().await;
-        let games = Game::all()
+        let mut games = Game::all()
             .exec(&mut *db)
             .await
             .map_err(|e| format!("查询游戏列表失败: {}", e))?;
+        sort_library(&mut games);
+
+        Ok(games)
+    }
+
+    /// 按指定字段排序获取游戏列表，可按完成状态筛选
+    ///
+    /// 未评分、未游玩的游戏无论升序降序都排在最后；相同值保持 `get_all_games` 的顺序。
+    pub async fn get_games_sorted(
+        &self,
+        sort_by: GameSortBy,
+        direction: SortDirection,
+        status: Option<GameStatus>,
+    ) -> Result<Vec<Game>, String> {
+        let mut games = self.get_all_games().await?;
+        if let Some(status) = status {
+            games.retain(|g| g.status == status.as_str());
+        }
+        let ordered = |ord: Ordering| match direction {
+            SortDirection::Asc => ord,
+            SortDirection::Desc => ord.reverse(),
+        };
+        games.sort_by(|a, b| match sort_by {
+            GameSortBy::Rating => cmp_none_last(a.rating, b.rating, ordered),
+            GameSortBy::LastPlayed => cmp_none_last(a.last_played_at, b.last_played_at, ordered),
+            GameSortBy::Title => ordered(a.title.to_lowercase().cmp(&b.title.to_lowercase())),
+            GameSortBy::CreatedAt => ordered(a.created_at.cmp(&b.created_at)),
+        });
 
         Ok(games)
     }
 
+    /// 按标题和路径搜索游戏（ASCII 字母不区分大小写）
+    ///
+    /// 匹配在 SQL 中完成，只加载命中的游戏再排序：
+    /// 标题完全匹配 > 标题前缀 > 标题包含 > 仅路径包含，同级保持 `get_all_games` 的顺序。
+    pub async fn search_games(&self, query: &str) -> Result<Vec<Game>, String> {
+        let needle = query.trim().to_lowercase();
+        if needle.is_empty() {
+            return self.get_all_games().await;
+        }
+
+        let mut db = self.db.lock().await;
+        let db_path = self.db_path.clone();
+        let pattern = needle.clone();
+        let ids = tokio::task::spawn_blocking(move || {
+            crate::db::bulk::search_game_ids(&db_path, &pattern)
+        })
+        .await
+        .map_err(|e| format!("搜索游戏失败: {}", e))??;
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut games = Game::filter(Game::fields().id().in_list(ids))
+            .exec(&mut *db)
+            .await
+            .map_err(|e| format!("查询游戏列表失败: {}", e))?;
+        sort_library(&mut games);
+
+        let mut ranked: Vec<(u8, Game)> = games
+            .into_iter()
+            .filter_map(|game| {
+                let title = game.title.to_lowercase();
+                let rank = if title == needle {
+                    0
+                } else if title.starts_with(&needle) {
+                    1
+                } else if title.contains(&needle) {
+                    2
+                } else if game.game_path.to_lowercase().contains(&needle) {
+                    3
+                } else {
+                    return None;
+                };
+                Some((rank, game))
+            })
+            .collect();
+        ranked.sort_by_key(|(rank, _)| *rank);
+
+        Ok(ranked.into_iter().map(|(_, game)| game).collect())
+    }
+
     /// 根据ID获取游戏
     pub async fn get_game_by_id(&self, id: &str) -> Result<Option<Game>, String> {
         let mut db = self.db.lock().await;
@@ -88,18 +177,17 @@ impl GameService {
         // 生成游戏ID
         let id = Uuid::new_v4().to_string();
 
-        // 如果没有提供标题，从路径提取
+        // 如果没有提供标题，优先读取游戏数据中的标题，否则从路径提取
         let title = input.title.unwrap_or_else(|| {
-            Path::new(&normalized_path)
-                .file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or("未命名游戏")
-                .to_string()
+            super::title::read_game_title(Path::new(&normalized_path)).unwrap_or_else(|| {
+                Path::new(&normalized_path)
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or("未命名游戏")
+                    .to_string()
+            })
         });
 
-        // 生成profile目录名
-        let profile_key = self.generate_profile_key(&title).await?;
-
         let engine_type = EngineType::from_str(&input.engine_type);
         let game_type = input
             .game_type
@@ -107,7 +195,9 @@ impl GameService {
         let detection_confidence = input.detection_confidence.unwrap_or(0).clamp(0, 100);
         let now = crate::utils::now_unix_ms();
 
+        // 生成 profile 目录名；与写入使用同一把锁，避免并发导入生成相同的 key
         let mut db = self.db.lock().await;
+        let profile_key = generate_profile_key(&mut *db, &title).await?;
         toasty::create!(Game {
             id: id.clone(),
             profile_key: profile_key.clone(),
@@ -151,6 +241,10 @@ impl GameService {
             game.title = title;
         }
         if let Some(engine_type) = input.engine_type {
+            // 用户修改了引擎类型，之后的批量重新检测不再覆盖
+            if engine_type != game.engine_type {
+                game.engine_user_set = true;
+            }
             game.engine_type = engine_type;
         }
         if let Some(ref path) = input.path {
@@ -189,6 +283,7 @@ impl GameService {
         // 更新数据库 — clone values before update() consumes the model
         let title = game.title.clone();
         let engine_type = game.engine_type.clone();
+        let engine_user_set = game.engine_user_set;
         let path = game.game_path.clone();
         let normalized_path = game.normalized_path.clone();
         let game_type = game.game_type.clone();
@@ -200,6 +295,7 @@ impl GameService {
         game.update()
             .title(title)
             .engine_type(engine_type)
+            .engine_user_set(engine_user_set)
             .game_path(path)
             .normalized_path(normalized_path)
             .game_type(game_type)
@@ -219,12 +315,36 @@ impl GameService {
         Ok(game)
     }
 
+    /// 写入重新检测得到的引擎类型与置信度（不改变手动指定标记）
+    pub async fn set_detected_engine(
+        &self,
+        id: &str,
+        engine_type: &str,
+        confidence: i32,
+    ) -> Result<(), String> {
+        let mut db = self.db.lock().await;
+        let mut game = Game::get_by_id(&mut *db, id)
+            .await
+            .map_err(|e| format!("查询游戏失败: {}", e))?;
+        game.update()
+            .engine_type(engine_type.to_string())
+            .game_type(infer_game_type_from_engine(engine_type))
+            .detection_confidence(confidence.clamp(0, 100))
+            .updated_at(crate::utils::now_unix_ms())
+            .exec(&mut *db)
+            .await
+            .map_err(|e| format!("更新引擎类型失败: {}", e))?;
+
+        Ok(())
+    }
+
     /// 删除游戏
     pub async fn delete_game(&self, id: &str) -> Result<(), String> {
         let mut db = self.db.lock().await;
         Game::delete_by_id(&mut *db, id)
             .await
             .map_err(|e| format!("删除游戏失败: {}", e))?;
+        delete_tags_of(&mut *db, id).await?;
 
         Ok(())
     }
@@ -241,10 +361,101 @@ impl GameService {
             Game::delete_by_id(&mut *db, &game.id)
                 .await
                 .map_err(|e| format!("清空游戏库失败: {}", e))?;
+            delete_tags_of(&mut *db, &game.id).await?;
         }
         Ok(count)
     }
 
+    /// 获取游戏标签（显示形式，按归一化标签排序）
+    pub async fn get_tags(&self, id: &str) -> Result<Vec<String>, String> {
+        let mut db = self.db.lock().await;
+        let mut rows = GameTag::filter(GameTag::fields().game_id().eq(id))
+            .exec(&mut *db)
+            .await
+            .map_err(|e| format!("查询游戏标签失败: {}", e))?;
+        rows.sort_by(|a, b| a.tag.cmp(&b.tag));
+
+        Ok(rows.into_iter().map(|t| t.display_tag).collect())
+    }
+
+    /// 获取全部游戏的标签（key = 游戏 ID）
+    pub async fn get_all_tags(&self) -> Result<HashMap<String, Vec<String>>, String> {
+        let mut db = self.db.lock().await;
+        let mut rows = GameTag::all()
+            .exec(&mut *db)
+            .await
+            .map_err(|e| format!("查询游戏标签失败: {}", e))?;
+        rows.sort_by(|a, b| a.tag.cmp(&b.tag));
+
+        let mut tags: HashMap<String, Vec<String>> = HashMap::new();
+        for row in rows {
+            tags.entry(row.game_id).or_default().push(row.display_tag);
+        }
+        Ok(tags)
+    }
+
+    /// 替换游戏的全部标签；标签去除首尾空白后按小写去重，保留首次出现的写法用于显示
+    pub async fn set_tags(&self, id: &str, tags: Vec<String>) -> Result<Vec<String>, String> {
+        let mut seen = HashSet::new();
+        let tags: Vec<(String, String, String)> = tags
+            .iter()
+            .map(|t| t.trim())
+            .filter(|t| !t.is_empty())
+            .filter_map(|t| {
+                let normalized = t.to_lowercase();
+                seen.insert(normalized.clone())
+                    .then(|| (Uuid::new_v4().to_string(), normalized, t.to_string()))
+            })
+            .collect();
+
+        let mut db = self.db.lock().await;
+        Game::get_by_id(&mut *db, id)
+            .await
+            .map_err(|e| format!("查询游戏失败: {}", e))?;
+
+        // 删除与重新写入在同一事务中完成，中途失败不会丢失原有标签
+        let db_path = self.db_path.clone();
+        let game_id = id.to_string();
+        let rows = tags.clone();
+        tokio::task::spawn_blocking(move || {
+            crate::db::bulk::replace_tags(&db_path, &game_id, &rows)
+        })
+        .await
+        .map_err(|e| format!("保存游戏标签失败: {}", e))??;
+
+        let mut tags: Vec<(String, String)> = tags
+            .into_iter()
+            .map(|(_, tag, display)| (tag, display))
+            .collect();
+        tags.sort();
+        Ok(tags.into_iter().map(|(_, display)| display).collect())
+    }
+
+    /// 查询带有指定标签的游戏（标签按归一化形式匹配）
+    pub async fn get_games_by_tag(&self, tag: &str) -> Result<Vec<Game>, String> {
+        let normalized = tag.trim().to_lowercase();
+        let mut db = self.db.lock().await;
+        let game_ids: Vec<String> =
+            GameTag::filter(GameTag::fields().tag().eq(normalized.as_str()))
+                .exec(&mut *db)
+                .await
+                .map_err(|e| format!("查询游戏标签失败: {}", e))?
+                .into_iter()
+                .map(|t| t.game_id)
+                .collect();
+        if game_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut games = Game::filter(Game::fields().id().in_list(game_ids))
+            .exec(&mut *db)
+            .await
+            .map_err(|e| format!("查询游戏列表失败: {}", e))?;
+        sort_library(&mut games);
+
+        Ok(games)
+    }
+
     /// 更新游戏最后游玩时间
     pub async fn update_last_played(&self, id: &str) -> Result<(), String> {
         let mut db = self.db.lock().await;
@@ -265,6 +476,136 @@ impl GameService {
         Ok(())
     }
 
+    /// 恢复导入游戏的收藏与游玩统计（游戏库导入用）
+    pub async fn restore_stats(
+        &self,
+        id: &str,
+        favorite: bool,
+        play_count: i64,
+        total_play_ms: i64,
+        last_played_at: Option<i64>,
+    ) -> Result<(), String> {
+        let mut db = self.db.lock().await;
+        let mut game = Game::get_by_id(&mut *db, id)
+            .await
+            .map_err(|e| format!("查询游戏失败: {}", e))?;
+
+        game.update()
+            .favorite(favorite)
+            .play_count(play_count.max(0))
+            .total_play_ms(total_play_ms.max(0))
+            .last_played_at(last_played_at)
+            .updated_at(crate::utils::now_unix_ms())
+            .exec(&mut *db)
+            .await
+            .map_err(|e| format!("恢复游戏统计失败: {}", e))?;
+
+        Ok(())
+    }
+
+    /// 设置或取消收藏
+    pub async fn set_favorite(&self, id: &str, favorite: bool) -> Result<(), String> {
+        let mut db = self.db.lock().await;
+        let mut game = Game::get_by_id(&mut *db, id)
+            .await
+            .map_err(|e| format!("查询游戏失败: {}", e))?;
+
+        game.update()
+            .favorite(favorite)
+            .updated_at(crate::utils::now_unix_ms())
+            .exec(&mut *db)
+            .await
+            .map_err(|e| format!("更新收藏状态失败: {}", e))?;
+
+        Ok(())
+    }
+
+    /// 设置评分（0-5），`None` 清除评分
+    pub async fn set_rating(&self, id: &str, rating: Option<i32>) -> Result<(), String> {
+        if let Some(r) = rating
+            && !(MIN_RATING..=MAX_RATING).contains(&r)
+        {
+            return Err(format!(
+                "评分必须在 {} 到 {} 之间: {}",
+                MIN_RATING, MAX_RATING, r
+            ));
+        }
+
+        let mut db = self.db.lock().await;
+        let mut game = Game::get_by_id(&mut *db, id)
+            .await
+            .map_err(|e| format!("查询游戏失败: {}", e))?;
+
+        game.update()
+            .rating(rating)
+            .updated_at(crate::utils::now_unix_ms())
+            .exec(&mut *db)
+            .await
+            .map_err(|e| format!("更新评分失败: {}", e))?;
+
+        Ok(())
+    }
+
+    /// 设置固定的运行时版本，传空取消固定
+    pub async fn set_runtime_version(
+        &self,
+        id: &str,
+        version: Option<String>,
+    ) -> Result<(), String> {
+        let mut db = self.db.lock().await;
+        let mut game = Game::get_by_id(&mut *db, id)
+            .await
+            .map_err(|e| format!("查询游戏失败: {}", e))?;
+
+        game.update()
+            .runtime_version(version)
+            .updated_at(crate::utils::now_unix_ms())
+            .exec(&mut *db)
+            .await
+            .map_err(|e| format!("更新运行时版本失败: {}", e))?;
+
+        Ok(())
+    }
+
+    /// 设置完成状态
+    pub async fn set_status(&self, id: &str, status: GameStatus) -> Result<(), String> {
+        let mut db = self.db.lock().await;
+        let mut game = Game::get_by_id(&mut *db, id)
+            .await
+            .map_err(|e| format!("查询游戏失败: {}", e))?;
+
+        game.update()
+            .status(status.as_str().to_string())
+            .updated_at(crate::utils::now_unix_ms())
+            .exec(&mut *db)
+            .await
+            .map_err(|e| format!("更新游戏状态失败: {}", e))?;
+
+        Ok(())
+    }
+
+    /// 累加一次游玩时长（毫秒）；同一游戏的多个会话各自累加
+    pub async fn add_play_time(&self, id: &str, delta_ms: i64) -> Result<(), String> {
+        if delta_ms <= 0 {
+            return Ok(());
+        }
+
+        let mut db = self.db.lock().await;
+        let mut game = Game::get_by_id(&mut *db, id)
+            .await
+            .map_err(|e| format!("查询游戏失败: {}", e))?;
+
+        let total_play_ms = game.total_play_ms.saturating_add(delta_ms);
+        game.update()
+            .total_play_ms(total_play_ms)
+            .updated_at(crate::utils::now_unix_ms())
+            .exec(&mut *db)
+            .await
+            .map_err(|e| format!("更新游玩时长失败: {}", e))?;
+
+        Ok(())
+    }
+
     /// 更新游戏封面路径
     pub async fn update_cover_path(
         &self,
@@ -275,6 +616,10 @@ impl GameService {
         let mut game = Game::get_by_id(&mut *db, id)
             .await
             .map_err(|e| format!("查询游戏失败: {}", e))?;
+        // 重新扫描时封面通常没有变化，不改动 updated_at
+        if game.cover_path == cover_path {
+            return Ok(());
+        }
 
         game.update()
             .cover_path(cover_path)
@@ -287,34 +632,88 @@ impl GameService {
     }
 
     /// 转换为DTO
-    pub fn to_dto(&self, game: Game) -> GameDto {
-        let path_valid = Path::new(&game.game_path).exists();
+    pub async fn to_dto(&self, game: Game) -> Result<GameDto, String> {
+        let tags = self.get_tags(&game.id).await?;
+        Ok(Self::build_dto(&game, tags))
+    }
+
+    /// 由数据库记录和已查询的标签构建 DTO（会检查游戏与封面路径是否存在）
+    ///
+    /// 封面以 `gmcover://` 协议 URL 输出，由 [`crate::services::cover_protocol`] 提供文件。
+    pub fn build_dto(game: &Game, tags: Vec<String>) -> GameDto {
+        let cover = game
+            .cover_path
+            .as_deref()
+            .map(Path::new)
+            .filter(|path| path.is_file());
         GameDto {
-            id: game.id,
-            title: game.title,
-            engine_type: game.engine_type,
-            path: game.game_path,
-            game_type: game.game_type,
+            id: game.id.clone(),
+            title: game.title.clone(),
+            engine_type: game.engine_type.clone(),
+            path: game.game_path.clone(),
+            game_type: game.game_type.clone(),
             detection_confidence: game.detection_confidence,
-            path_valid,
-            runtime_version: game.runtime_version,
-            cover_path: game.cover_path,
+            path_valid: Path::new(&game.game_path).exists(),
+            runtime_version: game.runtime_version.clone(),
+            cover_path: cover.map(|_| cover_url(&game.profile_key, false, game.updated_at)),
+            cover_thumb_path: cover
+                .and_then(|cover| FileService::new().cover_thumbnail(cover))
+                .map(|_| cover_url(&game.profile_key, true, game.updated_at)),
             play_count: game.play_count,
+            total_play_ms: game.total_play_ms,
+            tags,
+            favorite: game.favorite,
+            rating: game.rating,
+            status: game.status.clone(),
             created_at: game.created_at,
             last_played_at: game.last_played_at,
             updated_at: game.updated_at,
         }
     }
 
-    /// 迁移profile目录命名（从UUID迁移到可读格式）
-    pub async fn migrate_profile_keys(&self, container_root: &Path) -> Result<(), String> {
+    /// 为数据库迁移中重命名的重复 profile_key 复制原先共用的 profile 目录，返回复制的数量
+    pub async fn copy_renamed_profiles(&self, container_root: &Path) -> Result<u32, String> {
+        let renamed = {
+            let mut db = self.db.lock().await;
+            crate::db::get_setting(&mut *db, SETTING_RENAMED_PROFILE_KEYS).await?
+        };
+        let Some(renamed) = renamed else {
+            return Ok(0);
+        };
+        let renamed: Vec<(String, String)> = serde_json::from_str(&renamed)
+            .map_err(|e| format!("解析重命名的 profile_key 失败: {}", e))?;
+        if renamed.is_empty() {
+            return Ok(0);
+        }
+
+        let file_service = FileService::new();
+        let mut copied = 0;
+        for (old_key, new_key) in &renamed {
+            if file_service.copy_profile_dir(container_root, old_key, new_key)? {
+                tracing::info!(
+                    old_key,
+                    new_key,
+                    "已为重命名的 profile_key 复制 profile 目录"
+                );
+                copied += 1;
+            }
+        }
+
+        let mut db = self.db.lock().await;
+        crate::db::set_setting(&mut *db, SETTING_RENAMED_PROFILE_KEYS, "[]").await?;
+        Ok(copied)
+    }
+
+    /// 迁移profile目录命名（从UUID迁移到可读格式），返回重命名的数量
+    pub async fn migrate_profile_keys(&self, container_root: &Path) -> Result<u32, String> {
         let games = self.get_all_games().await?;
         if games.is_empty() {
-            return Ok(());
+            return Ok(0);
         }
 
         let mut used: HashSet<String> = games.iter().map(|g| g.profile_key.clone()).collect();
         let file_service = FileService::new();
+        let mut migrated = 0;
 
         for game in games {
             if !self.needs_profile_key_migration(&game.profile_key) {
@@ -339,9 +738,10 @@ impl GameService {
                 .map_err(|e| format!("更新profile_key失败: {}", e))?;
 
             used.insert(new_key);
+            migrated += 1;
         }
 
-        Ok(())
+        Ok(migrated)
     }
 
     fn needs_profile_key_migration(&self, key: &str) -> bool {
@@ -364,24 +764,60 @@ impl GameService {
         }
         format_profile_key(&base, max_num + 1)
     }
+}
 
-    async fn generate_profile_key(&self, title: &str) -> Result<String, String> {
-        let base = sanitize_profile_base(title);
+async fn generate_profile_key(db: &mut toasty::Db, title: &str) -> Result<String, String> {
+    let base = sanitize_profile_base(title);
 
-        let mut db = self.db.lock().await;
-        let all_games = Game::all()
-            .exec(&mut *db)
-            .await
-            .map_err(|e| format!("读取profile_key失败: {}", e))?;
+    let all_games = Game::all()
+        .exec(&mut *db)
+        .await
+        .map_err(|e| format!("读取profile_key失败: {}", e))?;
 
-        let mut max_num = 0u32;
-        for game in all_games {
-            if let Some(num) = parse_profile_suffix(&game.profile_key, &base) {
-                max_num = max_num.max(num);
-            }
+    let mut max_num = 0u32;
+    for game in all_games {
+        if let Some(num) = parse_profile_suffix(&game.profile_key, &base) {
+            max_num = max_num.max(num);
         }
+    }
+
+    Ok(format_profile_key(&base, max_num + 1))
+}
 
-        Ok(format_profile_key(&base, max_num + 1))
+/// 删除游戏的全部标签行
+async fn delete_tags_of(db: &mut toasty::Db, game_id: &str) -> Result<(), String> {
+    let rows = GameTag::filter(GameTag::fields().game_id().eq(game_id))
+        .exec(&mut *db)
+        .await
+        .map_err(|e| format!("查询游戏标签失败: {}", e))?;
+    for row in rows {
+        GameTag::delete_by_id(&mut *db, &row.id)
+            .await
+            .map_err(|e| format!("删除游戏标签失败: {}", e))?;
+    }
+    Ok(())
+}
+
+/// 游戏库默认顺序：收藏在前，组内按添加时间倒序
+fn sort_library(games: &mut [Game]) {
+    games.sort_by(|a, b| {
+        b.favorite
+            .cmp(&a.favorite)
+            .then_with(|| b.created_at.cmp(&a.created_at))
+    });
+}
+
+/// 比较可空字段，`None` 始终排在最后
+fn cmp_none_last<T: Ord>(
+    a: Option<T>,
+    b: Option<T>,
+    ordered: impl Fn(Ordering) -> Ordering,
+) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => ordered(a.cmp(&b)),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
     }
 }
 
@@ -419,12 +855,116 @@ fn format_profile_key(base: &str, num: u32) -> String {
 
 fn infer_game_type_from_engine(engine_type: &str) -> String {
     match EngineType::from_str(engine_type) {
-        EngineType::RenPy => "visual_novel".to_string(),
-        EngineType::RpgMakerVX
+        EngineType::RenPy | EngineType::Tyrano | EngineType::KiriKiri => "visual_novel".to_string(),
+        EngineType::RpgMaker2000
+        | EngineType::RpgMakerXP
+        | EngineType::RpgMakerVX
         | EngineType::RpgMakerVXAce
         | EngineType::RpgMakerMV
-        | EngineType::RpgMakerMZ => "rpg".to_string(),
+        | EngineType::RpgMakerMZ
+        | EngineType::WolfRpg => "rpg".to_string(),
         EngineType::Unity | EngineType::Godot => "game".to_string(),
         EngineType::Html | EngineType::Other => "unknown".to_string(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn service() -> (tempfile::TempDir, GameService) {
+        let tmp = tempfile::tempdir().unwrap();
+        let db_path = crate::db::database_path(tmp.path());
+        let db = crate::db::init_db(&db_path).await.unwrap();
+        (tmp, GameService::new(Arc::new(Mutex::new(db)), db_path))
+    }
+
+    async fn add(service: &GameService, tmp: &tempfile::TempDir, title: &str) -> Game {
+        let path = tmp.path().join("games").join(title);
+        std::fs::create_dir_all(&path).unwrap();
+        service
+            .add_game(AddGameInput {
+                title: Some(title.to_string()),
+                engine_type: "other".to_string(),
+                path: path.to_string_lossy().to_string(),
+                game_type: None,
+                detection_confidence: None,
+                metadata_json: None,
+                runtime_version: None,
+            })
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn games_by_tag_loads_every_tagged_game_favorites_first() {
+        let (tmp, service) = service().await;
+        let a = add(&service, &tmp, "Alpha").await;
+        let b = add(&service, &tmp, "Beta").await;
+        let c = add(&service, &tmp, "Gamma").await;
+        service.set_tags(&a.id, vec!["RPG".into()]).await.unwrap();
+        service.set_tags(&b.id, vec!["Short".into()]).await.unwrap();
+        service
+            .set_tags(&c.id, vec!["rpg".into(), "Short".into()])
+            .await
+            .unwrap();
+        service.set_favorite(&c.id, true).await.unwrap();
+
+        let ids: Vec<String> = service
+            .get_games_by_tag(" Rpg ")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|g| g.id)
+            .collect();
+        assert_eq!(ids, vec![c.id.clone(), a.id.clone()]);
+        assert!(service.get_games_by_tag("none").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn search_ranks_sql_matches() {
+        let (tmp, service) = service().await;
+        let contains = add(&service, &tmp, "The Quest").await;
+        let exact = add(&service, &tmp, "Quest").await;
+        let prefix = add(&service, &tmp, "Questing 100%").await;
+        add(&service, &tmp, "Other").await;
+
+        let ids: Vec<String> = service
+            .search_games("QUEST")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|g| g.id)
+            .collect();
+        assert_eq!(ids, vec![exact.id, prefix.id.clone(), contains.id]);
+
+        // LIKE 通配符按字面匹配
+        let ids: Vec<String> = service
+            .search_games("100%")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|g| g.id)
+            .collect();
+        assert_eq!(ids, vec![prefix.id]);
+        assert!(service.search_games("q_est").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn set_tags_replaces_previous_tags() {
+        let (tmp, service) = service().await;
+        let game = add(&service, &tmp, "Alpha").await;
+        service
+            .set_tags(&game.id, vec!["RPG".into(), "rpg".into(), "Short".into()])
+            .await
+            .unwrap();
+        let saved = service
+            .set_tags(&game.id, vec!["Long ".into()])
+            .await
+            .unwrap();
+
+        assert_eq!(saved, vec!["Long".to_string()]);
+        assert_eq!(service.get_tags(&game.id).await.unwrap(), saved);
+        assert!(service.get_games_by_tag("rpg").await.unwrap().is_empty());
+    }
+}
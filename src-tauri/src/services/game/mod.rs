@@ -1,2 +1,8 @@
+pub mod arg_template;
+pub mod hooks;
 pub mod launcher;
 pub mod manager;
+pub mod nwjs_entry;
+pub mod screenshot;
+pub mod session;
+pub mod title;
@@ -0,0 +1,153 @@
+//! NW.js 游戏（MV/MZ 等）的入口定位：优先使用 package.json 的 `main`，否则查找最近的 index.html。
+//!
+//! 常见布局：MV 部署版根目录 package.json 的 `main` 为 `www/index.html`；MZ 与部分 MV 游戏
+//! 的 index.html 位于根目录；也有只保留 `www/package.json` 或完全没有 package.json 的。
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+
+/// 可能放置 package.json 的目录（相对游戏目录）
+const PACKAGE_DIRS: &[&str] = &["", "www"];
+/// 查找 index.html 的最大目录深度
+const MAX_INDEX_DEPTH: usize = 3;
+
+/// 解析入口，返回相对游戏目录、以 `/` 分隔的路径（空字符串表示游戏目录本身）
+///
+/// 返回目录时 NW.js 读取其中的 package.json；返回 HTML 文件时启动器会在它旁边生成最小 package.json。
+pub fn find_nwjs_entry(game_dir: &Path) -> Option<String> {
+    if let Some(dir) = PACKAGE_DIRS
+        .iter()
+        .find(|dir| package_main_exists(&game_dir.join(dir)))
+    {
+        return Some(dir.to_string());
+    }
+
+    let index = find_nearest_index(game_dir)?;
+    let rel = index.strip_prefix(game_dir).ok()?;
+    // index.html 旁边已有 package.json（`main` 缺失或无效）时仍以目录启动，保留其中的窗口配置
+    if index.with_file_name("package.json").is_file() {
+        return Some(rel.parent().map(to_slash).unwrap_or_default());
+    }
+    Some(to_slash(rel))
+}
+
+/// 目录中的 package.json 存在且 `main` 指向已存在的文件
+fn package_main_exists(dir: &Path) -> bool {
+    let Ok(content) = std::fs::read_to_string(dir.join("package.json")) else {
+        return false;
+    };
+    let Ok(value) =
+        serde_json::from_str::<serde_json::Value>(content.trim_start_matches('\u{feff}'))
+    else {
+        return false;
+    };
+    value
+        .get("main")
+        .and_then(|main| main.as_str())
+        .map(|main| main.trim().trim_start_matches("./"))
+        .filter(|main| !main.is_empty())
+        .is_some_and(|main| dir.join(main).is_file())
+}
+
+/// 按广度优先查找离游戏目录最近的 index.html（跳过隐藏目录与 NW.js 运行时目录）
+fn find_nearest_index(game_dir: &Path) -> Option<PathBuf> {
+    let mut pending = VecDeque::from([(game_dir.to_path_buf(), 0)]);
+    while let Some((dir, depth)) = pending.pop_front() {
+        let index = dir.join("index.html");
+        if index.is_file() {
+            return Some(index);
+        }
+        if depth >= MAX_INDEX_DEPTH || crate::commands::game::game::is_nwjs_runtime_dir(&dir) {
+            continue;
+        }
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut children: Vec<_> = entries
+            .flatten()
+            .filter(|entry| entry.file_type().is_ok_and(|ty| ty.is_dir()))
+            .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+            .map(|entry| entry.path())
+            .collect();
+        children.sort();
+        pending.extend(children.into_iter().map(|child| (child, depth + 1)));
+    }
+    None
+}
+
+fn to_slash(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 在临时目录中按 (相对路径, 内容) 创建文件
+    fn layout(files: &[(&str, &str)]) -> tempfile::TempDir {
+        let tmp = tempfile::tempdir().unwrap();
+        for (path, content) in files {
+            let path = tmp.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        tmp
+    }
+
+    #[test]
+    fn root_package_with_www_main() {
+        let tmp = layout(&[
+            ("package.json", r#"{"main": "www/index.html"}"#),
+            ("www/index.html", ""),
+        ]);
+        assert_eq!(find_nwjs_entry(tmp.path()).as_deref(), Some(""));
+    }
+
+    #[test]
+    fn root_index_without_package() {
+        let tmp = layout(&[("index.html", "")]);
+        assert_eq!(find_nwjs_entry(tmp.path()).as_deref(), Some("index.html"));
+    }
+
+    #[test]
+    fn www_package_only() {
+        let tmp = layout(&[
+            ("www/package.json", r#"{"main": "index.html"}"#),
+            ("www/index.html", ""),
+        ]);
+        assert_eq!(find_nwjs_entry(tmp.path()).as_deref(), Some("www"));
+    }
+
+    #[test]
+    fn www_index_without_package() {
+        let tmp = layout(&[("www/index.html", "")]);
+        assert_eq!(
+            find_nwjs_entry(tmp.path()).as_deref(),
+            Some("www/index.html")
+        );
+    }
+
+    #[test]
+    fn invalid_main_falls_back_to_index() {
+        let tmp = layout(&[
+            ("package.json", r#"{"main": "missing.html"}"#),
+            ("www/index.html", ""),
+        ]);
+        assert_eq!(
+            find_nwjs_entry(tmp.path()).as_deref(),
+            Some("www/index.html")
+        );
+    }
+
+    #[test]
+    fn invalid_main_keeps_package_dir() {
+        let tmp = layout(&[
+            ("package.json", r#"{"main": "missing.html"}"#),
+            ("index.html", ""),
+        ]);
+        assert_eq!(find_nwjs_entry(tmp.path()).as_deref(), Some(""));
+    }
+}
@@ -0,0 +1,138 @@
+//! 截取游戏窗口画面（用于没有可用图标的游戏封面）。
+//!
+//! 目前仅支持 Linux：sway 等 wlroots 合成器用 `swaymsg` 定位窗口、`grim` 截图；
+//! X11（含 Xwayland）用 `xdotool` 按 pid 查找窗口、ImageMagick `import` 截图。
+
+use std::path::Path;
+
+/// 截取属于 `pid`（或其子进程）的窗口并保存为 PNG
+#[cfg(target_os = "linux")]
+pub fn capture_window(pid: u32, dest: &Path) -> Result<(), String> {
+    use std::process::Command;
+
+    let pids = process_tree(pid);
+
+    if std::env::var_os("WAYLAND_DISPLAY").is_some()
+        && program_exists("grim")
+        && let Some(geometry) = sway_window_geometry(&pids)
+    {
+        let status = Command::new("grim")
+            .arg("-g")
+            .arg(&geometry)
+            .arg(dest)
+            .status()
+            .map_err(|e| format!("执行 grim 失败: {}", e))?;
+        return if status.success() {
+            Ok(())
+        } else {
+            Err("grim 截图失败".to_string())
+        };
+    }
+
+    if std::env::var_os("DISPLAY").is_none() {
+        return Err("未找到可截取的显示服务（需要 X11/Xwayland 或 sway + grim）".to_string());
+    }
+    if !program_exists("xdotool") || !program_exists("import") {
+        return Err("截取窗口需要安装 xdotool 和 ImageMagick（import）".to_string());
+    }
+    let window = x11_window_for_pids(&pids).ok_or_else(|| "未找到游戏窗口".to_string())?;
+    let status = Command::new("import")
+        .args(["-window", &window])
+        .arg(dest)
+        .status()
+        .map_err(|e| format!("执行 import 失败: {}", e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err("import 截图失败".to_string())
+    }
+}
+
+/// 其它平台暂不支持
+#[cfg(not(target_os = "linux"))]
+pub fn capture_window(_pid: u32, _dest: &Path) -> Result<(), String> {
+    Err("当前平台暂不支持截取游戏窗口".to_string())
+}
+
+/// `pid` 及其全部子孙进程（wine、启动脚本会再派生真正的游戏进程）
+#[cfg(target_os = "linux")]
+fn process_tree(pid: u32) -> Vec<u32> {
+    let mut pids = vec![pid];
+    let mut index = 0;
+    while index < pids.len() {
+        let current = pids[index];
+        index += 1;
+        let Ok(tasks) = std::fs::read_dir(format!("/proc/{}/task", current)) else {
+            continue;
+        };
+        for task in tasks.flatten() {
+            let Ok(children) = std::fs::read_to_string(task.path().join("children")) else {
+                continue;
+            };
+            for child in children.split_whitespace().filter_map(|c| c.parse().ok()) {
+                if !pids.contains(&child) {
+                    pids.push(child);
+                }
+            }
+        }
+    }
+    pids
+}
+
+#[cfg(target_os = "linux")]
+fn program_exists(name: &str) -> bool {
+    crate::services::LauncherService::new()
+        .find_program(name)
+        .is_some()
+}
+
+/// 通过 `xdotool search --pid` 查找第一个可见窗口 ID
+#[cfg(target_os = "linux")]
+fn x11_window_for_pids(pids: &[u32]) -> Option<String> {
+    pids.iter().find_map(|pid| {
+        let output = std::process::Command::new("xdotool")
+            .args(["search", "--onlyvisible", "--pid", &pid.to_string()])
+            .output()
+            .ok()?;
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map(str::to_string)
+    })
+}
+
+/// 从 `swaymsg -t get_tree` 中查找属于这些进程的可见窗口，返回 grim 的 `-g` 参数
+#[cfg(target_os = "linux")]
+fn sway_window_geometry(pids: &[u32]) -> Option<String> {
+    fn find(node: &serde_json::Value, pids: &[u32]) -> Option<String> {
+        let pid = node.get("pid").and_then(|v| v.as_u64());
+        let visible = node.get("visible").and_then(|v| v.as_bool()) == Some(true);
+        if visible && pid.is_some_and(|pid| pids.iter().any(|p| u64::from(*p) == pid)) {
+            let rect = node.get("rect")?;
+            let value = |key: &str| rect.get(key).and_then(|v| v.as_i64());
+            return Some(format!(
+                "{},{} {}x{}",
+                value("x")?,
+                value("y")?,
+                value("width")?,
+                value("height")?
+            ));
+        }
+        ["nodes", "floating_nodes"]
+            .iter()
+            .filter_map(|key| node.get(*key).and_then(|v| v.as_array()))
+            .flatten()
+            .find_map(|child| find(child, pids))
+    }
+
+    let output = std::process::Command::new("swaymsg")
+        .args(["-t", "get_tree"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let tree: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+    find(&tree, pids)
+}
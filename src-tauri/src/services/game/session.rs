@@ -0,0 +1,474 @@
+//! 运行中游戏跟踪：保存已启动的子进程，后台等待其退出并通知前端。
+
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::process::Child;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// 退出检测轮询间隔
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// 终止游戏时 SIGTERM 与 SIGKILL 之间的宽限期，留给 NW.js 等写入存档
+#[cfg(unix)]
+const STOP_GRACE_PERIOD: Duration = Duration::from_secs(3);
+
+/// 一次运行中的游戏会话
+pub struct RunningGame {
+    pub pid: u32,
+    /// 启动时间（Unix 毫秒）
+    pub started_at: i64,
+    started: Instant,
+    child: Child,
+}
+
+/// 运行中游戏表：key = 游戏 ID
+pub type RunningGames = Arc<StdMutex<HashMap<String, Vec<RunningGame>>>>;
+
+/// 正在启动、尚未登记到运行表的游戏 ID
+pub type LaunchingGames = Arc<StdMutex<HashSet<String>>>;
+
+/// 启动中的占位，drop 时移除；应在进程登记到运行表之后再释放
+pub struct LaunchReservation {
+    launching: LaunchingGames,
+    game_id: String,
+}
+
+impl Drop for LaunchReservation {
+    fn drop(&mut self) {
+        self.launching.lock().unwrap().remove(&self.game_id);
+    }
+}
+
+/// 确认游戏既未运行也不在启动中，并登记为启动中。
+///
+/// 检查与登记都在运行表的锁内完成，连续两次启动请求只有一次能通过。
+pub fn reserve_launch(
+    running: &RunningGames,
+    launching: &LaunchingGames,
+    game_id: &str,
+) -> Result<LaunchReservation, String> {
+    let mut map = running.lock().unwrap();
+    let alive = map.get_mut(game_id).is_some_and(|sessions| {
+        sessions
+            .iter_mut()
+            .any(|s| matches!(s.child.try_wait(), Ok(None)))
+    });
+    if alive {
+        return Err("游戏已在运行中".to_string());
+    }
+    if !launching.lock().unwrap().insert(game_id.to_string()) {
+        return Err("游戏正在启动中".to_string());
+    }
+    Ok(LaunchReservation {
+        launching: launching.clone(),
+        game_id: game_id.to_string(),
+    })
+}
+
+/// 不限制同时运行数时的名额数
+const UNLIMITED_SLOTS: usize = Semaphore::MAX_PERMITS;
+
+/// 同时运行游戏数的名额，每个运行中的游戏（无论是否设置了上限）占用一个，退出后释放
+///
+/// 上限变化时按差值增减同一个信号量的名额；调低上限时已被占用、暂时无法收回的名额
+/// 记为欠账，在归还时直接作废，因此已运行的游戏始终计入新上限。
+pub struct LaunchSlots {
+    semaphore: Arc<Semaphore>,
+    state: Arc<StdMutex<SlotState>>,
+}
+
+struct SlotState {
+    /// 当前名额总数
+    size: usize,
+    /// 归还时需要作废的名额数
+    debt: usize,
+}
+
+/// 占用中的名额，drop 时归还
+pub struct LaunchSlot {
+    permit: Option<OwnedSemaphorePermit>,
+    state: Arc<StdMutex<SlotState>>,
+}
+
+impl Drop for LaunchSlot {
+    fn drop(&mut self) {
+        let Some(permit) = self.permit.take() else {
+            return;
+        };
+        let mut state = self.state.lock().unwrap();
+        if state.debt > 0 {
+            state.debt -= 1;
+            permit.forget();
+        }
+    }
+}
+
+impl LaunchSlots {
+    pub fn new() -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(UNLIMITED_SLOTS)),
+            state: Arc::new(StdMutex::new(SlotState {
+                size: UNLIMITED_SLOTS,
+                debt: 0,
+            })),
+        }
+    }
+
+    /// 应用同时运行上限（0 为不限制）
+    pub fn set_limit(&self, limit: u32) {
+        let size = if limit == 0 {
+            UNLIMITED_SLOTS
+        } else {
+            limit as usize
+        };
+        let mut state = self.state.lock().unwrap();
+        if size > state.size {
+            let mut added = size - state.size;
+            let cancelled = added.min(state.debt);
+            state.debt -= cancelled;
+            added -= cancelled;
+            self.semaphore.add_permits(added);
+        } else if size < state.size {
+            let removed = state.size - size;
+            let forgotten = self.semaphore.forget_permits(removed);
+            state.debt += removed - forgotten;
+        }
+        state.size = size;
+    }
+
+    /// 立即占用一个名额；已满时返回 `None`
+    pub fn try_acquire(&self) -> Option<LaunchSlot> {
+        let permit = self.semaphore.clone().try_acquire_owned().ok()?;
+        Some(self.slot(permit))
+    }
+
+    /// 等待空闲名额
+    pub async fn acquire(&self) -> Result<LaunchSlot, String> {
+        let permit = self
+            .semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|e| format!("等待启动名额失败: {}", e))?;
+        Ok(self.slot(permit))
+    }
+
+    fn slot(&self, permit: OwnedSemaphorePermit) -> LaunchSlot {
+        LaunchSlot {
+            permit: Some(permit),
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl Default for LaunchSlots {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `game_exited` 事件负载
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameExitedEvent {
+    pub game_id: String,
+    pub pid: u32,
+    /// 退出码（被信号终止时为空）
+    pub exit_code: Option<i32>,
+    /// 运行时长（毫秒）
+    pub duration_ms: i64,
+}
+
+/// 运行中的游戏实例（`get_running_games` 返回）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunningGameInfo {
+    pub game_id: String,
+    pub pid: u32,
+    /// 启动时间（Unix 毫秒），无法获取时为 0
+    pub started_at: i64,
+    /// 由本次运行启动并跟踪；为 false 表示应用重启前启动、按工作目录找回的进程，
+    /// 此时无法捕获退出事件
+    pub tracked: bool,
+}
+
+/// 登记新启动的游戏进程并返回其 pid。
+pub fn track(running: &RunningGames, game_id: &str, child: Child) -> u32 {
+    let pid = child.id();
+    let session = RunningGame {
+        pid,
+        started_at: crate::utils::now_unix_ms(),
+        started: Instant::now(),
+        child,
+    };
+    running
+        .lock()
+        .unwrap()
+        .entry(game_id.to_string())
+        .or_default()
+        .push(session);
+    pid
+}
+
+/// 终止游戏的全部运行实例（阻塞），返回被终止的进程数。
+///
+/// Unix 上先发送 SIGTERM，宽限期后仍未退出再强制结束；其余平台直接 `kill()`。
+/// 运行表条目由退出监视线程回收并发送 `game_exited` 事件。
+pub fn stop(running: &RunningGames, game_id: &str) -> Result<usize, String> {
+    let pids: Vec<u32> = {
+        let map = running.lock().unwrap();
+        map.get(game_id)
+            .map(|sessions| sessions.iter().map(|s| s.pid).collect())
+            .unwrap_or_default()
+    };
+    if pids.is_empty() {
+        return Err("游戏当前未在运行".to_string());
+    }
+
+    #[cfg(unix)]
+    {
+        for pid in &pids {
+            let _ = std::process::Command::new("kill")
+                .args(["-TERM", &pid.to_string()])
+                .status();
+        }
+
+        let deadline = Instant::now() + STOP_GRACE_PERIOD;
+        while Instant::now() < deadline {
+            if !is_running(running, game_id) {
+                return Ok(pids.len());
+            }
+            std::thread::sleep(EXIT_POLL_INTERVAL / 5);
+        }
+    }
+
+    let mut map = running.lock().unwrap();
+    if let Some(sessions) = map.get_mut(game_id) {
+        for session in sessions.iter_mut() {
+            if matches!(session.child.try_wait(), Ok(None)) {
+                session
+                    .child
+                    .kill()
+                    .map_err(|e| format!("终止游戏失败: {}", e))?;
+            }
+        }
+    }
+
+    Ok(pids.len())
+}
+
+/// 游戏是否仍有存活的进程（通过 `try_wait` 重新确认）。
+///
+/// 已退出的会话不在这里移除：退出监视线程会在下一次轮询时把它移出运行表，
+/// 并据此记录游玩时长、发送 `game_exited` 事件。
+pub fn is_running(running: &RunningGames, game_id: &str) -> bool {
+    let mut map = running.lock().unwrap();
+    map.get_mut(game_id).is_some_and(|sessions| {
+        sessions
+            .iter_mut()
+            .any(|s| matches!(s.child.try_wait(), Ok(None)))
+    })
+}
+
+/// 全部仍存活的已跟踪实例（通过 `try_wait` 重新确认），按启动时间排序。
+///
+/// 已退出的会话不会返回；它们由退出监视线程移出运行表，以便记录游玩时长。
+pub fn list(running: &RunningGames) -> Vec<RunningGameInfo> {
+    let mut map = running.lock().unwrap();
+    let mut list: Vec<RunningGameInfo> = map
+        .iter_mut()
+        .flat_map(|(game_id, sessions)| {
+            sessions
+                .iter_mut()
+                .filter(|s| matches!(s.child.try_wait(), Ok(None)))
+                .map(move |s| RunningGameInfo {
+                    game_id: game_id.clone(),
+                    pid: s.pid,
+                    started_at: s.started_at,
+                    tracked: true,
+                })
+        })
+        .collect();
+    list.sort_by_key(|info| info.started_at);
+    list
+}
+
+/// 查找工作目录为游戏目录、但不在运行表中的进程（应用重启前启动的游戏）
+///
+/// `games` 为 (游戏 ID, 规范化后的游戏目录)。已有跟踪实例的游戏会跳过，
+/// 以免把 wine 等子进程重复计入；每个游戏只返回最早启动的进程。
+#[cfg(target_os = "linux")]
+pub fn find_untracked(
+    running: &RunningGames,
+    games: &[(String, std::path::PathBuf)],
+) -> Vec<RunningGameInfo> {
+    let tracked_games: std::collections::HashSet<String> =
+        running.lock().unwrap().keys().cloned().collect();
+    let own_pid = std::process::id();
+    let boot_ms = boot_time_ms();
+
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    let mut found: HashMap<String, RunningGameInfo> = HashMap::new();
+    for entry in entries.flatten() {
+        let Some(pid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|n| n.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        if pid == own_pid {
+            continue;
+        }
+        // 僵尸进程或无权限访问的进程读取不到 cwd
+        let Ok(cwd) = std::fs::read_link(entry.path().join("cwd")) else {
+            continue;
+        };
+        let Some((game_id, _)) = games
+            .iter()
+            .find(|(id, dir)| *dir == cwd && !tracked_games.contains(id))
+        else {
+            continue;
+        };
+        let started_at = boot_ms
+            .and_then(|boot_ms| process_start_ms(pid, boot_ms))
+            .unwrap_or(0);
+        if found
+            .get(game_id)
+            .is_none_or(|existing| started_at < existing.started_at)
+        {
+            found.insert(
+                game_id.clone(),
+                RunningGameInfo {
+                    game_id: game_id.clone(),
+                    pid,
+                    started_at,
+                    tracked: false,
+                },
+            );
+        }
+    }
+    let mut list: Vec<RunningGameInfo> = found.into_values().collect();
+    list.sort_by_key(|info| info.started_at);
+    list
+}
+
+/// 其它平台无法按工作目录查找进程
+#[cfg(not(target_os = "linux"))]
+pub fn find_untracked(
+    running: &RunningGames,
+    games: &[(String, std::path::PathBuf)],
+) -> Vec<RunningGameInfo> {
+    let _ = (running, games);
+    Vec::new()
+}
+
+/// 系统启动时间（Unix 毫秒），取自 `/proc/stat` 的 `btime`
+#[cfg(target_os = "linux")]
+fn boot_time_ms() -> Option<i64> {
+    let stat = std::fs::read_to_string("/proc/stat").ok()?;
+    let secs: i64 = stat
+        .lines()
+        .find_map(|line| line.strip_prefix("btime "))?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(secs * 1000)
+}
+
+/// 进程启动时间（Unix 毫秒），由 `/proc/<pid>/stat` 第 22 个字段换算
+#[cfg(target_os = "linux")]
+fn process_start_ms(pid: u32, boot_ms: i64) -> Option<i64> {
+    /// Linux 用户态时钟频率（USER_HZ），各主流架构均为 100
+    const CLOCK_TICKS_PER_SEC: i64 = 100;
+
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    // 进程名可能包含空格和括号，从最后一个 `)` 之后开始按字段解析（第 3 个字段起）
+    let fields: Vec<&str> = stat
+        .get(stat.rfind(')')? + 1..)?
+        .split_whitespace()
+        .collect();
+    let ticks: i64 = fields.get(19)?.parse().ok()?;
+    Some(boot_ms + ticks * 1000 / CLOCK_TICKS_PER_SEC)
+}
+
+/// `pid` 是否为该游戏当前登记的运行实例
+pub fn is_tracked_pid(running: &RunningGames, game_id: &str, pid: u32) -> bool {
+    running
+        .lock()
+        .unwrap()
+        .get(game_id)
+        .is_some_and(|sessions| sessions.iter().any(|s| s.pid == pid))
+}
+
+/// 在阻塞线程中等待游戏退出，退出后移出运行表、调用 `on_exit` 并发送 `game_exited` 事件。
+///
+/// `Child` 必须留在运行表中以便随时终止，因此这里按固定间隔 `try_wait`，
+/// 而不是把进程所有权交给 `wait()`。
+pub fn spawn_exit_watcher<F>(
+    app: AppHandle,
+    running: RunningGames,
+    game_id: String,
+    pid: u32,
+    on_exit: F,
+) where
+    F: FnOnce(&GameExitedEvent) + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || {
+        loop {
+            let finished = {
+                let mut map = running.lock().unwrap();
+                let Some(sessions) = map.get_mut(&game_id) else {
+                    return;
+                };
+                let Some(index) = sessions.iter().position(|s| s.pid == pid) else {
+                    return;
+                };
+                match sessions[index].child.try_wait() {
+                    Ok(Some(status)) => {
+                        let session = sessions.remove(index);
+                        if sessions.is_empty() {
+                            map.remove(&game_id);
+                        }
+                        Some((status.code(), session.started.elapsed()))
+                    }
+                    Ok(None) => None,
+                    Err(e) => {
+                        tracing::warn!(game_id = %game_id, pid, error = %e, "等待游戏进程失败");
+                        let session = sessions.remove(index);
+                        if sessions.is_empty() {
+                            map.remove(&game_id);
+                        }
+                        Some((None, session.started.elapsed()))
+                    }
+                }
+            };
+
+            if let Some((exit_code, elapsed)) = finished {
+                let duration_ms = elapsed.as_millis() as i64;
+                tracing::info!(
+                    game_id = %game_id,
+                    pid,
+                    exit_code = ?exit_code,
+                    duration_ms,
+                    "游戏已退出"
+                );
+                let event = GameExitedEvent {
+                    game_id,
+                    pid,
+                    exit_code,
+                    duration_ms,
+                };
+                on_exit(&event);
+                let _ = app.emit("game_exited", event);
+                return;
+            }
+
+            std::thread::sleep(EXIT_POLL_INTERVAL);
+        }
+    });
+}
@@ -0,0 +1,50 @@
+//! 从游戏数据文件读取真实标题，避免使用无意义的目录名。
+
+use std::path::Path;
+
+/// MV/MZ 的 System.json 候选位置
+const SYSTEM_JSON_PATHS: &[&str] = &["www/data/System.json", "data/System.json"];
+
+/// 读取游戏标题：MV/MZ 读取 System.json 的 `gameTitle`，XP/VX/VX Ace 读取 Game.ini 的 `Title=`
+pub fn read_game_title(game_dir: &Path) -> Option<String> {
+    SYSTEM_JSON_PATHS
+        .iter()
+        .find_map(|rel| read_system_json_title(&game_dir.join(rel)))
+        .or_else(|| read_game_ini_title(&game_dir.join("Game.ini")))
+}
+
+fn read_system_json_title(path: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let value: serde_json::Value =
+        serde_json::from_str(content.trim_start_matches('\u{feff}')).ok()?;
+    non_empty(value.get("gameTitle")?.as_str()?)
+}
+
+/// Game.ini 多为日文 Windows 下保存的 Shift_JIS，非 UTF-8 时按 Shift_JIS 解码
+fn read_game_ini_title(path: &Path) -> Option<String> {
+    let bytes = std::fs::read(path).ok()?;
+    let content = match std::str::from_utf8(&bytes) {
+        Ok(text) => text.trim_start_matches('\u{feff}').to_string(),
+        Err(_) => {
+            let (text, _, had_errors) = encoding_rs::SHIFT_JIS.decode(&bytes);
+            if had_errors {
+                return None;
+            }
+            text.into_owned()
+        }
+    };
+
+    content.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        if key.trim().eq_ignore_ascii_case("title") {
+            non_empty(value)
+        } else {
+            None
+        }
+    })
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
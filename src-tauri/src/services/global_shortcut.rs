@@ -0,0 +1,58 @@
+//! 显示/隐藏主窗口的全局快捷键。
+
+use crate::models::SETTING_GLOBAL_SHORTCUT;
+use std::sync::Arc;
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
+use tokio::sync::Mutex;
+
+/// 解析快捷键（如 `CommandOrControl+Shift+G`）
+pub fn parse(accelerator: &str) -> Result<Shortcut, String> {
+    accelerator
+        .trim()
+        .parse::<Shortcut>()
+        .map_err(|e| format!("无效的快捷键 {}: {}", accelerator, e))
+}
+
+/// 启动时注册已保存的快捷键；未设置时跳过，注册失败只记录日志
+pub fn register_saved<R: Runtime>(app: &AppHandle<R>, db: &Arc<Mutex<toasty::Db>>) {
+    let db = db.clone();
+    let saved = tauri::async_runtime::block_on(async move {
+        let mut db_lock = db.lock().await;
+        crate::db::get_setting(&mut db_lock, SETTING_GLOBAL_SHORTCUT).await
+    });
+    let Some(accelerator) = saved.ok().flatten().filter(|v| !v.is_empty()) else {
+        return;
+    };
+    let result = parse(&accelerator).and_then(|shortcut| {
+        app.global_shortcut()
+            .register(shortcut)
+            .map_err(|e| e.to_string())
+    });
+    match result {
+        Ok(()) => tracing::info!(shortcut = %accelerator, "已注册全局快捷键"),
+        Err(e) => tracing::warn!(shortcut = %accelerator, error = %e, "注册全局快捷键失败"),
+    }
+}
+
+/// 用 `new` 替换已注册的 `old`；新快捷键注册失败（如被其它程序占用）时恢复旧的
+pub fn replace<R: Runtime>(
+    app: &AppHandle<R>,
+    old: Option<Shortcut>,
+    new: Option<Shortcut>,
+) -> Result<(), String> {
+    let manager = app.global_shortcut();
+    if let Some(old) = old {
+        let _ = manager.unregister(old);
+    }
+    let Some(new) = new else {
+        return Ok(());
+    };
+    if let Err(e) = manager.register(new) {
+        if let Some(old) = old {
+            let _ = manager.register(old);
+        }
+        return Err(format!("注册快捷键失败: {}", e));
+    }
+    Ok(())
+}
@@ -1,9 +1,13 @@
+pub mod cover_protocol;
 pub mod download;
 pub mod engine;
 pub mod extension;
 pub mod fs;
 pub mod game;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+pub mod global_shortcut;
 pub mod logger;
+pub mod window_state;
 
 pub use engine::EngineService;
 pub use fs::{ArchiveService, FileService};
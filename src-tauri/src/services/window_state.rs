@@ -0,0 +1,175 @@
+//! 主窗口状态（大小、位置、最大化）的保存与恢复。
+
+use crate::models::{SETTING_WINDOW_STATE, WindowState};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Manager, PhysicalPosition, PhysicalSize, Runtime, WebviewWindow, Window};
+use tokio::sync::Mutex;
+
+/// 主窗口标签
+pub const MAIN_WINDOW: &str = "main";
+/// 恢复位置时窗口至少要有这么多像素落在某个显示器内，否则放弃恢复位置
+const MIN_VISIBLE_PX: i32 = 64;
+/// 大小/位置停止变化多久后写入
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// 每次调度保存递增，延迟结束时只有最后一次调度会写入
+static SAVE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// 显示或隐藏主窗口（托盘菜单与全局快捷键共用）
+pub fn toggle_main_window<R: Runtime>(app: &AppHandle<R>) {
+    let Some(window) = app.get_webview_window(MAIN_WINDOW) else {
+        return;
+    };
+    if window.is_visible().unwrap_or(true) {
+        let _ = window.hide();
+    } else {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+/// 启动时恢复主窗口状态；读取失败或以前未保存过时保持默认
+pub fn restore<R: Runtime>(window: &WebviewWindow<R>, db: &Arc<Mutex<toasty::Db>>) {
+    let db = db.clone();
+    let saved = tauri::async_runtime::block_on(async move {
+        let mut db_lock = db.lock().await;
+        crate::db::get_setting(&mut db_lock, SETTING_WINDOW_STATE).await
+    });
+    let Some(state) = saved
+        .ok()
+        .flatten()
+        .and_then(|value| serde_json::from_str::<WindowState>(&value).ok())
+    else {
+        return;
+    };
+
+    let monitors = window.available_monitors().unwrap_or_default();
+    let (mut width, mut height) = (state.width.max(1), state.height.max(1));
+    let visible = monitors.iter().find(|monitor| {
+        let pos = monitor.position();
+        let size = monitor.size();
+        let (right, bottom) = (pos.x + size.width as i32, pos.y + size.height as i32);
+        let overlap_x = (state.x + width as i32).min(right) - state.x.max(pos.x);
+        // 无系统标题栏，窗口顶部（自绘标题栏）必须落在显示器内才能拖动
+        overlap_x >= MIN_VISIBLE_PX && state.y >= pos.y && state.y + MIN_VISIBLE_PX <= bottom
+    });
+    // 不超过所在显示器（或主显示器）的尺寸
+    if let Some(monitor) = visible.or(monitors.first()) {
+        width = width.min(monitor.size().width);
+        height = height.min(monitor.size().height);
+    }
+
+    let _ = window.set_size(PhysicalSize::new(width, height));
+    // 显示器已断开或分辨率变化导致窗口不可见时，保留系统默认位置
+    if visible.is_some() {
+        let _ = window.set_position(PhysicalPosition::new(state.x, state.y));
+    }
+    if state.maximized {
+        let _ = window.maximize();
+    }
+}
+
+/// 关闭主窗口时是否隐藏到托盘；未启用托盘时总是退出
+pub fn close_to_tray<R: Runtime>(window: &Window<R>) -> bool {
+    if !cfg!(feature = "tray") {
+        return false;
+    }
+    window
+        .try_state::<crate::commands::state::AppState>()
+        .is_some_and(|app_state| app_state.close_to_tray.load(Ordering::Relaxed))
+}
+
+/// 窗口关闭时在后台保存主窗口状态，并取消尚未写入的延迟保存
+///
+/// 退出应用前应等待返回的任务完成，避免写入被中断。
+pub fn save<R: Runtime>(window: &Window<R>) -> Option<tauri::async_runtime::JoinHandle<()>> {
+    let app_state = window.try_state::<crate::commands::state::AppState>()?;
+    let db = app_state.db.clone();
+    let snapshot = Snapshot::capture(window);
+    // 不在事件循环线程中等待数据库锁，其它操作持锁时会卡住界面
+    SAVE_GENERATION.fetch_add(1, Ordering::Relaxed);
+    Some(tauri::async_runtime::spawn(async move {
+        if let Err(e) = persist(db, snapshot).await {
+            tracing::warn!(error = %e, "保存窗口状态失败");
+        }
+    }))
+}
+
+/// 保存主窗口状态后退出应用
+pub fn save_and_exit<R: Runtime>(app: &AppHandle<R>) {
+    let saving = app.get_window(MAIN_WINDOW).and_then(|window| save(&window));
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Some(saving) = saving {
+            let _ = saving.await;
+        }
+        app.exit(0);
+    });
+}
+
+/// 窗口大小或位置变化时保存；拖动过程中事件频繁，停止变化一段时间后才写入
+pub fn schedule_save<R: Runtime>(window: &Window<R>) {
+    let generation = SAVE_GENERATION.fetch_add(1, Ordering::Relaxed) + 1;
+    let window = window.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(SAVE_DEBOUNCE).await;
+        if SAVE_GENERATION.load(Ordering::Relaxed) != generation {
+            return;
+        }
+        let Some(app_state) = window.try_state::<crate::commands::state::AppState>() else {
+            return;
+        };
+        let db = app_state.db.clone();
+        if let Err(e) = persist(db, Snapshot::capture(&window)).await {
+            tracing::warn!(error = %e, "保存窗口状态失败");
+        }
+    });
+}
+
+/// 保存时读取的窗口状态
+struct Snapshot {
+    maximized: bool,
+    minimized: bool,
+    geometry: Option<(PhysicalSize<u32>, PhysicalPosition<i32>)>,
+}
+
+impl Snapshot {
+    fn capture<R: Runtime>(window: &Window<R>) -> Self {
+        Self {
+            maximized: window.is_maximized().unwrap_or(false),
+            minimized: window.is_minimized().unwrap_or(false),
+            geometry: window.inner_size().ok().zip(window.outer_position().ok()),
+        }
+    }
+}
+
+/// 最大化或最小化时只更新最大化标记，保留之前的常规尺寸与位置
+async fn persist(db: Arc<Mutex<toasty::Db>>, snapshot: Snapshot) -> Result<(), String> {
+    let Snapshot {
+        maximized,
+        minimized,
+        geometry,
+    } = snapshot;
+    let mut db_lock = db.lock().await;
+    let previous = crate::db::get_setting(&mut db_lock, SETTING_WINDOW_STATE)
+        .await?
+        .and_then(|value| serde_json::from_str::<WindowState>(&value).ok());
+    let state = match (previous, geometry) {
+        (Some(previous), _) if maximized || minimized => WindowState {
+            maximized,
+            ..previous
+        },
+        (_, Some((size, position))) if !minimized => WindowState {
+            width: size.width,
+            height: size.height,
+            x: position.x,
+            y: position.y,
+            maximized,
+        },
+        _ => return Ok(()),
+    };
+    let value = serde_json::to_string(&state).map_err(|e| format!("序列化失败: {}", e))?;
+    crate::db::set_setting(&mut db_lock, SETTING_WINDOW_STATE, &value).await
+}
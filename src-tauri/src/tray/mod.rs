@@ -1,42 +1,39 @@
+use crate::commands::state::AppState;
+use crate::models::{GameSortBy, SortDirection};
 use tauri::Manager;
-use tauri::menu::MenuBuilder;
+use tauri::menu::{Menu, MenuBuilder, MenuItemBuilder, SubmenuBuilder};
 use tauri::tray::TrayIconBuilder;
 
+/// 托盘图标 ID
+const TRAY_ID: &str = "main-tray";
+/// 最近游玩子菜单中的游戏数
+const RECENT_GAMES_LIMIT: usize = 5;
+/// 最近游玩菜单项 ID 前缀，后接游戏 ID
+const LAUNCH_ITEM_PREFIX: &str = "launch:";
+
 pub fn setup_tray(app: &tauri::AppHandle) -> Result<(), String> {
-    let _ = app.remove_tray_by_id("main-tray");
+    let _ = app.remove_tray_by_id(TRAY_ID);
 
-    let menu = MenuBuilder::new(app)
-        .text("toggle_window", "显示/隐藏")
-        .separator()
-        .text("quit", "退出")
-        .build()
-        .map_err(|e| format!("创建托盘菜单失败: {}", e))?;
+    let recent = tauri::async_runtime::block_on(recent_games(app));
+    let menu = build_menu(app, &recent)?;
 
     let icon = app
         .default_window_icon()
         .cloned()
         .ok_or_else(|| "缺少默认窗口图标".to_string())?;
 
-    TrayIconBuilder::with_id("main-tray")
+    TrayIconBuilder::with_id(TRAY_ID)
         .icon(icon)
         .tooltip("GameManager")
         .menu(&menu)
         .on_menu_event(|app, event| match event.id().as_ref() {
-            "toggle_window" => {
-                if let Some(window) = app.get_webview_window("main") {
-                    let visible = window.is_visible().unwrap_or(true);
-                    if visible {
-                        let _ = window.hide();
-                    } else {
-                        let _ = window.show();
-                        let _ = window.set_focus();
-                    }
+            "toggle_window" => crate::services::window_state::toggle_main_window(app),
+            "quit" => crate::services::window_state::save_and_exit(app),
+            id => {
+                if let Some(game_id) = id.strip_prefix(LAUNCH_ITEM_PREFIX) {
+                    launch_from_tray(app, game_id.to_string());
                 }
             }
-            "quit" => {
-                app.exit(0);
-            }
-            _ => {}
         })
         .build(app)
         .map_err(|e| format!("创建托盘图标失败: {}", e))?;
@@ -45,5 +42,86 @@ pub fn setup_tray(app: &tauri::AppHandle) -> Result<(), String> {
 }
 
 pub fn remove_tray(app: &tauri::AppHandle) {
-    let _ = app.remove_tray_by_id("main-tray");
+    let _ = app.remove_tray_by_id(TRAY_ID);
+}
+
+/// 重新生成托盘菜单（最近游玩列表变化后调用）
+pub async fn refresh_menu(app: &tauri::AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    let recent = recent_games(app).await;
+    match build_menu(app, &recent) {
+        Ok(menu) => {
+            if let Err(e) = tray.set_menu(Some(menu)) {
+                tracing::warn!(error = %e, "更新托盘菜单失败");
+            }
+        }
+        Err(e) => tracing::warn!(error = %e, "更新托盘菜单失败"),
+    }
+}
+
+fn build_menu(
+    app: &tauri::AppHandle,
+    recent: &[(String, String)],
+) -> Result<Menu<tauri::Wry>, String> {
+    let mut submenu = SubmenuBuilder::new(app, "最近游玩");
+    if recent.is_empty() {
+        submenu = submenu.item(
+            &MenuItemBuilder::new("暂无")
+                .enabled(false)
+                .build(app)
+                .map_err(|e| format!("创建托盘菜单失败: {}", e))?,
+        );
+    }
+    for (id, title) in recent {
+        submenu = submenu.text(format!("{}{}", LAUNCH_ITEM_PREFIX, id), title);
+    }
+    let submenu = submenu
+        .build()
+        .map_err(|e| format!("创建托盘菜单失败: {}", e))?;
+
+    MenuBuilder::new(app)
+        .text("toggle_window", "显示/隐藏")
+        .item(&submenu)
+        .separator()
+        .text("quit", "退出")
+        .build()
+        .map_err(|e| format!("创建托盘菜单失败: {}", e))
+}
+
+/// 最近游玩的游戏（ID, 标题），按最后游玩时间倒序
+async fn recent_games(app: &tauri::AppHandle) -> Vec<(String, String)> {
+    let Some(state) = app.try_state::<AppState>() else {
+        return Vec::new();
+    };
+    let games = state
+        .game_service
+        .lock()
+        .await
+        .get_games_sorted(GameSortBy::LastPlayed, SortDirection::Desc, None)
+        .await;
+    match games {
+        Ok(games) => games
+            .into_iter()
+            .filter(|game| game.last_played_at.is_some())
+            .take(RECENT_GAMES_LIMIT)
+            .map(|game| (game.id, game.title))
+            .collect(),
+        Err(e) => {
+            tracing::warn!(error = %e, "读取最近游玩的游戏失败");
+            Vec::new()
+        }
+    }
+}
+
+/// 菜单事件回调是同步的，启动流程放到异步运行时中执行
+fn launch_from_tray(app: &tauri::AppHandle, game_id: String) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        if let Err(e) = crate::commands::launch_game(game_id.clone(), state, app.clone()).await {
+            tracing::warn!(game_id = %game_id, error = %e, "从托盘启动游戏失败");
+        }
+    });
 }
@@ -2,8 +2,10 @@
 use std::path::{Path, PathBuf};
 
 /// 规范化路径，失败时返回原路径。
+///
+/// Windows 上不返回 `\\?\` 形式的 verbatim 路径（除非路径只能这样表示）。
 pub fn canonicalize(path: &Path) -> PathBuf {
-    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+    dunce::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
 }
 
 /// 确保目录存在，不存在则递归创建。
@@ -16,6 +18,33 @@ pub fn is_within(path: &Path, root: &Path) -> bool {
     canonicalize(path).starts_with(&canonicalize(root))
 }
 
+/// 判断 `path` 是否为容器 `profiles/` 下的目录（均先规范化，`profiles/` 本身不算）。
+///
+/// 删除 profile 目录前用于防止误删容器外或整个 `profiles/` 目录。
+pub fn is_profile_dir(container_root: &Path, path: &Path) -> bool {
+    let profiles_root = canonicalize(&container_root.join("profiles"));
+    let path = canonicalize(path);
+    path != profiles_root && path.starts_with(&profiles_root)
+}
+
+/// 递归统计目录中文件的总大小（不跟随符号链接，跳过无权限读取的条目）。
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let meta = entry.path().symlink_metadata().ok()?;
+            Some(if meta.is_dir() {
+                dir_size(&entry.path())
+            } else {
+                meta.len()
+            })
+        })
+        .sum()
+}
+
 /// 判断文件是否为可直接启动的 Linux ELF 或带 shebang 的可执行脚本。
 pub fn is_linux_native_executable(path: &Path) -> bool {
     if path
@@ -51,3 +80,42 @@ pub fn is_linux_native_executable(path: &Path) -> bool {
     #[cfg(not(unix))]
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profile_dir_must_be_inside_profiles_root() {
+        let tmp = tempfile::tempdir().unwrap();
+        let profiles = tmp.path().join("profiles");
+        std::fs::create_dir_all(profiles.join("game")).unwrap();
+        std::fs::create_dir(tmp.path().join("other")).unwrap();
+
+        assert!(is_profile_dir(tmp.path(), &profiles.join("game")));
+        assert!(!is_profile_dir(tmp.path(), &profiles));
+        assert!(!is_profile_dir(tmp.path(), &tmp.path().join("other")));
+        assert!(!is_profile_dir(
+            tmp.path(),
+            &profiles.join("..").join("other")
+        ));
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(tmp.path().join("other"), profiles.join("link")).unwrap();
+            assert!(!is_profile_dir(tmp.path(), &profiles.join("link")));
+        }
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn canonicalize_has_no_verbatim_prefix() {
+        let tmp = tempfile::tempdir().unwrap();
+        let child = tmp.path().join("child");
+        std::fs::create_dir(&child).unwrap();
+
+        let canonical = canonicalize(&child);
+        assert!(!canonical.to_string_lossy().starts_with(r"\\?\"));
+        assert!(is_within(&child, tmp.path()));
+    }
+}
@@ -159,9 +159,13 @@ export default function App() {
 
   // 游戏操作回调
   const onLaunchGame = useCallback(async (id: string) => {
-    const success = await handleLaunchGame(id);
-    if (success) {
+    const result = await handleLaunchGame(id);
+    if (result) {
       toast.success(t("toast.launchSuccess"));
+      // 终端模式下跟踪的是终端进程，运行状态与游玩时长不可靠
+      if (!result.tracked) {
+        toast.info(t("toast.launchUntracked"));
+      }
     }
   }, [handleLaunchGame, t]);
 
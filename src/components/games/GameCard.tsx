@@ -1,6 +1,4 @@
-import { useMemo } from "react";
 import { Icon } from "@iconify/react";
-import { convertFileSrc } from "@tauri-apps/api/core";
 import { Badge } from "@/components/ui/badge";
 import { Button } from "@/components/ui/button";
 import { useEngineRegistry } from "@/hooks/useEngineRegistry";
@@ -19,14 +17,8 @@ interface GameCardProps {
 export default function GameCard({ game, isLaunching = false, onLaunch, onEdit, onDelete }: GameCardProps) {
   const { t, locale } = useI18n();
   const { getName, getIcon } = useEngineRegistry();
-  const coverSrc = useMemo(() => {
-    if (!game.coverPath) return "";
-    try {
-      return convertFileSrc(game.coverPath);
-    } catch {
-      return `asset://localhost/${game.coverPath}`;
-    }
-  }, [game.coverPath]);
+  // 列表视图优先使用缩略图（gmcover:// 协议 URL）
+  const coverSrc = game.coverThumbPath ?? game.coverPath ?? "";
 
   return (
     <div className="group relative flex items-center gap-3 rounded-xl border bg-card px-4 py-2.5 text-card-foreground transition-all hover:bg-muted/40 hover:shadow-md">
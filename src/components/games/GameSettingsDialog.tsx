@@ -25,7 +25,7 @@ import {
 } from "@/lib/api";
 import { useI18n } from "@/i18n";
 import { useEngineRegistry } from "@/hooks/useEngineRegistry";
-import type { GameConfig, GameDto } from "@/types";
+import type { GameConfig, GameDto, SandboxHomeMode } from "@/types";
 
 interface GameSettingsDialogProps {
   open: boolean;
@@ -72,7 +72,7 @@ export default function GameSettingsDialog({
   const [runner, setRunner] = useState<GameConfig["runner"]>("auto");
   const [entryPath, setEntryPath] = useState("");
   const [argsText, setArgsText] = useState("");
-  const [sandboxHome, setSandboxHome] = useState(true);
+  const [sandboxHome, setSandboxHome] = useState<SandboxHomeMode>(true);
   const [coverFile, setCoverFile] = useState("");
 
   const [settingsLoading, setSettingsLoading] = useState(false);
@@ -492,10 +492,19 @@ export default function GameSettingsDialog({
             </FormRow>
 
             <FormRow label={t("gameSettings.sandboxHome")}>
-              <Switch
-                checked={sandboxHome}
-                onCheckedChange={(v) => setSandboxHome(Boolean(v))}
-              />
+              <Select
+                value={sandboxHome === "xdg-only" ? "xdg-only" : sandboxHome ? "full" : "off"}
+                onValueChange={(v) =>
+                  setSandboxHome(v === "xdg-only" ? "xdg-only" : v === "full")
+                }
+              >
+                <SelectTrigger size="sm"><SelectValue /></SelectTrigger>
+                <SelectContent>
+                  <SelectItem value="full">{t("gameSettings.sandboxFull")}</SelectItem>
+                  <SelectItem value="xdg-only">{t("gameSettings.sandboxXdgOnly")}</SelectItem>
+                  <SelectItem value="off">{t("gameSettings.sandboxOff")}</SelectItem>
+                </SelectContent>
+              </Select>
             </FormRow>
           </div>
         </div>
@@ -7,6 +7,7 @@
 import { useState, useRef, useEffect, useCallback } from 'react'
 import { toast } from 'sonner'
 import { useI18n } from '@/i18n'
+import { getGameByPath } from '@/lib/api'
 
 export interface DragDropState {
   /** 是否正在拖拽 */
@@ -49,7 +50,13 @@ export function useDragDrop(onDrop?: (path: string) => void): DragDropState {
     setDroppedPath('')
   }, [])
 
-  const handleFileDrop = useCallback((path: string) => {
+  const handleFileDrop = useCallback(async (path: string) => {
+    // 已在库中的游戏直接提示，不再进入导入流程
+    const existing = await getGameByPath(path).catch(() => null)
+    if (existing) {
+      toast.info(t('drag.alreadyInLibrary', { title: existing.title }))
+      return
+    }
     setDroppedPath(path)
     if (onDropRef.current) {
       onDropRef.current(path)
@@ -87,7 +94,7 @@ export function useDragDrop(onDrop?: (path: string) => void): DragDropState {
             setIsDragging(false)
             const path = paths[0]
             if (path) {
-              handleFileDrop(path)
+              void handleFileDrop(path)
             } else {
               toast.error(t('drag.localExecutableOnly'))
             }
@@ -131,7 +138,7 @@ export function useDragDrop(onDrop?: (path: string) => void): DragDropState {
         toast.error(t('drag.localExecutableOnly'))
         return
       }
-      handleFileDrop(path)
+      void handleFileDrop(path)
     }
 
     function setupDomDragDrop() {
@@ -73,8 +73,9 @@ export function useGameLibraryActions(options: Options) {
         path: payload.path,
         runtimeVersion: payload.runtimeVersion,
       });
-      await saveGameSettings(payload.id, payload.settings);
+      const { warnings } = await saveGameSettings(payload.id, payload.settings);
       toast.success(t("toast.settingsSaved"));
+      warnings.forEach((warning) => toast.warning(warning.message));
       options.closeGameSettings();
       await options.refresh(true);
     } catch (e) {
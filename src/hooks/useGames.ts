@@ -6,7 +6,7 @@
 
 import { useState, useCallback } from "react";
 import { getGames, addGame, updateGame, deleteGame, launchGame } from "@/lib/api";
-import type { GameDto, AddGameInput, UpdateGameInput } from "@/types";
+import type { GameDto, AddGameInput, UpdateGameInput, LaunchResult } from "@/types";
 
 /** 游戏列表缓存过期时间 (10秒) */
 const GAMES_CACHE_TTL = 10_000;
@@ -162,20 +162,20 @@ export function useGames() {
     }
   }, []);
 
-  const handleLaunchGame = useCallback(async (id: string): Promise<boolean> => {
-    if (launchingId) return false; // prevent double-click
+  const handleLaunchGame = useCallback(async (id: string): Promise<LaunchResult | null> => {
+    if (launchingId) return null; // prevent double-click
     setLaunchingId(id);
     setError(null);
     try {
-      await launchGame(id);
+      const result = await launchGame(id);
       invalidateGamesCache();
       await fetchGames(true);
-      return true;
+      return result;
     } catch (e) {
       const msg = e instanceof Error ? e.message : "启动游戏失败";
       setError(msg);
       console.error("启动游戏失败:", e);
-      return false;
+      return null;
     } finally {
       setLaunchingId(null);
     }
@@ -17,9 +17,17 @@ export function useTauriEvents(updateTask: (label: string, progress: number) =>
           downloaded: number;
           total?: number | null;
           percent?: number | null;
+          attempt?: number;
+          maxAttempts?: number;
+          cancelled?: boolean;
         }>("nwjs_download_progress", (event) => {
           const p = event.payload?.percent ?? 0;
-          updateTask(t("task.downloadNwjs", { version: event.payload.version, flavor: event.payload.flavor }), p);
+          const { version, flavor, attempt = 1, maxAttempts = 1 } = event.payload;
+          const label =
+            attempt > 1
+              ? t("task.downloadNwjsRetry", { version, flavor, attempt, max: maxAttempts })
+              : t("task.downloadNwjs", { version, flavor });
+          updateTask(label, p);
         });
         unlisteners.push(u1);
 
@@ -28,11 +36,12 @@ export function useTauriEvents(updateTask: (label: string, progress: number) =>
           version: string;
           flavor: "normal" | "sdk";
           target: string;
-          stage: "downloaded" | "installed";
+          stage: "downloaded" | "extracting" | "installed";
           label: string;
+          percent?: number;
         }>("nwjs_install_stage", (event) => {
           const label = event.payload?.label ?? t("task.processing");
-          updateTask(label, 100);
+          updateTask(label, event.payload?.percent ?? 100);
         });
         unlisteners.push(u2);
 
@@ -81,6 +81,9 @@ export const enUSMessages: MessageMap = {
   "gameSettings.argsPlaceholder": "--debug --foo=bar",
   "gameSettings.sandboxHome": "Sandbox Home",
   "gameSettings.sandboxHomeDesc": "Isolate user data for this game",
+  "gameSettings.sandboxFull": "Isolate HOME and XDG dirs",
+  "gameSettings.sandboxXdgOnly": "Isolate XDG dirs only",
+  "gameSettings.sandboxOff": "Off (writes to your real home)",
   "settings.title": "Settings",
   "settings.description": "Preferences and storage",
   "settings.group.appearance": "Appearance",
@@ -173,8 +176,10 @@ export const enUSMessages: MessageMap = {
   "drag.executableOnly": "Only executable files are supported",
   "drag.dropSelected": "Drop selected. Choose an engine type",
   "drag.localExecutableOnly": "Only local executable files can be dropped",
+  "drag.alreadyInLibrary": "Already in library: {{title}}",
   "statusBar.status": "Status:",
   "toast.launchSuccess": "Game launched successfully",
+  "toast.launchUntracked": "Launched in a terminal; the game process can't be tracked and play time won't be recorded",
   "toast.deleteSuccess": "Game deleted successfully",
   "toast.importSuccess": "Import succeeded",
   "toast.importFailed": "Import failed",
@@ -186,6 +191,7 @@ export const enUSMessages: MessageMap = {
   "toast.coverUpdated": "Cover updated",
   "toast.coverUpdateFailed": "Cover update failed",
   "task.downloadNwjs": "Download NW.js {{version}} ({{flavor}})",
+  "task.downloadNwjsRetry": "Download NW.js {{version}} ({{flavor}}) retrying ({{attempt}}/{{max}})",
   "task.processing": "Processing...",
   "task.scanning": "Scanning...",
   "task.scanComplete": "Scan complete",
@@ -81,6 +81,9 @@ export const zhCNMessages: MessageMap = {
   "gameSettings.argsPlaceholder": "--debug --foo=bar",
   "gameSettings.sandboxHome": "沙盒主目录",
   "gameSettings.sandboxHomeDesc": "隔离游戏的用户数据",
+  "gameSettings.sandboxFull": "隔离主目录与 XDG 目录",
+  "gameSettings.sandboxXdgOnly": "仅隔离 XDG 目录",
+  "gameSettings.sandboxOff": "不隔离（写入真实用户目录）",
   "settings.title": "设置",
   "settings.description": "偏好与存储",
   "settings.group.appearance": "外观",
@@ -173,8 +176,10 @@ export const zhCNMessages: MessageMap = {
   "drag.executableOnly": "仅支持可执行文件",
   "drag.dropSelected": "已选择拖拽文件，请选择引擎类型",
   "drag.localExecutableOnly": "仅支持拖拽本地可执行文件",
+  "drag.alreadyInLibrary": "已在库中：{{title}}",
   "statusBar.status": "状态：",
   "toast.launchSuccess": "游戏启动成功",
+  "toast.launchUntracked": "已在终端中启动，无法跟踪游戏进程，不会记录游玩时长",
   "toast.deleteSuccess": "游戏删除成功",
   "toast.importSuccess": "导入成功",
   "toast.importFailed": "导入失败",
@@ -186,6 +191,7 @@ export const zhCNMessages: MessageMap = {
   "toast.coverUpdated": "图标已更新",
   "toast.coverUpdateFailed": "图标更新失败",
   "task.downloadNwjs": "下载 NW.js {{version}}（{{flavor}}）",
+  "task.downloadNwjsRetry": "下载 NW.js {{version}}（{{flavor}}）重试中 ({{attempt}}/{{max}})",
   "task.processing": "处理中…",
   "task.scanning": "扫描中…",
   "task.scanComplete": "扫描完成",
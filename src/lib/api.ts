@@ -4,38 +4,90 @@
 
 import { invoke as tauriInvoke } from '@tauri-apps/api/core'
 import type {
+  AppError,
+  AppErrorCode,
   GameDto,
+  GameSortBy,
+  GameStatus,
+  SortDirection,
   AddGameInput,
   UpdateGameInput,
   LaunchResult,
+  SaveBackupInfo,
+  GameDiskUsage,
   ScanGamesInput,
   ScanGamesResult,
+  ImportGameDirsResult,
   GameConfig,
+  SaveGameSettingsResult,
+  RunningGameInfo,
+  ImportLibraryResult,
+  DeduplicateResult,
   EngineDto,
   EngineUpdateInfo,
   EngineUpdateResult,
   AppSettings,
   SetContainerRootInput,
   NwjsStableInfo,
+  NwjsVersionInfo,
+  NwjsInstalledRuntimes,
+  NwjsRuntimeCheck,
+  DownloadTaskInfo,
+  ExternalToolPaths,
+  LaunchLimit,
   NwjsInstallResult,
   MkxpzImportResult,
   CleanupResult,
+  DiagnosticsInfo,
   Capabilities,
   IntegrationSettingsInput,
   IntegrationStatus,
+  ProtonVersion,
   EngineDetail,
   EngineProfileDetail,
+  EngineDetection,
+  RedetectEnginesResult,
 } from '@/types'
 
 /**
  * 调用Tauri命令的通用封装
  */
+/**
+ * 命令调用失败时抛出的错误，保留后端返回的 code 与字段错误
+ */
+export class CommandError extends Error {
+  readonly code: AppErrorCode
+  readonly fields: NonNullable<AppError['fields']>
+  /** 目录未识别出引擎时最接近的引擎（最接近的在前） */
+  readonly nearMisses: NonNullable<AppError['nearMisses']>
+  /** 目录未识别出引擎时该目录的路径 */
+  readonly path?: string
+
+  constructor(error: AppError) {
+    super(error.message)
+    this.name = 'CommandError'
+    this.code = error.code
+    this.fields = error.fields ?? []
+    this.nearMisses = error.nearMisses ?? []
+    this.path = error.path
+  }
+}
+
+function isAppError(error: unknown): error is AppError {
+  return (
+    typeof error === 'object' &&
+    error !== null &&
+    typeof (error as AppError).code === 'string' &&
+    typeof (error as AppError).message === 'string'
+  )
+}
+
 async function invoke<T>(command: string, args?: Record<string, unknown>): Promise<T> {
   try {
     return await tauriInvoke<T>(command, args)
   } catch (error) {
     console.error(`Tauri命令调用失败 [${command}]:`, error)
-    throw error
+    throw isAppError(error) ? new CommandError(error) : error
   }
 }
 
@@ -55,6 +107,20 @@ export async function getGame(id: string): Promise<GameDto | null> {
   return invoke<GameDto | null>('get_game', { id })
 }
 
+/**
+ * 按路径查找已在库中的游戏（路径为文件时按所在目录查找）
+ */
+export async function getGameByPath(path: string): Promise<GameDto | null> {
+  return invoke<GameDto | null>('get_game_by_path', { path })
+}
+
+/**
+ * 按标题或路径搜索游戏
+ */
+export async function searchGames(query: string): Promise<GameDto[]> {
+  return invoke<GameDto[]>('search_games', { query })
+}
+
 /**
  * 添加游戏
  */
@@ -69,6 +135,20 @@ export async function updateGame(id: string, input: UpdateGameInput): Promise<Ga
   return invoke<GameDto>('update_game', { id, input })
 }
 
+/**
+ * 将游戏指向新的目录（需识别为相同引擎），保留存档与统计
+ */
+export async function relocateGame(id: string, newPath: string): Promise<GameDto> {
+  return invoke<GameDto>('relocate_game', { id, newPath })
+}
+
+/**
+ * 在指定目录下查找已移动游戏的候选目录
+ */
+export async function findRelocated(id: string, searchRoot: string): Promise<string[]> {
+  return invoke<string[]>('find_relocated', { id, searchRoot })
+}
+
 /**
  * 删除游戏
  */
@@ -76,6 +156,13 @@ export async function deleteGame(id: string): Promise<void> {
   return invoke<void>('delete_game', { id })
 }
 
+/**
+ * 批量删除游戏，返回删除数量（removeProfiles 为 true 时同时删除 profile 目录）
+ */
+export async function deleteGames(ids: string[], removeProfiles = false): Promise<number> {
+  return invoke<number>('delete_games', { ids, removeProfiles })
+}
+
 /** 移除游戏库中的全部条目，不删除实际游戏文件。 */
 export async function removeAllGames(): Promise<number> {
   return invoke<number>('remove_all_games')
@@ -88,6 +175,115 @@ export async function launchGame(id: string): Promise<LaunchResult> {
   return invoke<LaunchResult>('launch_game', { id })
 }
 
+/**
+ * 终止正在运行的游戏
+ */
+export async function stopGame(id: string): Promise<void> {
+  return invoke<void>('stop_game', { id })
+}
+
+/**
+ * 获取当前运行中的游戏实例
+ */
+export async function getRunningGames(): Promise<RunningGameInfo[]> {
+  return invoke<RunningGameInfo[]>('get_running_games')
+}
+
+/**
+ * 备份游戏存档为带时间戳的 zip，返回压缩包路径
+ */
+export async function backupSave(id: string): Promise<string> {
+  return invoke<string>('backup_save', { id })
+}
+
+/**
+ * 列出游戏的存档备份（最新的在前）
+ */
+export async function listSaveBackups(id: string): Promise<SaveBackupInfo[]> {
+  return invoke<SaveBackupInfo[]>('list_save_backups', { id })
+}
+
+/**
+ * 从存档备份恢复（会先备份当前存档），返回恢复的文件数
+ */
+export async function restoreSave(id: string, backupPath: string): Promise<number> {
+  return invoke<number>('restore_save', { id, backupPath })
+}
+
+/**
+ * 导出游戏库到 JSON 清单，返回导出的游戏数
+ */
+export async function exportLibrary(destPath: string): Promise<number> {
+  return invoke<number>('export_library', { destPath })
+}
+
+/**
+ * 从 JSON 清单导入游戏库（merge 为 false 时先清空现有游戏库）
+ */
+export async function importLibrary(srcPath: string, merge: boolean): Promise<ImportLibraryResult> {
+  return invoke<ImportLibraryResult>('import_library', { srcPath, merge })
+}
+
+/**
+ * 合并路径相同的重复游戏记录
+ */
+export async function deduplicateGames(): Promise<DeduplicateResult> {
+  return invoke<DeduplicateResult>('deduplicate_games')
+}
+
+/**
+ * 按指定字段排序获取游戏列表，可选按完成状态筛选
+ */
+export async function listGamesSorted(
+  sortBy: GameSortBy,
+  direction: SortDirection,
+  status?: GameStatus
+): Promise<GameDto[]> {
+  return invoke<GameDto[]>('list_games_sorted', { sortBy, direction, status: status ?? null })
+}
+
+/**
+ * 设置游戏完成状态
+ */
+export async function setGameStatus(id: string, status: GameStatus): Promise<void> {
+  return invoke<void>('set_game_status', { id, status })
+}
+
+/**
+ * 设置游戏评分（0-5），传 null 清除评分
+ */
+export async function setGameRating(id: string, rating: number | null): Promise<void> {
+  return invoke<void>('set_game_rating', { id, rating })
+}
+
+/**
+ * 固定游戏使用的 NW.js 版本（需已安装），传 null 取消固定
+ */
+export async function setGameRuntime(id: string, version: string | null): Promise<void> {
+  return invoke<void>('set_game_runtime', { id, version })
+}
+
+/**
+ * 设置或取消收藏游戏
+ */
+export async function setGameFavorite(id: string, favorite: boolean): Promise<void> {
+  return invoke<void>('set_game_favorite', { id, favorite })
+}
+
+/**
+ * 设置游戏标签（返回去重后的标签）
+ */
+export async function setGameTags(id: string, tags: string[]): Promise<string[]> {
+  return invoke<string[]>('set_game_tags', { id, tags })
+}
+
+/**
+ * 按标签筛选游戏
+ */
+export async function listGamesByTag(tag: string): Promise<GameDto[]> {
+  return invoke<GameDto[]>('list_games_by_tag', { tag })
+}
+
 /**
  * 导入游戏（可执行文件路径）
  */
@@ -95,6 +291,21 @@ export async function importGameDir(executablePath: string, engineType: string):
   return invoke<GameDto>('import_game_dir', { input: { executablePath, engineType } })
 }
 
+/**
+ * 批量导入游戏目录（自动识别引擎），每处理一个目录发送 import_dirs_progress 事件
+ */
+export async function importGameDirs(paths: string[]): Promise<ImportGameDirsResult> {
+  return invoke<ImportGameDirsResult>('import_game_dirs', { paths })
+}
+
+/**
+ * 从压缩包（.zip/.7z/.tar.gz）导入游戏：解压到 destDir 下的同名目录后导入，
+ * 解压进度通过 archive_import_progress 事件发送
+ */
+export async function importGameArchive(archivePath: string, destDir: string): Promise<GameDto> {
+  return invoke<GameDto>('import_game_archive', { archivePath, destDir })
+}
+
 /**
  * 扫描游戏
  */
@@ -110,10 +321,27 @@ export async function getGameSettings(id: string): Promise<GameConfig> {
 }
 
 /**
- * 保存游戏设置
+ * 保存游戏设置（校验失败时抛出 code 为 validation 的 CommandError）
+ */
+export async function saveGameSettings(
+  id: string,
+  input: GameConfig
+): Promise<SaveGameSettingsResult> {
+  return invoke<SaveGameSettingsResult>('save_game_settings', { id, input })
+}
+
+/**
+ * 列出游戏目录中可作为启动入口的文件（相对游戏目录）
  */
-export async function saveGameSettings(id: string, input: GameConfig): Promise<void> {
-  return invoke<void>('save_game_settings', { id, input })
+export async function listExecutables(id: string): Promise<string[]> {
+  return invoke<string[]>('list_executables', { id })
+}
+
+/**
+ * 设置游戏启动入口；传空字符串恢复自动识别
+ */
+export async function setGameEntry(id: string, entryPath: string): Promise<void> {
+  return invoke<void>('set_game_entry', { id, entryPath })
 }
 
 /**
@@ -123,6 +351,20 @@ export async function refreshGameCover(id: string): Promise<GameDto> {
   return invoke<GameDto>('refresh_game_cover', { id })
 }
 
+/**
+ * 从远程 URL（http/https）下载图片并设为封面
+ */
+export async function setCoverFromUrl(id: string, url: string): Promise<GameDto> {
+  return invoke<GameDto>('set_cover_from_url', { id, url })
+}
+
+/**
+ * 截取正在运行的游戏窗口作为封面（目前仅支持 Linux，需要 grim 或 xdotool + ImageMagick）
+ */
+export async function captureWindowCover(id: string, pid: number): Promise<GameDto> {
+  return invoke<GameDto>('capture_window_cover', { id, pid })
+}
+
 /**
  * 获取游戏 profile 目录
  */
@@ -137,6 +379,27 @@ export async function openPath(path: string): Promise<void> {
   return invoke<void>('open_path', { path })
 }
 
+/**
+ * 统计游戏目录与 profile 目录的磁盘占用
+ */
+export async function getGameDiskUsage(id: string): Promise<GameDiskUsage> {
+  return invoke<GameDiskUsage>('get_game_disk_usage', { id })
+}
+
+/**
+ * 在文件管理器中打开游戏目录
+ */
+export async function openGameFolder(id: string): Promise<void> {
+  return invoke<void>('open_game_folder', { id })
+}
+
+/**
+ * 在文件管理器中打开游戏存档目录（不存在时自动创建）
+ */
+export async function openGameSaveFolder(id: string): Promise<void> {
+  return invoke<void>('open_game_save_folder', { id })
+}
+
 // ============ 引擎相关API ============
 
 /**
@@ -182,6 +445,13 @@ export async function getEngineUpdateInfo(id: string): Promise<EngineUpdateInfo>
   return invoke<EngineUpdateInfo>('get_engine_update_info', { id })
 }
 
+/**
+ * 检查全部引擎的更新（NW.js 最新版本只请求一次）
+ */
+export async function checkAllEngineUpdates(): Promise<EngineUpdateInfo[]> {
+  return invoke<EngineUpdateInfo[]>('check_all_engine_updates')
+}
+
 /**
  * 更新引擎
  */
@@ -219,6 +489,13 @@ export async function getPlatform(): Promise<string> {
   return invoke<string>('get_platform')
 }
 
+/**
+ * 收集运行环境诊断信息（用于问题反馈）
+ */
+export async function getDiagnostics(): Promise<DiagnosticsInfo> {
+  return invoke<DiagnosticsInfo>('get_diagnostics')
+}
+
 /**
  * 获取能力列表
  */
@@ -233,6 +510,13 @@ export async function getIntegrationStatus(key: string): Promise<IntegrationStat
   return invoke<IntegrationStatus>('get_integration_status', { key })
 }
 
+/**
+ * 列出已安装的 Steam Proton 版本（新版本在前）
+ */
+export async function listProtonVersions(): Promise<ProtonVersion[]> {
+  return invoke<ProtonVersion[]>('list_proton_versions')
+}
+
 /**
  * 更新集成设置
  */
@@ -240,6 +524,16 @@ export async function setIntegrationSettings(input: IntegrationSettingsInput): P
   return invoke<void>('set_integration_settings', { input })
 }
 
+/**
+ * 新建 Bottles bottle，返回刷新后的 Bottles 集成状态
+ */
+export async function createBottle(
+  name: string,
+  environment: 'gaming' | 'application' | 'custom'
+): Promise<IntegrationStatus> {
+  return invoke<IntegrationStatus>('create_bottle', { name, environment })
+}
+
 /**
  * 获取 NW.js 稳定版信息
  */
@@ -248,10 +542,230 @@ export async function getNwjsStableInfo(): Promise<NwjsStableInfo> {
 }
 
 /**
- * 下载 NW.js 稳定版
+ * 获取 NW.js 全部版本（按版本号降序）
  */
-export async function downloadNwjsStable(flavor: 'normal' | 'sdk'): Promise<NwjsInstallResult> {
-  return invoke<NwjsInstallResult>('download_nwjs_stable', { flavor })
+export async function getNwjsVersions(): Promise<NwjsVersionInfo[]> {
+  return invoke<NwjsVersionInfo[]>('get_nwjs_versions')
+}
+
+/**
+ * 列出本地已安装的 NW.js 运行时及磁盘占用
+ */
+export async function listInstalledRuntimes(): Promise<NwjsInstalledRuntimes> {
+  return invoke<NwjsInstalledRuntimes>('list_installed_runtimes')
+}
+
+/**
+ * 检查已安装的 NW.js 运行时是否完整，启动前可据此提示重新下载
+ */
+export async function verifyRuntime(
+  version: string,
+  flavor: 'normal' | 'sdk'
+): Promise<NwjsRuntimeCheck> {
+  return invoke<NwjsRuntimeCheck>('verify_runtime', { version, flavor })
+}
+
+/**
+ * 取消正在进行或排队中的 NW.js 下载
+ */
+export async function cancelNwjsDownload(taskId: string): Promise<void> {
+  return invoke('cancel_nwjs_download', { taskId })
+}
+
+/**
+ * 列出进行中与排队中的下载任务
+ */
+export async function listDownloadTasks(): Promise<DownloadTaskInfo[]> {
+  return invoke<DownloadTaskInfo[]>('list_download_tasks')
+}
+
+/**
+ * 获取外部工具路径（wine、EasyRPG Player）
+ */
+export async function getExternalTools(): Promise<ExternalToolPaths> {
+  return invoke<ExternalToolPaths>('get_external_tools')
+}
+
+/**
+ * 设置外部工具路径（留空表示未配置）
+ */
+export async function setExternalTools(tools: ExternalToolPaths): Promise<void> {
+  return invoke('set_external_tools', { tools })
+}
+
+/**
+ * 是否允许执行游戏启动钩子
+ */
+export async function getAllowHooks(): Promise<boolean> {
+  return invoke<boolean>('get_allow_hooks')
+}
+
+/**
+ * 设置是否允许执行游戏启动钩子
+ */
+export async function setAllowHooks(allow: boolean): Promise<void> {
+  return invoke('set_allow_hooks', { allow })
+}
+
+/**
+ * 关闭主窗口时是否隐藏到托盘
+ */
+export async function getCloseToTray(): Promise<boolean> {
+  return invoke<boolean>('get_close_to_tray')
+}
+
+/**
+ * 设置关闭主窗口时是否隐藏到托盘
+ */
+export async function setCloseToTray(enabled: boolean): Promise<void> {
+  return invoke('set_close_to_tray', { enabled })
+}
+
+/**
+ * 启动游戏后是否最小化主窗口
+ */
+export async function getMinimizeOnLaunch(): Promise<boolean> {
+  return invoke<boolean>('get_minimize_on_launch')
+}
+
+/**
+ * 设置启动游戏后是否最小化主窗口（游戏配置中的设置优先）
+ */
+export async function setMinimizeOnLaunch(enabled: boolean): Promise<void> {
+  return invoke('set_minimize_on_launch', { enabled })
+}
+
+/**
+ * 是否在终端窗口中启动游戏
+ */
+export async function getLaunchInTerminal(): Promise<boolean> {
+  return invoke<boolean>('get_launch_in_terminal')
+}
+
+/**
+ * 设置是否在终端窗口中启动游戏（游戏配置中的设置优先）
+ */
+export async function setLaunchInTerminal(enabled: boolean): Promise<void> {
+  return invoke('set_launch_in_terminal', { enabled })
+}
+
+/**
+ * 获取同时运行游戏数的限制
+ */
+export async function getLaunchLimit(): Promise<LaunchLimit> {
+  return invoke<LaunchLimit>('get_launch_limit')
+}
+
+/**
+ * 设置同时运行游戏数的限制（对之后的启动生效）
+ */
+export async function setLaunchLimit(limit: LaunchLimit): Promise<void> {
+  return invoke('set_launch_limit', { limit })
+}
+
+/**
+ * 获取显示/隐藏主窗口的全局快捷键（未设置时为 null）
+ */
+export async function getGlobalShortcut(): Promise<string | null> {
+  return invoke<string | null>('get_global_shortcut')
+}
+
+/**
+ * 设置全局快捷键（如 CommandOrControl+Shift+G），传 null 停用
+ */
+export async function setGlobalShortcut(shortcut: string | null): Promise<void> {
+  return invoke('set_global_shortcut', { shortcut })
+}
+
+/**
+ * 获取用户自定义的扫描忽略规则（不含内置规则）
+ */
+export async function getScanIgnores(): Promise<string[]> {
+  return invoke<string[]>('get_scan_ignores')
+}
+
+/**
+ * 设置扫描忽略规则（按目录名匹配，支持 * 和 ? 通配符）
+ */
+export async function setScanIgnores(patterns: string[]): Promise<void> {
+  return invoke('set_scan_ignores', { patterns })
+}
+
+/**
+ * 获取某个引擎类型的默认启动参数
+ */
+export async function getEngineDefaults(engineType: string): Promise<string[]> {
+  return invoke<string[]>('get_engine_defaults', { engineType })
+}
+
+/**
+ * 设置某个引擎类型的默认启动参数（放在游戏参数之前，同名参数以游戏设置为准）
+ */
+export async function setEngineDefaults(engineType: string, args: string[]): Promise<void> {
+  return invoke('set_engine_defaults', { engineType, args })
+}
+
+/**
+ * 获取 NW.js 下载镜像
+ */
+export async function getNwjsMirror(): Promise<string> {
+  return invoke<string>('get_nwjs_mirror')
+}
+
+/**
+ * 设置 NW.js 下载镜像（空字符串恢复官方地址）
+ */
+export async function setNwjsMirror(mirror: string): Promise<void> {
+  return invoke('set_nwjs_mirror', { mirror })
+}
+
+/**
+ * 获取启动游戏时默认使用的 NW.js 版本类型
+ */
+export async function getNwjsDefaultFlavor(): Promise<'normal' | 'sdk'> {
+  return invoke<'normal' | 'sdk'>('get_nwjs_default_flavor')
+}
+
+/**
+ * 设置启动游戏时默认使用的 NW.js 版本类型（单个游戏可覆盖）
+ */
+export async function setNwjsDefaultFlavor(flavor: 'normal' | 'sdk'): Promise<void> {
+  return invoke('set_nwjs_default_flavor', { flavor })
+}
+
+/**
+ * 下载 NW.js 稳定版（skipChecksum 为 true 时跳过 SHA256 校验）
+ */
+export async function downloadNwjsStable(
+  flavor: 'normal' | 'sdk',
+  skipChecksum?: boolean,
+): Promise<NwjsInstallResult> {
+  return invoke<NwjsInstallResult>('download_nwjs_stable', { flavor, skipChecksum })
+}
+
+/**
+ * 下载指定版本的 NW.js（不清理其它版本），可同时发起多个，超出并发上限时排队
+ */
+export async function downloadNwjsVersion(
+  version: string,
+  flavor: 'normal' | 'sdk',
+  skipChecksum?: boolean,
+): Promise<NwjsInstallResult> {
+  return invoke<NwjsInstallResult>('download_nwjs_version', { version, flavor, skipChecksum })
+}
+
+/**
+ * 删除已安装的 NW.js 运行时
+ */
+export async function deleteNwjsRuntime(version: string, flavor: 'normal' | 'sdk'): Promise<void> {
+  return invoke('delete_nwjs_runtime', { version, flavor })
+}
+
+/**
+ * 为已安装的 NW.js 版本替换支持 MP4/H.264 的 ffmpeg，返回替换的运行时数量
+ */
+export async function downloadNwjsFfmpeg(version: string): Promise<number> {
+  return invoke<number>('download_nwjs_ffmpeg', { version })
 }
 
 /**
@@ -268,6 +782,27 @@ export async function cleanupUnusedContainers(): Promise<CleanupResult> {
   return invoke<CleanupResult>('cleanup_unused_containers')
 }
 
+/**
+ * 把旧版 UUID 命名的 profile 目录迁移为按游戏标题命名，返回重命名的数量
+ */
+export async function migrateProfiles(): Promise<number> {
+  return invoke<number>('migrate_profiles')
+}
+
+/**
+ * 备份数据库，未指定路径时写入应用数据目录，返回备份文件路径
+ */
+export async function backupDatabase(destPath?: string): Promise<string> {
+  return invoke<string>('backup_database', { destPath: destPath ?? null })
+}
+
+/**
+ * 从备份文件恢复数据库（恢复前会自动备份当前数据库）
+ */
+export async function restoreDatabase(srcPath: string): Promise<void> {
+  return invoke('restore_database', { srcPath })
+}
+
 // ============ mkxp-z 相关API ============
 
 /**
@@ -297,3 +832,17 @@ export async function setEngineEnabled(id: string, enabled: boolean): Promise<vo
 export async function getEngineProfileDetail(id: string): Promise<EngineProfileDetail> {
   return invoke<EngineProfileDetail>('get_engine_profile_detail', { id })
 }
+
+/**
+ * 检测目录（或可执行文件所在目录）的引擎类型及各候选引擎的置信度
+ */
+export async function detectEngine(path: string): Promise<EngineDetection> {
+  return invoke<EngineDetection>('detect_engine', { path })
+}
+
+/**
+ * 对游戏库中所有游戏重新检测引擎类型，跳过路径不存在或手动指定了引擎的游戏
+ */
+export async function redetectEngines(): Promise<RedetectEnginesResult> {
+  return invoke<RedetectEnginesResult>('redetect_engines')
+}
@@ -18,6 +18,10 @@ export interface EngineDto {
   engineType: string
   path: string
   installedAt: number
+  /** 运行时构建的目标平台（如 linux-x64），旧记录为空 */
+  target?: string | null
+  /** 安装目录占用空间（字节） */
+  sizeBytes?: number | null
 }
 
 export interface EngineUpdateInfo {
@@ -35,6 +39,45 @@ export interface EngineUpdateResult {
   installDir?: string
 }
 
+/** 引擎检测候选 */
+export interface EngineCandidate {
+  engineType: string
+  name: string
+  /** 置信度（0-100） */
+  confidence: number
+  /** 加分项原始得分 */
+  score: number
+}
+
+/** 引擎检测结果（candidates 按匹配程度从高到低排序） */
+export interface EngineDetection {
+  /** 最佳匹配的引擎 ID，未命中时为空 */
+  engineType?: string
+  confidence: number
+  candidates: EngineCandidate[]
+}
+
+/** 重新检测后引擎类型发生变化的游戏 */
+export interface EngineChange {
+  gameId: string
+  title: string
+  oldEngineType: string
+  newEngineType: string
+  /** 新检测结果的置信度（0-100） */
+  confidence: number
+}
+
+/** 批量重新检测引擎结果 */
+export interface RedetectEnginesResult {
+  /** 参与检测的游戏数 */
+  checked: number
+  /** 路径已不存在而跳过的游戏数 */
+  skippedMissing: number
+  /** 引擎类型由用户手动指定而跳过的游戏数 */
+  skippedUserSet: number
+  changed: EngineChange[]
+}
+
 /** 插件管理面板用的引擎详情 */
 export interface EngineDetail {
   id: string
@@ -0,0 +1,50 @@
+/**
+ * 后端命令错误
+ */
+export type AppErrorCode =
+  | 'not_found'
+  | 'runtime_missing'
+  | 'path_invalid'
+  | 'db'
+  | 'io'
+  | 'download'
+  | 'validation'
+  | 'engine_not_supported'
+  | 'other'
+
+/**
+ * 未识别目录时单个引擎的规则命中情况
+ */
+export interface EngineNearMiss {
+  engineType: string
+  name: string
+  /** 加分项得分 */
+  score: number
+  /** 匹配所需的最低加分项得分 */
+  minScore: number
+  markers: {
+    group: 'required' | 'optional' | 'forbidden'
+    ruleType: string
+    /** 规则检查的路径、模式或扩展名 */
+    target: string
+    found: boolean
+  }[]
+}
+
+/**
+ * 命令失败时后端返回的错误；code 为 validation 时 fields 列出失败的字段，
+ * 为 engine_not_supported 时 nearMisses 列出最接近的引擎（bestScore 为其中最高得分），
+ * path 为未识别的目录（导入压缩包时为解压后的目录）
+ */
+export interface AppError {
+  code: AppErrorCode
+  message: string
+  fields?: {
+    /** 字段名，如 entryPath、args[2] */
+    field: string
+    message: string
+  }[]
+  bestScore?: number | null
+  nearMisses?: EngineNearMiss[]
+  path?: string
+}
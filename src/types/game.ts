@@ -22,10 +22,22 @@ export interface GameDto {
   pathValid: boolean
   /** 运行时版本 */
   runtimeVersion?: string
-  /** 封面路径 */
+  /** 封面 URL（gmcover:// 自定义协议，可直接用于 img src） */
   coverPath?: string
+  /** 封面缩略图 URL（网格视图使用，缺失时回退到 coverPath） */
+  coverThumbPath?: string
   /** 游玩次数 */
   playCount: number
+  /** 累计游玩时长（毫秒） */
+  totalPlayMs: number
+  /** 标签 */
+  tags: string[]
+  /** 是否收藏 */
+  favorite: boolean
+  /** 评分（0-5，未评分为空） */
+  rating?: number
+  /** 完成状态 */
+  status: GameStatus
   /** 创建时间（Unix毫秒时间戳） */
   createdAt: number
   /** 最后游玩时间（Unix毫秒时间戳） */
@@ -34,6 +46,21 @@ export interface GameDto {
   updatedAt: number
 }
 
+/**
+ * 游戏完成状态
+ */
+export type GameStatus = '未开始' | '进行中' | '已通关' | '弃坑'
+
+/**
+ * 游戏列表排序字段
+ */
+export type GameSortBy = 'rating' | 'title' | 'lastPlayed' | 'createdAt'
+
+/**
+ * 排序方向
+ */
+export type SortDirection = 'asc' | 'desc'
+
 /**
  * 添加游戏输入
  */
@@ -80,6 +107,61 @@ export interface UpdateGameInput {
 export interface LaunchResult {
   /** 进程ID */
   pid: number
+  /** NW.js 远程调试端口（开启 devtools 且使用 SDK 版时），可打开 http://localhost:<port> */
+  devtoolsPort?: number | null
+  /** 能否跟踪游戏进程；在终端窗口中启动时为 false，pid 为终端进程，不记录游玩时长 */
+  tracked: boolean
+}
+
+/**
+ * 游戏存档备份
+ */
+export interface SaveBackupInfo {
+  /** 压缩包绝对路径 */
+  path: string
+  fileName: string
+  /** 文件大小（字节） */
+  size: number
+  /** 备份时间（Unix 毫秒） */
+  createdAt: number
+}
+
+/**
+ * 运行中的游戏实例
+ */
+export interface RunningGameInfo {
+  gameId: string
+  pid: number
+  /** 启动时间（Unix 毫秒），无法获取时为 0 */
+  startedAt: number
+  /** 为 false 表示应用重启前启动、重新找回的进程，不会收到退出事件 */
+  tracked: boolean
+}
+
+/**
+ * 主目录沙盒模式
+ */
+export type SandboxHomeMode = boolean | 'xdg-only'
+
+/**
+ * 保存游戏设置结果
+ */
+export interface SaveGameSettingsResult {
+  /** 已保存但需要提醒的设置，如关闭了沙盒主目录 */
+  warnings: {
+    field: string
+    message: string
+  }[]
+}
+
+/**
+ * 游戏磁盘占用（字节）
+ */
+export interface GameDiskUsage {
+  /** 游戏目录 */
+  gameBytes: number
+  /** profile 目录（存档、配置、Wine 前缀等） */
+  profileBytes: number
 }
 
 /**
@@ -90,6 +172,37 @@ export interface ScanGamesInput {
   root: string
   /** 最大扫描深度 */
   maxDepth: number
+  /** 仅检测并返回候选列表，不导入 */
+  dryRun?: boolean
+  /** 不跟随符号链接进入目录 */
+  skipSymlinks?: boolean
+  /** 增量扫描：跳过修改时间自上次扫描以来未变化的顶层目录 */
+  incremental?: boolean
+}
+
+/**
+ * 扫描识别出的候选游戏
+ */
+export interface ScanCandidate {
+  /** 游戏目录 */
+  path: string
+  /** 识别出的引擎类型 */
+  engineType: string
+  /** 检测置信度 */
+  confidence: number
+  /** 是否已在库中 */
+  existing: boolean
+}
+
+/**
+ * 批量导入游戏目录结果
+ */
+export interface ImportGameDirsResult {
+  imported: GameDto[]
+  /** 已在库中而跳过的路径 */
+  skipped: string[]
+  /** 导入失败的 [路径, 原因] */
+  failed: [string, string][]
 }
 
 /**
@@ -104,6 +217,12 @@ export interface ScanGamesResult {
   imported: number
   /** 已存在数量 */
   skippedExisting: number
+  /** 增量扫描中因未变化而跳过的顶层目录数 */
+  skippedUnchanged: number
+  /** 按引擎类型统计的发现游戏数 */
+  byEngine: Record<string, number>
+  /** 识别出的全部候选游戏（按扫描顺序） */
+  candidates: ScanCandidate[]
 }
 
 /**
@@ -117,15 +236,127 @@ export interface GameConfig {
   /** 运行时版本 */
   runtimeVersion?: string
   /** 启动运行器；auto 表示使用引擎插件默认策略 */
-  runner?: "auto" | "native" | "nwjs" | "mkxpz" | "bottles" | string
+  runner?: "auto" | "native" | "nwjs" | "mkxpz" | "bottles" | "proton" | string
   /** 启动参数 */
   args: string[]
-  /** 沙盒主目录 */
-  sandboxHome: boolean
+  /** 额外环境变量（值不做 shell 展开） */
+  env?: Record<string, string>
+  /** 沙盒主目录：true 隔离 HOME 与 XDG 目录，'xdg-only' 只隔离 XDG 目录，false 不隔离 */
+  sandboxHome: SandboxHomeMode
   /** 使用 Bottles 运行（仅 Other） */
   useBottles?: boolean
-  /** Bottles bottle 名称 */
+  /** Bottles bottle 名称，留空使用全局默认 bottle */
   bottleName?: string
+  /** Proton 版本（目录名），留空使用检测到的最新版本 */
+  protonVersion?: string | null
   /** 封面文件名 */
   coverFile?: string
+  /** Wine 前缀目录（留空使用 profile 下的默认前缀） */
+  winePrefix?: string
+  /** 允许同时运行多个实例 */
+  allowMultiple?: boolean
+  /** 强制区域设置（如 ja_JP.UTF-8），需宿主系统已安装该 locale */
+  locale?: string
+  /** Linux 上使用 gamemoderun 启动 */
+  useGamemode?: boolean
+  /** Linux 上启用 MangoHud 叠加层 */
+  useMangohud?: boolean
+  /** 启动前执行的命令（需在设置中允许钩子），非零退出码时中止启动 */
+  preLaunch?: string
+  /** 游戏退出后执行的命令（需在设置中允许钩子） */
+  postExit?: string
+  /** NW.js 版本类型（normal / sdk），留空使用全局默认 */
+  flavor?: 'normal' | 'sdk'
+  /** 启动时开启 NW.js 远程调试（需要 SDK 版运行时） */
+  devtools?: boolean
+  /** 游戏目录自带 NW.js 运行时（导入时检测），无需另行下载 */
+  selfContained?: boolean
+  /** 启动后最小化主窗口、退出后恢复；留空使用全局设置 */
+  minimizeOnLaunch?: boolean
+  /** 在终端窗口中启动以实时查看输出；留空使用全局设置 */
+  launchInTerminal?: boolean
+}
+
+/**
+ * 游戏库导入结果
+ */
+export interface ImportLibraryResult {
+  /** 新增的游戏数 */
+  added: number
+  /** 路径已存在而跳过的游戏数 */
+  skipped: number
+  /** 导入失败的游戏数 */
+  failed: number
+}
+
+/**
+ * 重复游戏合并结果
+ */
+export interface DeduplicateResult {
+  /** 存在重复的游戏组数 */
+  groups: number
+  /** 被合并删除的重复记录数 */
+  merged: number
+  /** 清理的重复 profile 目录数 */
+  profilesRemoved: number
+  /** 删除重复 profile 前创建的存档备份（位于保留记录的备份目录） */
+  saveBackups: string[]
+}
+
+/**
+ * 游戏退出事件（game_exited）
+ */
+export interface GameExitedEvent {
+  /** 游戏ID */
+  gameId: string
+  /** 进程ID */
+  pid: number
+  /** 退出码（被信号终止时为空） */
+  exitCode?: number | null
+  /** 运行时长（毫秒） */
+  durationMs: number
+}
+
+/**
+ * 启动包装工具缺失事件（launch_tool_missing），每个工具每次运行只提示一次
+ */
+export interface LaunchToolMissingEvent {
+  /** 游戏ID */
+  gameId: string
+  /** 缺失的程序名 */
+  tool: "gamemoderun" | "mangohud" | "nwjs-sdk" | string
+}
+
+/**
+ * 运行时与当前平台架构不一致事件（runtime_target_mismatch），游戏仍会尝试启动
+ */
+export interface RuntimeTargetMismatchEvent {
+  /** 游戏ID */
+  gameId: string
+  /** NW.js 版本 */
+  version: string
+  /** 运行时构建的目标平台（如 linux-x64） */
+  runtimeTarget: string
+  /** 当前平台 */
+  currentTarget: string
+}
+
+/**
+ * 启动排队事件（launch_queued）：已达同时运行上限，等待其它游戏退出
+ */
+export interface LaunchQueuedEvent {
+  /** 游戏ID */
+  gameId: string
+  /** 同时运行的游戏数上限 */
+  maxConcurrent: number
+}
+
+/**
+ * 排队的启动已实际开始事件（queued_launch_started）
+ */
+export interface QueuedLaunchStartedEvent {
+  /** 游戏ID */
+  gameId: string
+  /** 进程ID */
+  pid: number
 }
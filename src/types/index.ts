@@ -6,3 +6,4 @@ export * from './game'
 export * from './engine'
 export * from './settings'
 export * from './ui'
+export * from './error'
@@ -2,6 +2,8 @@
  * 设置相关类型定义
  */
 
+import type { EngineDto } from './engine'
+
 /**
  * 应用设置
  */
@@ -28,6 +30,88 @@ export interface NwjsStableInfo {
   sdkUrl: string
 }
 
+/**
+ * 外部工具路径（绝对路径或 PATH 中的程序名）
+ */
+export interface ExternalToolPaths {
+  /** wine 路径，为空时从 PATH 查找 */
+  wineBinary?: string
+  /** EasyRPG Player 路径，配置后 RPG Maker 2000/2003 游戏优先用它运行 */
+  easyrpgBinary?: string
+}
+
+/**
+ * 同时运行游戏数的限制
+ */
+export interface LaunchLimit {
+  /** 同时运行的游戏数上限，0 为不限制 */
+  maxConcurrent: number
+  /** 达到上限时排队，等其它游戏退出后自动启动；否则直接拒绝 */
+  queue: boolean
+}
+
+/**
+ * NW.js 版本信息
+ */
+export interface NwjsVersionInfo {
+  version: string
+  /** 本地是否已安装 */
+  installed: boolean
+}
+
+/**
+ * 下载任务状态
+ */
+export interface DownloadTaskInfo {
+  taskId: string
+  version: string
+  flavor: 'normal' | 'sdk'
+  target: string
+  /** queued 表示等待空闲的下载名额 */
+  stage: 'queued' | 'downloading' | 'extracting'
+  downloaded: number
+  total: number | null
+  /** 已请求取消 */
+  cancelled: boolean
+  /** 开始时间（Unix 毫秒） */
+  startedAt: number
+}
+
+/**
+ * 本地已安装的 NW.js 运行时
+ */
+export interface NwjsInstalledRuntime {
+  version: string
+  flavor: 'normal' | 'sdk'
+  target: string
+  installDir: string
+  /** 占用磁盘空间（字节） */
+  sizeBytes: number
+  /** 引擎列表中是否已登记（游戏只会使用已登记的运行时） */
+  registered: boolean
+  /** 已替换为支持 MP4/H.264 的 ffmpeg */
+  codecFfmpeg: boolean
+}
+
+/**
+ * 本地运行时列表及磁盘占用
+ */
+export interface NwjsInstalledRuntimes {
+  runtimes: NwjsInstalledRuntime[]
+  totalBytes: number
+  /** 已登记但安装目录不存在的引擎 ID */
+  missingEngineIds: string[]
+}
+
+/**
+ * NW.js 运行时完整性检查结果
+ */
+export interface NwjsRuntimeCheck {
+  ok: boolean
+  /** 缺失的文件或目录（相对安装目录；安装目录不存在时为其完整路径） */
+  missing: string[]
+}
+
 /**
  * NW.js 下载结果
  */
@@ -46,6 +130,22 @@ export interface CleanupResult {
   deleted: number
 }
 
+/**
+ * 运行环境诊断信息
+ */
+export interface DiagnosticsInfo {
+  os: string
+  arch: string
+  appVersion: string
+  containerRoot: string
+  dbPath: string
+  /** 已登记的运行时 */
+  installedRuntimes: EngineDto[]
+  bottlesInstalled: boolean
+  /** wine --version 输出，未安装时为空 */
+  wineVersion: string | null
+}
+
 /**
  * mkxp-z 导入结果
  */
@@ -73,6 +173,17 @@ export interface IntegrationStatus {
   options?: IntegrationOptions
 }
 
+/**
+ * 已安装的 Steam Proton 版本
+ */
+export interface ProtonVersion {
+  /** 版本目录名（如 Proton 9.0、GE-Proton9-20） */
+  name: string
+  path: string
+  /** 第三方兼容工具（compatibilitytools.d） */
+  custom: boolean
+}
+
 /**
  * 能力列表
  */